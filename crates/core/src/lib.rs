@@ -21,7 +21,7 @@ pub fn health_status(service: &'static str) -> HealthStatus<'static> {
     }
 }
 
-#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct ScreenPersonRequest {
     pub reference_id: Option<String>,
     #[validate(length(min = 1))]
@@ -33,6 +33,48 @@ pub struct ScreenPersonRequest {
     pub country: Option<String>,
     #[validate(length(min = 2, max = 2))]
     pub nationality: Option<String>,
+    /// Document numbers of the customer; an exact match is strong evidence
+    /// regardless of how well the name matches
+    #[serde(default)]
+    #[validate(length(max = 20), nested)]
+    pub identifiers: Vec<ScreenIdentifier>,
+}
+
+/// Kinds of identifier a subject can be matched on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierType {
+    Passport,
+    NationalId,
+    TaxId,
+    /// IMO ship identification number
+    Imo,
+    /// Legal Entity Identifier (ISO 17442)
+    Lei,
+    CryptoWallet,
+    Other,
+}
+
+impl IdentifierType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierType::Passport => "passport",
+            IdentifierType::NationalId => "national_id",
+            IdentifierType::TaxId => "tax_id",
+            IdentifierType::Imo => "imo",
+            IdentifierType::Lei => "lei",
+            IdentifierType::CryptoWallet => "crypto_wallet",
+            IdentifierType::Other => "other",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ScreenIdentifier {
+    #[serde(rename = "type")]
+    pub id_type: IdentifierType,
+    #[validate(length(min = 1, max = 128))]
+    pub number: String,
 }
 
 impl ScreenPersonRequest {
@@ -59,7 +101,7 @@ pub struct ScreenPersonResponse {
     pub checked_at: String,
 }
 
-#[derive(Clone, Debug, Serialize, ToSchema)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct Hit {
     pub subject_id: String,
     pub matched_name: String,
@@ -69,30 +111,90 @@ pub struct Hit {
     pub risk_level: RiskLevel,
     pub components: ScoreComponents,
     pub explanation: Vec<String>,
+    /// Set when the best-scoring name was one of the subject's aliases rather than its primary name
+    pub matched_alias: Option<MatchedAlias>,
+    /// Fingerprint of the listed subject's record; changes when a later ingest changes the record
+    pub record_version: Option<String>,
+    /// An analyst previously cleared this subject for this customer
+    pub suppressed: bool,
+    pub suppression_reason: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, ToSchema)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct MatchedAlias {
+    pub name: String,
+    pub alias_type: AliasType,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct ScoreComponents {
     pub name_similarity: f32,
     pub dob_similarity: f32,
     pub country_match: f32,
+    /// Every word of the input shares a Double Metaphone code with the matched name
+    pub phonetic_match: bool,
+    /// 1.0 when an input identifier equals one of the subject's, -1.0 when the
+    /// subject has a different identifier of the same type, 0.0 otherwise
+    #[serde(default)]
+    pub identifier_match: f32,
 }
 
 impl ScoreComponents {
-    pub fn explain(&self, matched_name: &str, country: Option<&str>) -> Vec<String> {
+    /// Reasons for the score, worded for the kind of subject matched: a
+    /// vessel's country is its flag and its identifier its IMO number. The
+    /// identifier and alias the match was made on, if any, come first.
+    pub fn explain(
+        &self,
+        kind: SubjectKind,
+        primary_name: &str,
+        country: Option<&str>,
+        matched_alias: Option<&MatchedAlias>,
+        matched_identifier: Option<&ScreenIdentifier>,
+    ) -> Vec<String> {
         let mut explanations = Vec::new();
-        
+
+        if let Some(id) = matched_identifier {
+            explanations.push(match id.id_type {
+                IdentifierType::Imo => format!("IMO number '{}' is listed for this vessel", id.number),
+                _ => format!("Identifier {} '{}' is listed for this subject", id.id_type.as_str(), id.number),
+            });
+        }
+        if let Some(alias) = matched_alias {
+            explanations.push(match (kind, alias.alias_type) {
+                (SubjectKind::Vessel | SubjectKind::Aircraft, AliasType::Fka) => {
+                    format!("Matched on a former name of '{}'", primary_name)
+                }
+                _ => format!("Matched on {} alias of '{}'", alias.alias_type.label(), primary_name),
+            });
+        }
+
+        let matched_name = matched_alias.map_or(primary_name, |alias| alias.name.as_str());
+        let name_label = match kind {
+            SubjectKind::Vessel => "Vessel name",
+            SubjectKind::Aircraft => "Aircraft name",
+            SubjectKind::Person | SubjectKind::Entity => "Name",
+        };
+
         if self.name_similarity >= 0.95 {
-            explanations.push(format!("Name '{}' is a very close match ({:.0}%)", matched_name, self.name_similarity * 100.0));
+            explanations.push(format!("{} '{}' is a very close match ({:.0}%)", name_label, matched_name, self.name_similarity * 100.0));
         } else if self.name_similarity >= 0.8 {
-            explanations.push(format!("Name '{}' is similar ({:.0}%)", matched_name, self.name_similarity * 100.0));
-        } else {
-            explanations.push(format!("Name '{}' partially matches ({:.0}%)", matched_name, self.name_similarity * 100.0));
+            explanations.push(format!("{} '{}' is similar ({:.0}%)", name_label, matched_name, self.name_similarity * 100.0));
+        } else if self.name_similarity > 0.0 || self.identifier_match <= 0.0 {
+            // Skipped when only the identifier was searched for
+            explanations.push(format!("{} '{}' partially matches ({:.0}%)", name_label, matched_name, self.name_similarity * 100.0));
+        }
+
+        if self.phonetic_match && self.name_similarity < 0.95 {
+            explanations.push(format!("{} sounds like '{}'", name_label, matched_name));
         }
         
         if self.country_match > 0.0 {
             if let Some(c) = country {
-                explanations.push(format!("Country '{}' matches", c));
+                explanations.push(match kind {
+                    SubjectKind::Vessel => format!("Flag '{}' matches", c),
+                    SubjectKind::Aircraft => format!("Registration country '{}' matches", c),
+                    SubjectKind::Person | SubjectKind::Entity => format!("Country '{}' matches", c),
+                });
             }
         }
         
@@ -101,34 +203,295 @@ impl ScoreComponents {
         } else if self.dob_similarity >= 0.5 {
             explanations.push("Year of birth is close".to_string());
         }
+
+        if self.identifier_match < 0.0 {
+            explanations.push(match kind {
+                SubjectKind::Vessel => "Listed IMO number differs".to_string(),
+                _ => "Listed identifier of the same type differs".to_string(),
+            });
+        }
         
         explanations
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, ToSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum RiskLevel {
     Hit,
     Review,
     None,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, ToSchema)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema)]
+pub enum AliasType {
+    Aka,
+    Fka,
+    LowQualityAka,
+}
+
+impl AliasType {
+    /// How list publishers write the alias type in prose
+    pub fn label(&self) -> &'static str {
+        match self {
+            AliasType::Aka => "a.k.a.",
+            AliasType::Fka => "f.k.a.",
+            AliasType::LowQualityAka => "low-quality a.k.a.",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum SubjectKind {
     Person,
     Entity,
+    /// A ship, identified by its IMO number
+    Vessel,
+    Aircraft,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, ToSchema)]
+impl SubjectKind {
+    /// Lower-case name used in the subject store and the index
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubjectKind::Person => "person",
+            SubjectKind::Entity => "entity",
+            SubjectKind::Vessel => "vessel",
+            SubjectKind::Aircraft => "aircraft",
+        }
+    }
+
+    /// The kind stored as `code`; the EU list calls entities "enterprise"
+    pub fn from_code(code: &str) -> Option<SubjectKind> {
+        match code.to_lowercase().as_str() {
+            "person" => Some(SubjectKind::Person),
+            "entity" | "enterprise" => Some(SubjectKind::Entity),
+            "vessel" => Some(SubjectKind::Vessel),
+            "aircraft" => Some(SubjectKind::Aircraft),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum HitSource {
     EuConsolidated,
     UnSc,
     Ofac,
     Uk,
+    Canada,
+    Switzerland,
+    Australia,
+    /// Members of the European Parliament
     PepEu,
+    PepEuCommission,
+    PepUsCongress,
+    PepUkParliament,
+    PepDeBundestag,
+    PepFrAssemblee,
+    PepNlTweedeKamer,
+    PepAtParliament,
+    PepBeParliament,
+    PepEsCongress,
+    /// Built-in test data used when no index is loaded
     Stub,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ListType {
+    Sanctions,
+    Pep,
+    Test,
+}
+
+/// What a source is and who publishes it
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct SourceInfo {
+    pub source: HitSource,
+    /// Source code the ingest binary stores subjects under
+    pub code: &'static str,
+    pub name: &'static str,
+    pub list_type: ListType,
+    /// ISO 3166 alpha-2 country, or "EU" / "UN" for supranational lists
+    pub jurisdiction: &'static str,
+    pub authority: &'static str,
+}
+
+impl HitSource {
+    /// Every source the ingest binary loads
+    pub const INGESTED: [HitSource; 17] = [
+        HitSource::EuConsolidated,
+        HitSource::UnSc,
+        HitSource::Ofac,
+        HitSource::Uk,
+        HitSource::Canada,
+        HitSource::Switzerland,
+        HitSource::Australia,
+        HitSource::PepEu,
+        HitSource::PepEuCommission,
+        HitSource::PepUsCongress,
+        HitSource::PepUkParliament,
+        HitSource::PepDeBundestag,
+        HitSource::PepFrAssemblee,
+        HitSource::PepNlTweedeKamer,
+        HitSource::PepAtParliament,
+        HitSource::PepBeParliament,
+        HitSource::PepEsCongress,
+    ];
+
+    /// Source code stored with ingested subjects
+    pub fn code(&self) -> &'static str {
+        self.info().code
+    }
+
+    /// The source stored under `code`, also accepting the older
+    /// `EU_CONSOLIDATED`, `UN_SC` and `PEP_EU` spellings
+    pub fn from_code(code: &str) -> Option<HitSource> {
+        let code = code.to_uppercase();
+        match code.as_str() {
+            "EU_CONSOLIDATED" => Some(HitSource::EuConsolidated),
+            "UN_SC" => Some(HitSource::UnSc),
+            "PEP_EU" => Some(HitSource::PepEu),
+            "STUB" => Some(HitSource::Stub),
+            _ => HitSource::INGESTED.into_iter().find(|s| s.code() == code),
+        }
+    }
+
+    pub fn info(&self) -> SourceInfo {
+        use ListType::{Pep, Sanctions};
+        let (code, name, list_type, jurisdiction, authority) = match self {
+            HitSource::EuConsolidated => (
+                "EU",
+                "EU Consolidated Financial Sanctions List",
+                Sanctions,
+                "EU",
+                "European Commission",
+            ),
+            HitSource::UnSc => (
+                "UN",
+                "UN Security Council Consolidated List",
+                Sanctions,
+                "UN",
+                "United Nations Security Council",
+            ),
+            HitSource::Ofac => (
+                "OFAC",
+                "Specially Designated Nationals and Blocked Persons List",
+                Sanctions,
+                "US",
+                "Office of Foreign Assets Control, US Department of the Treasury",
+            ),
+            HitSource::Uk => (
+                "UK",
+                "UK Sanctions List",
+                Sanctions,
+                "GB",
+                "Foreign, Commonwealth & Development Office",
+            ),
+            HitSource::Canada => (
+                "CANADA",
+                "Consolidated Canadian Autonomous Sanctions List",
+                Sanctions,
+                "CA",
+                "Global Affairs Canada",
+            ),
+            HitSource::Switzerland => (
+                "SWITZERLAND",
+                "Swiss Sanctions List",
+                Sanctions,
+                "CH",
+                "State Secretariat for Economic Affairs (SECO)",
+            ),
+            HitSource::Australia => (
+                "AUSTRALIA",
+                "Australian Consolidated Sanctions List",
+                Sanctions,
+                "AU",
+                "Department of Foreign Affairs and Trade",
+            ),
+            HitSource::PepEu => (
+                "PEP_EU_PARLIAMENT",
+                "Members of the European Parliament",
+                Pep,
+                "EU",
+                "European Parliament",
+            ),
+            HitSource::PepEuCommission => (
+                "PEP_EU_COMMISSION",
+                "Members of the European Commission",
+                Pep,
+                "EU",
+                "European Commission",
+            ),
+            HitSource::PepUsCongress => (
+                "PEP_US_CONGRESS",
+                "Members of the US Congress",
+                Pep,
+                "US",
+                "United States Congress",
+            ),
+            HitSource::PepUkParliament => (
+                "PEP_UK_PARLIAMENT",
+                "Members of the UK Parliament",
+                Pep,
+                "GB",
+                "UK Parliament",
+            ),
+            HitSource::PepDeBundestag => (
+                "PEP_DE_BUNDESTAG",
+                "Members of the German Bundestag",
+                Pep,
+                "DE",
+                "Deutscher Bundestag",
+            ),
+            HitSource::PepFrAssemblee => (
+                "PEP_FR_ASSEMBLEE",
+                "Members of the French Assemblée nationale",
+                Pep,
+                "FR",
+                "Assemblée nationale",
+            ),
+            HitSource::PepNlTweedeKamer => (
+                "PEP_NL_TWEEDE_KAMER",
+                "Members of the Dutch Tweede Kamer",
+                Pep,
+                "NL",
+                "Tweede Kamer der Staten-Generaal",
+            ),
+            HitSource::PepAtParliament => (
+                "PEP_AT",
+                "Members of the Austrian Parliament",
+                Pep,
+                "AT",
+                "Parlament Österreich",
+            ),
+            HitSource::PepBeParliament => (
+                "PEP_BE",
+                "Members of the Belgian Chamber of Representatives",
+                Pep,
+                "BE",
+                "Belgian Federal Parliament",
+            ),
+            HitSource::PepEsCongress => (
+                "PEP_ES",
+                "Members of the Spanish Congress of Deputies",
+                Pep,
+                "ES",
+                "Congreso de los Diputados",
+            ),
+            HitSource::Stub => ("STUB", "Built-in test data", ListType::Test, "", ""),
+        };
+        SourceInfo {
+            source: *self,
+            code,
+            name,
+            list_type,
+            jurisdiction,
+            authority,
+        }
+    }
+}
+
 pub fn new_request_id() -> String {
     Uuid::new_v4().to_string()
 }
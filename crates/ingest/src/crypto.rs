@@ -0,0 +1,154 @@
+use anyhow::Result;
+use matching_core::identifier::{crypto_chain, normalize_chain, normalize_crypto_address};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::loader::subject_id;
+use crate::parser_eu::ParsedSubject;
+
+/// A listed subject a digital currency address belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct CryptoAddressMatch {
+    pub subject_id: String,
+    pub primary_name: String,
+    pub kind: String,
+    pub source: String,
+    pub chain: String,
+    /// The address as the list publishes it
+    pub address: String,
+    pub record_version: Option<String>,
+}
+
+/// Replace the addresses stored for `source` with those of its latest load,
+/// so delisted addresses stop matching. Subjects must already be upserted.
+pub fn replace_crypto_addresses(conn: &Connection, source: &str, subjects: &[ParsedSubject]) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM crypto_address WHERE source = ?1", params![source])?;
+
+    let mut count = 0;
+    for subject in subjects {
+        let id = subject_id(source, &subject.source_ref);
+        for identifier in &subject.details.identifiers {
+            let Some(chain) = crypto_chain(&identifier.id_type) else {
+                continue;
+            };
+            count += tx.execute(
+                r#"INSERT OR IGNORE INTO crypto_address (address, chain, listed_address, subject_id, source)
+                VALUES (?1, ?2, ?3, ?4, ?5)"#,
+                params![
+                    normalize_crypto_address(&identifier.number),
+                    chain,
+                    identifier.number.trim(),
+                    &id,
+                    source,
+                ],
+            )?;
+        }
+    }
+    tx.commit()?;
+
+    tracing::info!(source, count, "stored digital currency addresses");
+    Ok(count)
+}
+
+/// Subjects listing `address`, on `chain` when one is given. A hex (`0x`)
+/// address is the same account on every EVM chain and for every token on it,
+/// and OFAC labels it with the token (an ERC-20 USDT address is listed under
+/// USDT, not ETH), so `chain` does not narrow those.
+pub fn find_crypto_address(conn: &Connection, address: &str, chain: Option<&str>) -> Result<Vec<CryptoAddressMatch>> {
+    let address = normalize_crypto_address(address);
+    let chain = chain.filter(|_| !address.starts_with("0x")).map(normalize_chain);
+    let mut stmt = conn.prepare(
+        r#"SELECT c.subject_id, s.primary_name, s.kind, c.source, c.chain, c.listed_address, s.record_hash
+        FROM crypto_address c
+        JOIN subject s ON s.id = c.subject_id
+        WHERE c.address = ?1 AND (?2 IS NULL OR c.chain = ?2)
+        ORDER BY c.subject_id, c.chain"#,
+    )?;
+    let matches = stmt
+        .query_map(
+            params![address, chain],
+            |row| {
+                Ok(CryptoAddressMatch {
+                    subject_id: row.get(0)?,
+                    primary_name: row.get(1)?,
+                    kind: row.get(2)?,
+                    source: row.get(3)?,
+                    chain: row.get(4)?,
+                    address: row.get(5)?,
+                    record_version: row.get(6)?,
+                })
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{init_schema, open_db};
+    use crate::loader::upsert_subjects;
+    use crate::parser_ofac::parse_ofac_xml;
+    use std::path::PathBuf;
+
+    const SDN: &str = r#"<?xml version="1.0"?>
+        <sdnList>
+            <sdnEntry>
+                <uid>25470</uid>
+                <lastName>SUEX OTC, S.R.O.</lastName>
+                <sdnType>Entity</sdnType>
+                <idList>
+                    <id>
+                        <uid>1</uid>
+                        <idType>Digital Currency Address - XBT</idType>
+                        <idNumber>12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx</idNumber>
+                    </id>
+                    <id>
+                        <uid>2</uid>
+                        <idType>Digital Currency Address - ETH</idType>
+                        <idNumber>0x2f389cE8bD8ff92De3402FFCe4691d17fC4f6535</idNumber>
+                    </id>
+                    <id>
+                        <uid>4</uid>
+                        <idType>Digital Currency Address - USDT</idType>
+                        <idNumber>0x6aCDFBA02D390b97Ac2b2d42A63E85293BCc160e</idNumber>
+                    </id>
+                    <id>
+                        <uid>3</uid>
+                        <idType>Registration Number</idType>
+                        <idNumber>07486049</idNumber>
+                    </id>
+                </idList>
+            </sdnEntry>
+        </sdnList>"#;
+
+    #[test]
+    fn ofac_addresses_are_looked_up_exactly() {
+        let conn = open_db(&PathBuf::from(":memory:")).unwrap();
+        init_schema(&conn).unwrap();
+        let subjects = parse_ofac_xml(SDN.as_bytes()).unwrap();
+        upsert_subjects(&conn, &subjects, "OFAC").unwrap();
+        assert_eq!(replace_crypto_addresses(&conn, "OFAC", &subjects).unwrap(), 3);
+
+        let found = find_crypto_address(&conn, "0x2F389CE8BD8FF92DE3402FFCE4691D17FC4F6535", None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].subject_id, "ofac_ofac_25470");
+        assert_eq!(found[0].primary_name, "SUEX OTC, S.R.O.");
+        assert_eq!(found[0].chain, "ETH");
+        assert_eq!(found[0].address, "0x2f389cE8bD8ff92De3402FFCe4691d17fC4f6535");
+
+        assert_eq!(find_crypto_address(&conn, "12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx", Some("btc")).unwrap().len(), 1);
+        assert!(find_crypto_address(&conn, "12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx", Some("ETH")).unwrap().is_empty());
+        // An ERC-20 token address is found when the caller names the host chain
+        let found = find_crypto_address(&conn, "0x6acdfba02d390b97ac2b2d42a63e85293bcc160e", Some("ETH")).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].chain, "USDT");
+        // Base58 addresses are case-sensitive
+        assert!(find_crypto_address(&conn, "12hqdsicffsbaydj6bhne22sfjtesmmzkx", None).unwrap().is_empty());
+
+        // A later load without the subject drops its addresses
+        assert_eq!(replace_crypto_addresses(&conn, "OFAC", &[]).unwrap(), 0);
+        assert!(find_crypto_address(&conn, "12HQDsicffSBaYdJ6BhnE22sfjTESmmzKx", None).unwrap().is_empty());
+    }
+}
@@ -12,6 +12,15 @@ CREATE TABLE IF NOT EXISTS subject (
     country TEXT,
     source TEXT NOT NULL,
     source_ref TEXT NOT NULL,
+    record_hash TEXT,
+    content_hash TEXT,
+    nationalities TEXT,
+    places_of_birth TEXT,
+    programs TEXT,
+    listed_on TEXT,
+    remarks TEXT,
+    vessel TEXT,
+    aircraft TEXT,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now'))
 );
@@ -24,6 +33,37 @@ CREATE TABLE IF NOT EXISTS subject_alias (
     UNIQUE(subject_id, name, alias_type)
 );
 
+CREATE TABLE IF NOT EXISTS subject_identifier (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_id TEXT NOT NULL REFERENCES subject(id) ON DELETE CASCADE,
+    id_type TEXT NOT NULL,
+    number TEXT NOT NULL,
+    country TEXT,
+    issued_on TEXT,
+    expires_on TEXT,
+    note TEXT
+);
+
+CREATE TABLE IF NOT EXISTS subject_address (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject_id TEXT NOT NULL REFERENCES subject(id) ON DELETE CASCADE,
+    street TEXT,
+    city TEXT,
+    region TEXT,
+    postal_code TEXT,
+    country TEXT
+);
+
+-- Digital currency addresses of listed subjects, replaced on every load of their source
+CREATE TABLE IF NOT EXISTS crypto_address (
+    address TEXT NOT NULL,
+    chain TEXT NOT NULL,
+    listed_address TEXT NOT NULL,
+    subject_id TEXT NOT NULL REFERENCES subject(id) ON DELETE CASCADE,
+    source TEXT NOT NULL,
+    PRIMARY KEY (address, chain, subject_id)
+);
+
 CREATE TABLE IF NOT EXISTS dataset_version (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     source TEXT NOT NULL,
@@ -36,6 +76,9 @@ CREATE INDEX IF NOT EXISTS idx_subject_source ON subject(source);
 CREATE INDEX IF NOT EXISTS idx_subject_name ON subject(primary_name);
 CREATE INDEX IF NOT EXISTS idx_alias_subject ON subject_alias(subject_id);
 CREATE INDEX IF NOT EXISTS idx_alias_name ON subject_alias(name);
+CREATE INDEX IF NOT EXISTS idx_identifier_subject ON subject_identifier(subject_id);
+CREATE INDEX IF NOT EXISTS idx_address_subject ON subject_address(subject_id);
+CREATE INDEX IF NOT EXISTS idx_crypto_address_source ON crypto_address(source);
 "#;
 
 pub fn open_db(path: &Path) -> Result<Connection> {
@@ -49,6 +92,27 @@ pub fn open_db(path: &Path) -> Result<Connection> {
 
 pub fn init_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch(SCHEMA)?;
+    // Columns added after the first release; CREATE TABLE IF NOT EXISTS leaves older DBs without them
+    add_column_if_missing(conn, "subject", "record_hash", "TEXT")?;
+    add_column_if_missing(conn, "subject", "content_hash", "TEXT")?;
+    // List-valued details are stored as JSON arrays, vessel and aircraft particulars as JSON objects
+    for column in ["nationalities", "places_of_birth", "programs", "listed_on", "remarks", "vessel", "aircraft"] {
+        add_column_if_missing(conn, "subject", column, "TEXT")?;
+    }
+    Ok(())
+}
+
+/// Add a column to an existing table unless a previous run already did.
+pub fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?
+        .iter()
+        .any(|name| name == column);
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))?;
+    }
     Ok(())
 }
 
@@ -60,6 +124,45 @@ pub fn record_dataset_version(conn: &Connection, source: &str, record_count: i64
     Ok(conn.last_insert_rowid())
 }
 
+/// Latest dataset_version id of every source, i.e. the list versions a
+/// screening run right now is matched against
+pub fn current_dataset_versions(conn: &Connection) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT MAX(id) FROM dataset_version GROUP BY source ORDER BY source"
+    )?;
+    let ids = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<i64>, _>>()?;
+    Ok(ids)
+}
+
+/// The most recent load of one source
+#[derive(Debug, Clone)]
+pub struct DatasetLoad {
+    pub source: String,
+    pub fetched_at: String,
+    pub record_count: i64,
+}
+
+/// Latest load of every source that has been ingested at least once
+pub fn latest_dataset_loads(conn: &Connection) -> Result<Vec<DatasetLoad>> {
+    let mut stmt = conn.prepare(
+        "SELECT source, fetched_at, record_count FROM dataset_version
+         WHERE id IN (SELECT MAX(id) FROM dataset_version GROUP BY source)
+         ORDER BY source"
+    )?;
+    let loads = stmt
+        .query_map([], |row| {
+            Ok(DatasetLoad {
+                source: row.get(0)?,
+                fetched_at: row.get(1)?,
+                record_count: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(loads)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,7 +179,20 @@ mod tests {
             .unwrap();
         assert_eq!(count, 0);
     }
-}
 
+    #[test]
+    fn current_versions_are_latest_per_source() {
+        let conn = open_db(&PathBuf::from(":memory:")).unwrap();
+        init_schema(&conn).unwrap();
+
+        record_dataset_version(&conn, "EU", 10, None).unwrap();
+        let un = record_dataset_version(&conn, "UN", 5, None).unwrap();
+        let eu = record_dataset_version(&conn, "EU", 12, None).unwrap();
 
+        assert_eq!(current_dataset_versions(&conn).unwrap(), vec![eu, un]);
 
+        let loads = latest_dataset_loads(&conn).unwrap();
+        assert_eq!(loads.len(), 2);
+        assert_eq!((loads[0].source.as_str(), loads[0].record_count), ("EU", 12));
+    }
+}
@@ -1,21 +1,29 @@
 use anyhow::{Context, Result};
+use matching_core::identifier::{classify, identifier_key};
+use matching_core::{normalize_name, phonetic_keys, transliterate_variants};
 use rusqlite::Connection;
 use std::path::Path;
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
-use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Schema, Value, STORED, STRING, TEXT};
 use tantivy::{doc, Index, IndexWriter, TantivyDocument};
-use unicode_normalization::UnicodeNormalization;
 
 pub struct SearchIndex {
     index: Index,
     pub subject_id: Field,
     pub primary_name: Field,
     pub aliases: Field,
+    pub alias_names: Field,
+    pub alias_types: Field,
+    pub translit: Field,
+    pub phonetic: Field,
     pub country: Field,
     pub dob_year: Field,
+    pub date_of_birth: Field,
     pub source: Field,
     pub kind: Field,
+    pub record_hash: Field,
+    pub identifiers: Field,
 }
 
 impl SearchIndex {
@@ -26,10 +34,24 @@ impl SearchIndex {
         let subject_id = schema_builder.add_text_field("subject_id", STRING | STORED);
         let primary_name = schema_builder.add_text_field("primary_name", TEXT | STORED);
         let aliases = schema_builder.add_text_field("aliases", TEXT);
+        // Original alias names and their types, stored in parallel order so a
+        // match can be traced back to the alias that produced it
+        let alias_names = schema_builder.add_text_field("alias_names", STORED);
+        let alias_types = schema_builder.add_text_field("alias_types", STORED);
+        // Latin renderings of the primary name and aliases (see matching_core::transliterate)
+        let translit = schema_builder.add_text_field("translit", TEXT);
+        // Double Metaphone codes of every name token, one untokenized value per code
+        let phonetic = schema_builder.add_text_field("phonetic", STRING);
         let country = schema_builder.add_text_field("country", STRING | STORED);
         let dob_year = schema_builder.add_text_field("dob_year", STRING | STORED);
+        // As listed: a full date, year-month or year
+        let date_of_birth = schema_builder.add_text_field("date_of_birth", STORED);
         let source = schema_builder.add_text_field("source", STRING | STORED);
         let kind = schema_builder.add_text_field("kind", STRING | STORED);
+        // Fingerprint of the subject record (see loader::record_hash)
+        let record_hash = schema_builder.add_text_field("record_hash", STORED);
+        // Exact-match keys of the subject's documents (see matching_core::identifier_key)
+        let identifiers = schema_builder.add_text_field("identifiers", STRING | STORED);
 
         let schema = schema_builder.build();
         let index = Index::create_in_dir(index_path, schema)
@@ -40,10 +62,17 @@ impl SearchIndex {
             subject_id,
             primary_name,
             aliases,
+            alias_names,
+            alias_types,
+            translit,
+            phonetic,
             country,
             dob_year,
+            date_of_birth,
             source,
             kind,
+            record_hash,
+            identifiers,
         })
     }
 
@@ -55,20 +84,41 @@ impl SearchIndex {
         let subject_id = schema.get_field("subject_id").unwrap();
         let primary_name = schema.get_field("primary_name").unwrap();
         let aliases = schema.get_field("aliases").unwrap();
+        let alias_names = schema.get_field("alias_names")
+            .context("index predates alias storage, re-run ingest")?;
+        let alias_types = schema.get_field("alias_types")
+            .context("index predates alias storage, re-run ingest")?;
+        let translit = schema.get_field("translit")
+            .context("index predates transliteration, re-run ingest")?;
+        let phonetic = schema.get_field("phonetic")
+            .context("index predates phonetic codes, re-run ingest")?;
         let country = schema.get_field("country").unwrap();
         let dob_year = schema.get_field("dob_year").unwrap();
+        let date_of_birth = schema.get_field("date_of_birth")
+            .context("index predates full birth dates, re-run ingest")?;
         let source = schema.get_field("source").unwrap();
         let kind = schema.get_field("kind").unwrap();
+        let record_hash = schema.get_field("record_hash")
+            .context("index predates record hashes, re-run ingest")?;
+        let identifiers = schema.get_field("identifiers")
+            .context("index predates identifiers, re-run ingest")?;
 
         Ok(Self {
             index,
             subject_id,
             primary_name,
             aliases,
+            alias_names,
+            alias_types,
+            translit,
+            phonetic,
             country,
             dob_year,
+            date_of_birth,
             source,
             kind,
+            record_hash,
+            identifiers,
         })
     }
 
@@ -77,11 +127,13 @@ impl SearchIndex {
         writer.delete_all_documents()?;
 
         let mut stmt = conn.prepare(
-            r#"SELECT s.id, s.primary_name, s.country, s.date_of_birth_year, s.source, s.kind,
-                      GROUP_CONCAT(a.name, ' ') as aliases
-               FROM subject s
-               LEFT JOIN subject_alias a ON a.subject_id = s.id
-               GROUP BY s.id"#
+            "SELECT id, primary_name, country, date_of_birth_year, source, kind, record_hash, date_of_birth FROM subject"
+        )?;
+        let mut alias_stmt = conn.prepare(
+            "SELECT name, alias_type FROM subject_alias WHERE subject_id = ?1 ORDER BY id"
+        )?;
+        let mut identifier_stmt = conn.prepare(
+            "SELECT id_type, number FROM subject_identifier WHERE subject_id = ?1"
         )?;
 
         let mut count = 0;
@@ -93,20 +145,61 @@ impl SearchIndex {
             let dob_year: Option<i32> = row.get(3)?;
             let source: String = row.get(4)?;
             let kind: String = row.get(5)?;
-            let aliases_str: Option<String> = row.get(6)?;
+            let record_hash: Option<String> = row.get(6)?;
+            let date_of_birth: Option<String> = row.get(7)?;
+
+            let aliases = alias_stmt
+                .query_map([&id], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut identifier_keys = identifier_stmt
+                .query_map([&id], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))?
+                .map(|r| r.map(|(id_type, number)| identifier_key(classify(&id_type), &number)))
+                .collect::<Result<Vec<_>, _>>()?;
+            identifier_keys.sort_unstable();
+            identifier_keys.dedup();
 
             let normalized_name = normalize_for_index(&name);
-            let normalized_aliases = aliases_str.as_ref().map(|a| normalize_for_index(a)).unwrap_or_default();
+            let normalized_aliases = aliases
+                .iter()
+                .map(|(alias, _)| normalize_for_index(alias))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let translit_variants = std::iter::once(name.as_str())
+                .chain(aliases.iter().map(|(alias, _)| alias.as_str()))
+                .flat_map(transliterate_variants)
+                .collect::<Vec<_>>();
+            let mut phonetic_codes = std::iter::once(normalized_name.as_str())
+                .chain(aliases.iter().map(|(alias, _)| alias.as_str()))
+                .chain(translit_variants.iter().map(String::as_str))
+                .flat_map(|n| phonetic_keys(&normalize_for_index(n)))
+                .collect::<Vec<_>>();
+            phonetic_codes.sort_unstable();
+            phonetic_codes.dedup();
 
-            writer.add_document(doc!(
+            let mut document = doc!(
                 self.subject_id => id,
                 self.primary_name => normalized_name,
                 self.aliases => normalized_aliases,
+                self.translit => translit_variants.join(" "),
                 self.country => country.unwrap_or_default(),
                 self.dob_year => dob_year.map(|y| y.to_string()).unwrap_or_default(),
+                self.date_of_birth => date_of_birth.unwrap_or_default(),
                 self.source => source,
                 self.kind => kind,
-            ))?;
+                self.record_hash => record_hash.unwrap_or_default(),
+            );
+            for (alias, alias_type) in aliases {
+                document.add_text(self.alias_names, alias);
+                document.add_text(self.alias_types, alias_type);
+            }
+            for code in phonetic_codes {
+                document.add_text(self.phonetic, code);
+            }
+            for key in identifier_keys {
+                document.add_text(self.identifiers, key);
+            }
+
+            writer.add_document(document)?;
             count += 1;
         }
 
@@ -119,8 +212,14 @@ impl SearchIndex {
         let reader = self.index.reader()?;
         let searcher = reader.searcher();
 
-        let normalized_query = normalize_for_index(query);
-        let query_parser = QueryParser::for_index(&self.index, vec![self.primary_name, self.aliases]);
+        let normalized_query = std::iter::once(normalize_for_index(query))
+            .chain(transliterate_variants(query))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let query_parser = QueryParser::for_index(
+            &self.index,
+            vec![self.primary_name, self.aliases, self.translit],
+        );
         
         let parsed_query = query_parser.parse_query(&normalized_query)
             .unwrap_or_else(|_| {
@@ -128,6 +227,14 @@ impl SearchIndex {
                 Box::new(tantivy::query::FuzzyTermQuery::new(term, 2, true))
             });
 
+        // Phonetic codes pull in spellings the parsed terms cannot reach
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![(Occur::Should, parsed_query)];
+        for code in phonetic_keys(&normalized_query) {
+            let term = tantivy::Term::from_field_text(self.phonetic, &code);
+            clauses.push((Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic))));
+        }
+        let parsed_query = BooleanQuery::new(clauses);
+
         let top_docs = searcher.search(&parsed_query, &TopDocs::with_limit(limit))?;
 
         let mut hits = Vec::new();
@@ -181,14 +288,10 @@ pub struct SearchHit {
     pub kind: String,
 }
 
+/// Index-side normalization; must stay identical to what the matching engine
+/// applies to queries, so it delegates to `matching_core::normalize_name`.
 fn normalize_for_index(s: &str) -> String {
-    s.nfd()
-        .filter(|c| !matches!(c, '\u{0300}'..='\u{036F}'))
-        .collect::<String>()
-        .to_lowercase()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+    normalize_name(s)
 }
 
 #[cfg(test)]
@@ -199,5 +302,89 @@ mod tests {
     fn normalize_strips_accents() {
         assert_eq!(normalize_for_index("Alvaro Nunez"), "alvaro nunez");
     }
-}
 
+    #[test]
+    fn cyrillic_query_finds_latin_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO subject (id, kind, primary_name, source, source_ref) VALUES ('eu_1', 'person', 'Vladimir Vladimirovich PUTIN', 'EU', '1')",
+            [],
+        )
+        .unwrap();
+
+        let index = SearchIndex::create(&dir.path().join("index")).unwrap();
+        index.build_from_db(&conn).unwrap();
+
+        let hits = index.search("Владимир Путин", 5).unwrap();
+        assert_eq!(hits.first().map(|h| h.subject_id.as_str()), Some("eu_1"));
+    }
+
+    #[test]
+    fn phonetic_spelling_finds_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO subject (id, kind, primary_name, source, source_ref) VALUES ('un_1', 'person', 'Muammar QADHAFI', 'UN', '1')",
+            [],
+        )
+        .unwrap();
+
+        let index = SearchIndex::create(&dir.path().join("index")).unwrap();
+        index.build_from_db(&conn).unwrap();
+
+        let hits = index.search("Kadafi", 5).unwrap();
+        assert_eq!(hits.first().map(|h| h.subject_id.as_str()), Some("un_1"));
+    }
+
+    #[test]
+    fn identifiers_match_exactly_whatever_the_name() {
+        use aegistry_core::{IdentifierType, ScreenIdentifier};
+        use matching_core::{MatchingEngine, ScoreThresholds, ScoreWeights, ScreeningQuery};
+
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO subject (id, kind, primary_name, source, source_ref) VALUES ('ofac_1', 'person', 'Uday HUSSEIN', 'OFAC', '1');
+             INSERT INTO subject (id, kind, primary_name, source, source_ref) VALUES ('ofac_2', 'person', 'John SMITH', 'OFAC', '2');
+             INSERT INTO subject_identifier (subject_id, id_type, number) VALUES ('ofac_1', 'Passport', 'A 1234567');
+             INSERT INTO subject_identifier (subject_id, id_type, number) VALUES ('ofac_2', 'Passport', 'C9999999');",
+        )
+        .unwrap();
+        let index_path = dir.path().join("index");
+        SearchIndex::create(&index_path).unwrap().build_from_db(&conn).unwrap();
+        let engine = MatchingEngine::open(&index_path, &dir.path().join("test.db")).unwrap();
+
+        let passport = |number: &str| ScreenIdentifier {
+            id_type: IdentifierType::Passport,
+            number: number.to_string(),
+        };
+
+        // The name alone would never surface this subject
+        let query = ScreeningQuery {
+            identifiers: vec![passport("a1234567")],
+            ..ScreeningQuery::name("Oday Husain Altikriti")
+        };
+        let results = engine.search_and_score(&query, 5, &ScoreWeights::default(), &ScoreThresholds::default()).unwrap();
+        let top = results.first().unwrap();
+        assert_eq!(top.subject_id, "ofac_1");
+        assert_eq!(top.components.identifier_match, 1.0);
+        assert!(top.score >= 0.95);
+
+        // Same name, different passport: the match is weakened
+        let plain = engine.search_and_score(&ScreeningQuery::name("John Smith"), 5, &ScoreWeights::default(), &ScoreThresholds::default()).unwrap();
+        let query = ScreeningQuery {
+            identifiers: vec![passport("A1234567")],
+            ..ScreeningQuery::name("John Smith")
+        };
+        let conflicting = engine.search_and_score(&query, 5, &ScoreWeights::default(), &ScoreThresholds::default()).unwrap();
+        let score_of = |results: &[matching_core::MatchResult]| {
+            results.iter().find(|r| r.subject_id == "ofac_2").map(|r| (r.score, r.components.identifier_match)).unwrap()
+        };
+        assert_eq!(score_of(&conflicting).1, -1.0);
+        assert!(score_of(&conflicting).0 < score_of(&plain).0);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod crypto;
 pub mod db;
 pub mod fetcher;
 pub mod indexer;
@@ -21,16 +22,22 @@ pub mod pep_austria;
 pub mod pep_belgium;
 pub mod pep_spain;
 
-pub use db::{init_schema, open_db, record_dataset_version};
+pub use crypto::{find_crypto_address, replace_crypto_addresses, CryptoAddressMatch};
+pub use db::{current_dataset_versions, init_schema, latest_dataset_loads, open_db, record_dataset_version, DatasetLoad};
 pub use fetcher::{compute_sha256, fetch_eu_sanctions_xml, fetch_ofac_sdn_xml, fetch_uk_sanctions_xml, fetch_un_sanctions_xml, fetch_canada_sanctions, fetch_switzerland_sanctions, fetch_australia_sanctions};
 pub use indexer::{SearchHit, SearchIndex};
-pub use loader::upsert_subjects;
+pub use loader::{get_subject, upsert_subjects, SubjectRecord};
 pub use monitoring::{
-    add_monitored_subject, compute_result_hash, get_all_active_subjects, get_monitored_subjects,
-    init_monitoring_schema, record_monitoring_result, remove_monitored_subject, MonitoredSubject,
-    MonitoringResult,
+    add_monitored_subject, compute_result_hash, diff_hits, get_all_active_subjects, get_monitored_subject,
+    get_monitored_subjects, get_previous_hits, init_monitoring_schema, list_monitored_subjects,
+    record_monitoring_result, remove_monitored_subject, set_subject_paused, upsert_monitored_subject,
+    upsert_monitored_subjects, ChangedHit, HitDiff, MonitoredSubject, MonitoringResult, SubjectAttributes,
+    SubjectFilter,
+};
+pub use parser_eu::{
+    parse_eu_xml, AircraftDetails, ParsedAddress, ParsedAlias, ParsedIdentifier, ParsedSubject, SubjectDetails, SubjectKind,
+    VesselDetails,
 };
-pub use parser_eu::{parse_eu_xml, ParsedAlias, ParsedSubject, SubjectKind};
 pub use parser_ofac::parse_ofac_xml;
 pub use parser_uk::parse_uk_xml;
 pub use parser_un::parse_un_xml;
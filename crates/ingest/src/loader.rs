@@ -1,31 +1,127 @@
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedAddress, ParsedAlias, ParsedIdentifier, ParsedSubject, SubjectDetails};
 use anyhow::Result;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Fingerprint of everything we store about a subject. It changes exactly when
+/// a later ingest changes the record, which is what invalidates analyst
+/// decisions (e.g. false-positive suppressions) made against the old one.
+pub fn record_hash(subject: &ParsedSubject) -> String {
+    let mut parts = base_parts(subject);
+    // Only appended when present so records without them keep the fingerprint
+    // they had before these fields were stored
+    if !subject.nationalities.is_empty() || subject.details != SubjectDetails::default() {
+        let mut nationalities = subject.nationalities.clone();
+        nationalities.sort();
+        parts.push(nationalities.join("\n"));
+        parts.push(serde_json::to_string(&subject.details).unwrap_or_default());
+    }
+    digest(&parts)
+}
+
+/// `record_hash` as computed before nationalities and details were stored
+fn legacy_record_hash(subject: &ParsedSubject) -> String {
+    digest(&base_parts(subject))
+}
+
+fn base_parts(subject: &ParsedSubject) -> Vec<String> {
+    let mut aliases: Vec<String> = subject
+        .aliases
+        .iter()
+        .map(|a| format!("{}|{}", a.alias_type, a.name))
+        .collect();
+    aliases.sort();
+
+    vec![
+        format!("{:?}", subject.kind),
+        subject.primary_name.clone(),
+        subject.date_of_birth.clone().unwrap_or_default(),
+        subject.country.clone().unwrap_or_default(),
+        aliases.join("\n"),
+    ]
+}
+
+fn digest(parts: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn json_list(values: &[String]) -> Option<String> {
+    if values.is_empty() {
+        None
+    } else {
+        serde_json::to_string(values).ok()
+    }
+}
+
+fn parse_json_list(value: Option<String>) -> Vec<String> {
+    value
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
+fn json_object<T: Serialize>(value: &Option<T>) -> Option<String> {
+    value.as_ref().and_then(|v| serde_json::to_string(v).ok())
+}
+
+fn parse_json_object<T: DeserializeOwned>(value: Option<String>) -> Option<T> {
+    value.and_then(|v| serde_json::from_str(&v).ok())
+}
+
+/// Id a subject from `source` is stored under
+pub fn subject_id(source: &str, source_ref: &str) -> String {
+    format!("{}_{}", source.to_lowercase(), source_ref)
+}
 
 pub fn upsert_subjects(conn: &Connection, subjects: &[ParsedSubject], source: &str) -> Result<usize> {
     let mut inserted = 0;
     let mut updated = 0;
 
     for subject in subjects {
-        let subject_id = format!("{}_{}", source.to_lowercase(), subject.source_ref);
-        let kind_str = match subject.kind {
-            SubjectKind::Person => "person",
-            SubjectKind::Entity => "entity",
-        };
+        let subject_id = subject_id(source, &subject.source_ref);
+        let kind_str = subject.kind.as_str();
+
+        let content_hash = record_hash(subject);
 
-        let exists: bool = conn.query_row(
-            "SELECT 1 FROM subject WHERE id = ?1",
+        let stored: Option<(Option<String>, Option<String>)> = conn.query_row(
+            "SELECT record_hash, content_hash FROM subject WHERE id = ?1",
             params![&subject_id],
-            |_| Ok(true),
-        ).unwrap_or(false);
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
 
-        if exists {
+        if let Some((stored_record, stored_content)) = stored {
+            // The published record_hash only moves when the content does. Rows
+            // written before content_hash existed were fingerprinted without
+            // nationalities and details, so picking those up is not a change.
+            let unchanged = match &stored_content {
+                Some(stored_content) => *stored_content == content_hash,
+                None => stored_record.as_deref() == Some(legacy_record_hash(subject).as_str()),
+            };
+            let record_hash = match stored_record {
+                Some(stored_record) if unchanged => stored_record,
+                _ => content_hash.clone(),
+            };
             conn.execute(
                 r#"UPDATE subject SET 
+                    kind = ?12,
                     primary_name = ?2,
                     date_of_birth = ?3,
                     date_of_birth_year = ?4,
                     country = ?5,
+                    record_hash = ?6,
+                    nationalities = ?7,
+                    places_of_birth = ?8,
+                    programs = ?9,
+                    listed_on = ?10,
+                    remarks = ?11,
+                    vessel = ?13,
+                    aircraft = ?14,
+                    content_hash = ?15,
                     updated_at = datetime('now')
                 WHERE id = ?1"#,
                 params![
@@ -34,13 +130,24 @@ pub fn upsert_subjects(conn: &Connection, subjects: &[ParsedSubject], source: &s
                     &subject.date_of_birth,
                     &subject.date_of_birth_year,
                     &subject.country,
+                    &record_hash,
+                    json_list(&subject.nationalities),
+                    json_list(&subject.details.places_of_birth),
+                    json_list(&subject.details.programs),
+                    &subject.details.listed_on,
+                    &subject.details.remarks,
+                    kind_str,
+                    json_object(&subject.details.vessel),
+                    json_object(&subject.details.aircraft),
+                    &content_hash,
                 ],
             )?;
             updated += 1;
         } else {
             conn.execute(
-                r#"INSERT INTO subject (id, kind, primary_name, date_of_birth, date_of_birth_year, country, source, source_ref)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+                r#"INSERT INTO subject (id, kind, primary_name, date_of_birth, date_of_birth_year, country, source, source_ref, record_hash,
+                    content_hash, nationalities, places_of_birth, programs, listed_on, remarks, vessel, aircraft)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"#,
                 params![
                     &subject_id,
                     kind_str,
@@ -50,6 +157,14 @@ pub fn upsert_subjects(conn: &Connection, subjects: &[ParsedSubject], source: &s
                     &subject.country,
                     source,
                     &subject.source_ref,
+                    &content_hash,
+                    json_list(&subject.nationalities),
+                    json_list(&subject.details.places_of_birth),
+                    json_list(&subject.details.programs),
+                    &subject.details.listed_on,
+                    &subject.details.remarks,
+                    json_object(&subject.details.vessel),
+                    json_object(&subject.details.aircraft),
                 ],
             )?;
             inserted += 1;
@@ -66,17 +181,153 @@ pub fn upsert_subjects(conn: &Connection, subjects: &[ParsedSubject], source: &s
                 params![&subject_id, &alias.name, &alias.alias_type],
             )?;
         }
+
+        conn.execute(
+            "DELETE FROM subject_identifier WHERE subject_id = ?1",
+            params![&subject_id],
+        )?;
+        for identifier in &subject.details.identifiers {
+            conn.execute(
+                r#"INSERT INTO subject_identifier (subject_id, id_type, number, country, issued_on, expires_on, note)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+                params![
+                    &subject_id,
+                    &identifier.id_type,
+                    &identifier.number,
+                    &identifier.country,
+                    &identifier.issued_on,
+                    &identifier.expires_on,
+                    &identifier.note,
+                ],
+            )?;
+        }
+
+        conn.execute(
+            "DELETE FROM subject_address WHERE subject_id = ?1",
+            params![&subject_id],
+        )?;
+        for address in &subject.details.addresses {
+            conn.execute(
+                r#"INSERT INTO subject_address (subject_id, street, city, region, postal_code, country)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+                params![
+                    &subject_id,
+                    &address.street,
+                    &address.city,
+                    &address.region,
+                    &address.postal_code,
+                    &address.country,
+                ],
+            )?;
+        }
     }
 
     tracing::info!(inserted, updated, "upserted subjects into database");
     Ok(inserted + updated)
 }
 
+/// A stored list entry with everything the source published about it.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubjectRecord {
+    pub id: String,
+    pub kind: String,
+    pub primary_name: String,
+    pub aliases: Vec<ParsedAlias>,
+    pub date_of_birth: Option<String>,
+    pub country: Option<String>,
+    pub nationalities: Vec<String>,
+    #[serde(flatten)]
+    pub details: SubjectDetails,
+    pub source: String,
+    pub source_ref: String,
+    pub record_version: Option<String>,
+    pub updated_at: String,
+}
+
+pub fn get_subject(conn: &Connection, subject_id: &str) -> Result<Option<SubjectRecord>> {
+    let record = conn
+        .query_row(
+            r#"SELECT id, kind, primary_name, date_of_birth, country, source, source_ref, record_hash, updated_at,
+                nationalities, places_of_birth, programs, listed_on, remarks, vessel, aircraft
+            FROM subject WHERE id = ?1"#,
+            params![subject_id],
+            |row| {
+                Ok(SubjectRecord {
+                    id: row.get(0)?,
+                    kind: row.get(1)?,
+                    primary_name: row.get(2)?,
+                    aliases: Vec::new(),
+                    date_of_birth: row.get(3)?,
+                    country: row.get(4)?,
+                    source: row.get(5)?,
+                    source_ref: row.get(6)?,
+                    record_version: row.get(7)?,
+                    updated_at: row.get(8)?,
+                    nationalities: parse_json_list(row.get(9)?),
+                    details: SubjectDetails {
+                        places_of_birth: parse_json_list(row.get(10)?),
+                        programs: parse_json_list(row.get(11)?),
+                        listed_on: row.get(12)?,
+                        remarks: row.get(13)?,
+                        vessel: parse_json_object(row.get(14)?),
+                        aircraft: parse_json_object(row.get(15)?),
+                        ..SubjectDetails::default()
+                    },
+                })
+            },
+        )
+        .optional()?;
+    let Some(mut record) = record else {
+        return Ok(None);
+    };
+
+    let mut stmt = conn.prepare("SELECT name, alias_type FROM subject_alias WHERE subject_id = ?1 ORDER BY id")?;
+    record.aliases = stmt
+        .query_map(params![subject_id], |row| {
+            Ok(ParsedAlias { name: row.get(0)?, alias_type: row.get(1)? })
+        })?
+        .collect::<Result<_, _>>()?;
+
+    let mut stmt = conn.prepare(
+        r#"SELECT id_type, number, country, issued_on, expires_on, note
+        FROM subject_identifier WHERE subject_id = ?1 ORDER BY id"#,
+    )?;
+    record.details.identifiers = stmt
+        .query_map(params![subject_id], |row| {
+            Ok(ParsedIdentifier {
+                id_type: row.get(0)?,
+                number: row.get(1)?,
+                country: row.get(2)?,
+                issued_on: row.get(3)?,
+                expires_on: row.get(4)?,
+                note: row.get(5)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT street, city, region, postal_code, country FROM subject_address WHERE subject_id = ?1 ORDER BY id",
+    )?;
+    record.details.addresses = stmt
+        .query_map(params![subject_id], |row| {
+            Ok(ParsedAddress {
+                street: row.get(0)?,
+                city: row.get(1)?,
+                region: row.get(2)?,
+                postal_code: row.get(3)?,
+                country: row.get(4)?,
+            })
+        })?
+        .collect::<Result<_, _>>()?;
+
+    Ok(Some(record))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::{init_schema, open_db};
-    use crate::parser_eu::ParsedAlias;
+    use crate::parser_eu::{SubjectKind, VesselDetails};
     use std::path::PathBuf;
 
     #[test]
@@ -96,6 +347,7 @@ mod tests {
             date_of_birth_year: Some(1980),
             country: Some("US".to_string()),
             nationalities: vec!["US".to_string()],
+            details: SubjectDetails::default(),
         }];
 
         let count = upsert_subjects(&conn, &subjects, "EU").unwrap();
@@ -104,5 +356,142 @@ mod tests {
         let count = upsert_subjects(&conn, &subjects, "EU").unwrap();
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn record_hash_tracks_content_changes() {
+        let mut subject = ParsedSubject {
+            source_ref: "test_001".to_string(),
+            kind: SubjectKind::Person,
+            primary_name: "Test Person".to_string(),
+            aliases: vec![
+                ParsedAlias { name: "TP".to_string(), alias_type: "aka".to_string() },
+                ParsedAlias { name: "T. Person".to_string(), alias_type: "aka".to_string() },
+            ],
+            date_of_birth: Some("1980-01-01".to_string()),
+            date_of_birth_year: Some(1980),
+            country: Some("US".to_string()),
+            nationalities: vec![],
+            details: SubjectDetails::default(),
+        };
+        let original = record_hash(&subject);
+
+        // Alias order is not part of the record
+        subject.aliases.reverse();
+        assert_eq!(record_hash(&subject), original);
+
+        subject.date_of_birth = Some("1981-01-01".to_string());
+        assert_ne!(record_hash(&subject), original);
+
+        let changed = record_hash(&subject);
+        subject.details.programs.push("IRQ".to_string());
+        assert_ne!(record_hash(&subject), changed);
+    }
+
+    #[test]
+    fn storing_details_for_a_legacy_row_keeps_its_record_hash() {
+        let conn = open_db(&PathBuf::from(":memory:")).unwrap();
+        init_schema(&conn).unwrap();
+        let stored_hash = |conn: &Connection| -> String {
+            conn.query_row("SELECT record_hash FROM subject WHERE id = 'eu_test_001'", [], |row| row.get(0))
+                .unwrap()
+        };
+
+        let mut subject = ParsedSubject {
+            source_ref: "test_001".to_string(),
+            kind: SubjectKind::Person,
+            primary_name: "Test Person".to_string(),
+            aliases: vec![],
+            date_of_birth: Some("1980-01-01".to_string()),
+            date_of_birth_year: Some(1980),
+            country: Some("US".to_string()),
+            nationalities: vec![],
+            details: SubjectDetails::default(),
+        };
+        upsert_subjects(&conn, &[subject.clone()], "EU").unwrap();
+        // A row written before content_hash existed
+        conn.execute("UPDATE subject SET content_hash = NULL", []).unwrap();
+        let legacy = stored_hash(&conn);
+
+        // The first ingest after the parser upgrade fills in nationalities and details
+        subject.nationalities.push("US".to_string());
+        subject.details.programs.push("IRQ".to_string());
+        upsert_subjects(&conn, &[subject.clone()], "EU").unwrap();
+        assert_eq!(stored_hash(&conn), legacy);
+
+        upsert_subjects(&conn, &[subject.clone()], "EU").unwrap();
+        assert_eq!(stored_hash(&conn), legacy);
+
+        // A real change to the record still moves the hash
+        subject.details.programs.push("SDGT".to_string());
+        upsert_subjects(&conn, &[subject.clone()], "EU").unwrap();
+        assert_eq!(stored_hash(&conn), record_hash(&subject));
+    }
+
+    #[test]
+    fn details_round_trip_through_the_store() {
+        let conn = open_db(&PathBuf::from(":memory:")).unwrap();
+        init_schema(&conn).unwrap();
+
+        let mut subject = ParsedSubject {
+            source_ref: "1234".to_string(),
+            kind: SubjectKind::Person,
+            primary_name: "Test Person".to_string(),
+            aliases: vec![ParsedAlias { name: "TP".to_string(), alias_type: "aka".to_string() }],
+            date_of_birth: Some("1980-01-01".to_string()),
+            date_of_birth_year: Some(1980),
+            country: Some("IQ".to_string()),
+            nationalities: vec!["IQ".to_string(), "SY".to_string()],
+            details: SubjectDetails {
+                identifiers: vec![ParsedIdentifier {
+                    id_type: "Passport".to_string(),
+                    number: "A1234567".to_string(),
+                    country: Some("IQ".to_string()),
+                    issued_on: Some("2010-05-01".to_string()),
+                    expires_on: None,
+                    note: None,
+                }],
+                addresses: vec![ParsedAddress {
+                    city: Some("Baghdad".to_string()),
+                    country: Some("IQ".to_string()),
+                    ..ParsedAddress::default()
+                }],
+                places_of_birth: vec!["Tikrit, Iraq".to_string()],
+                programs: vec!["IRAQ2".to_string()],
+                listed_on: Some("2003-07-08".to_string()),
+                remarks: Some("Former minister".to_string()),
+                ..SubjectDetails::default()
+            },
+        };
+        upsert_subjects(&conn, std::slice::from_ref(&subject), "OFAC").unwrap();
+
+        let record = get_subject(&conn, "ofac_1234").unwrap().unwrap();
+        assert_eq!(record.primary_name, "Test Person");
+        assert_eq!(record.aliases.len(), 1);
+        assert_eq!(record.nationalities, subject.nationalities);
+        assert_eq!(record.details, subject.details);
+        assert_eq!(record.record_version.as_deref(), Some(record_hash(&subject).as_str()));
+
+        // A re-ingest replaces the details rather than appending to them
+        subject.details.identifiers.clear();
+        upsert_subjects(&conn, std::slice::from_ref(&subject), "OFAC").unwrap();
+        let record = get_subject(&conn, "ofac_1234").unwrap().unwrap();
+        assert!(record.details.identifiers.is_empty());
+        assert_eq!(record.details.addresses.len(), 1);
+
+        // A later load can reclassify the subject, e.g. an entity that is really a ship
+        subject.kind = SubjectKind::Vessel;
+        subject.details.vessel = Some(VesselDetails {
+            flag: Some("Iran".to_string()),
+            call_sign: Some("9HA4512".to_string()),
+            ..VesselDetails::default()
+        });
+        upsert_subjects(&conn, std::slice::from_ref(&subject), "OFAC").unwrap();
+        let record = get_subject(&conn, "ofac_1234").unwrap().unwrap();
+        assert_eq!(record.kind, "vessel");
+        assert_eq!(record.details.vessel, subject.details.vessel);
+        assert!(record.details.aircraft.is_none());
+
+        assert!(get_subject(&conn, "ofac_missing").unwrap().is_none());
+    }
 }
 
@@ -3,19 +3,25 @@ use ingest::{
     compute_sha256, fetch_eu_commission, fetch_eu_parliament_meps, fetch_eu_sanctions_xml,
     fetch_ofac_sdn_xml, fetch_uk_sanctions_xml, fetch_un_sanctions_xml, init_monitoring_schema,
     init_schema, open_db, parse_eu_xml, parse_ofac_xml, parse_uk_xml, parse_un_xml,
-    record_dataset_version, upsert_subjects, SearchIndex,
+    record_dataset_version, replace_crypto_addresses, upsert_subjects, SearchIndex,
     fetch_us_congress, fetch_uk_parliament, fetch_german_bundestag,
     fetch_french_assemblee, fetch_dutch_tweede_kamer,
     fetch_austria_parliament, fetch_belgium_parliament, fetch_spain_congress,
     fetch_canada_sanctions, fetch_switzerland_sanctions, fetch_australia_sanctions,
     parse_canada_sanctions, parse_switzerland_sanctions, parse_australia_sanctions,
 };
-use std::path::PathBuf;
+use aegistry_core::HitSource;
+use matching_core::MatchingEngine;
+use std::path::{Path, PathBuf};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 const DATA_DIR: &str = "data";
 const DB_FILE: &str = "aegistry.db";
 const INDEX_DIR: &str = "index";
+/// Written by the screening API; holds each tenant's thresholds and weights
+const RISK_DB_FILE: &str = "risk.db";
+/// Written by the screening API; holds the hits tenants have cleared
+const TENANT_DB_FILE: &str = "tenants.db";
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -231,15 +237,30 @@ async fn main() -> Result<()> {
 
     // Re-screen all monitored subjects to detect changes
     tracing::info!("re-screening monitored subjects...");
-    re_screen_monitored_subjects(&conn, &index)?;
+    re_screen_monitored_subjects(
+        &conn,
+        &index_path,
+        &db_path,
+        &data_path.join(RISK_DB_FILE),
+        &data_path.join(TENANT_DB_FILE),
+    )?;
 
     tracing::info!("ingest complete");
     Ok(())
 }
 
-fn re_screen_monitored_subjects(conn: &rusqlite::Connection, index: &ingest::indexer::SearchIndex) -> Result<()> {
-    use ingest::monitoring::{get_all_active_subjects, record_monitoring_result, compute_result_hash};
-    
+fn re_screen_monitored_subjects(
+    conn: &rusqlite::Connection,
+    index_path: &Path,
+    db_path: &Path,
+    risk_db_path: &Path,
+    tenant_db_path: &Path,
+) -> Result<()> {
+    use ingest::monitoring::{
+        detect_change, get_all_active_subjects, hits_result_hash, load_risk_configs, record_monitoring_result,
+        screen_monitored_subject, Suppressions,
+    };
+
     let subjects = match get_all_active_subjects(conn) {
         Ok(s) => s,
         Err(e) => {
@@ -253,41 +274,69 @@ fn re_screen_monitored_subjects(conn: &rusqlite::Connection, index: &ingest::ind
         return Ok(());
     }
 
+    let engine = MatchingEngine::open(index_path, db_path)?;
+
+    // Tenants without a stored config (or a missing risk DB) use the API defaults
+    let risk_configs = if risk_db_path.exists() {
+        match rusqlite::Connection::open(risk_db_path).map_err(anyhow::Error::from).and_then(|c| load_risk_configs(&c)) {
+            Ok(configs) => configs,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load tenant risk configs, using defaults");
+                Default::default()
+            }
+        }
+    } else {
+        Default::default()
+    };
+
+    // Cleared hits stay out of the hash too, so they never register as a change
+    let suppressions = if tenant_db_path.exists() {
+        match rusqlite::Connection::open(tenant_db_path).map_err(anyhow::Error::from).and_then(|c| Suppressions::load(&c)) {
+            Ok(suppressions) => suppressions,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load suppressions, re-screening without them");
+                Suppressions::default()
+            }
+        }
+    } else {
+        Suppressions::default()
+    };
+
     tracing::info!(count = subjects.len(), "re-screening monitored subjects");
 
     for subject in subjects {
-        // Perform search
-        let search_hits = match index.search(&subject.name, 10) {
+        let risk = risk_configs.get(&subject.tenant_id).copied().unwrap_or_default();
+        // A failed search says nothing about the subject; recording it would
+        // read as every hit removed
+        let hits = match screen_monitored_subject(&engine, &subject, &risk, &suppressions) {
             Ok(hits) => hits,
             Err(e) => {
-                tracing::warn!(error = %e, reference_id = %subject.reference_id, "search failed");
+                tracing::warn!(error = %e, reference_id = %subject.reference_id, "failed to re-screen monitored subject");
                 continue;
             }
         };
 
-        // Build hit data for hash computation
-        let hit_data: Vec<(String, f32)> = search_hits
-            .iter()
-            .map(|h| (h.subject_id.clone(), 0.9)) // Simplified score
-            .collect();
-        
-        let new_hash = compute_result_hash(&hit_data);
-        let has_changes = subject.last_result_hash.as_ref().map(|h| h != &new_hash).unwrap_or(true);
-        
+        let new_hash = hits_result_hash(&hits);
+        let has_changes = detect_change(conn, &subject, &new_hash).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, reference_id = %subject.reference_id, "failed to compare with the last result");
+            false
+        });
+
         // Record result
         if let Err(e) = record_monitoring_result(
             conn,
             subject.id,
             &new_hash,
-            search_hits.len(),
-            search_hits.first().map(|_| 0.9).unwrap_or(0.0),
+            hits.len(),
+            hits.iter().map(|h| h.score).fold(0.0, f32::max),
             has_changes,
+            Some(&hits),
         ) {
             tracing::warn!(error = %e, reference_id = %subject.reference_id, "failed to record monitoring result");
         } else if has_changes {
             tracing::info!(
                 reference_id = %subject.reference_id,
-                hit_count = search_hits.len(),
+                hit_count = hits.len(),
                 "detected changes for monitored subject"
             );
         }
@@ -306,8 +355,8 @@ async fn fetch_and_ingest_eu(conn: &rusqlite::Connection) -> Result<usize> {
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "EU")?;
-    record_dataset_version(conn, "EU", count as i64, Some(&file_hash))?;
+    let count = upsert_subjects(conn, &subjects, HitSource::EuConsolidated.code())?;
+    record_dataset_version(conn, HitSource::EuConsolidated.code(), count as i64, Some(&file_hash))?;
     Ok(count)
 }
 
@@ -322,8 +371,8 @@ async fn fetch_and_ingest_un(conn: &rusqlite::Connection) -> Result<usize> {
     }
 
     // Convert to ParsedSubject format
-    let count = upsert_subjects(conn, &subjects, "UN")?;
-    record_dataset_version(conn, "UN", count as i64, Some(&file_hash))?;
+    let count = upsert_subjects(conn, &subjects, HitSource::UnSc.code())?;
+    record_dataset_version(conn, HitSource::UnSc.code(), count as i64, Some(&file_hash))?;
     Ok(count)
 }
 
@@ -337,8 +386,9 @@ async fn fetch_and_ingest_ofac(conn: &rusqlite::Connection) -> Result<usize> {
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "OFAC")?;
-    record_dataset_version(conn, "OFAC", count as i64, Some(&file_hash))?;
+    let count = upsert_subjects(conn, &subjects, HitSource::Ofac.code())?;
+    replace_crypto_addresses(conn, HitSource::Ofac.code(), &subjects)?;
+    record_dataset_version(conn, HitSource::Ofac.code(), count as i64, Some(&file_hash))?;
     Ok(count)
 }
 
@@ -352,8 +402,8 @@ async fn fetch_and_ingest_uk(conn: &rusqlite::Connection) -> Result<usize> {
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "UK")?;
-    record_dataset_version(conn, "UK", count as i64, Some(&file_hash))?;
+    let count = upsert_subjects(conn, &subjects, HitSource::Uk.code())?;
+    record_dataset_version(conn, HitSource::Uk.code(), count as i64, Some(&file_hash))?;
     Ok(count)
 }
 
@@ -364,8 +414,8 @@ async fn fetch_and_ingest_eu_parliament(conn: &rusqlite::Connection) -> Result<u
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_EU_PARLIAMENT")?;
-    record_dataset_version(conn, "PEP_EU_PARLIAMENT", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepEu.code())?;
+    record_dataset_version(conn, HitSource::PepEu.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -376,8 +426,8 @@ async fn fetch_and_ingest_eu_commission(conn: &rusqlite::Connection) -> Result<u
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_EU_COMMISSION")?;
-    record_dataset_version(conn, "PEP_EU_COMMISSION", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepEuCommission.code())?;
+    record_dataset_version(conn, HitSource::PepEuCommission.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -388,8 +438,8 @@ async fn fetch_and_ingest_us_congress(conn: &rusqlite::Connection) -> Result<usi
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_US_CONGRESS")?;
-    record_dataset_version(conn, "PEP_US_CONGRESS", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepUsCongress.code())?;
+    record_dataset_version(conn, HitSource::PepUsCongress.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -400,8 +450,8 @@ async fn fetch_and_ingest_uk_parliament(conn: &rusqlite::Connection) -> Result<u
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_UK_PARLIAMENT")?;
-    record_dataset_version(conn, "PEP_UK_PARLIAMENT", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepUkParliament.code())?;
+    record_dataset_version(conn, HitSource::PepUkParliament.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -412,8 +462,8 @@ async fn fetch_and_ingest_german_bundestag(conn: &rusqlite::Connection) -> Resul
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_DE_BUNDESTAG")?;
-    record_dataset_version(conn, "PEP_DE_BUNDESTAG", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepDeBundestag.code())?;
+    record_dataset_version(conn, HitSource::PepDeBundestag.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -424,8 +474,8 @@ async fn fetch_and_ingest_french_assemblee(conn: &rusqlite::Connection) -> Resul
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_FR_ASSEMBLEE")?;
-    record_dataset_version(conn, "PEP_FR_ASSEMBLEE", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepFrAssemblee.code())?;
+    record_dataset_version(conn, HitSource::PepFrAssemblee.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -436,8 +486,8 @@ async fn fetch_and_ingest_dutch_tweede_kamer(conn: &rusqlite::Connection) -> Res
         return Ok(0);
     }
 
-    let count = upsert_subjects(conn, &subjects, "PEP_NL_TWEEDE_KAMER")?;
-    record_dataset_version(conn, "PEP_NL_TWEEDE_KAMER", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepNlTweedeKamer.code())?;
+    record_dataset_version(conn, HitSource::PepNlTweedeKamer.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -446,7 +496,8 @@ async fn fetch_and_ingest_austria(conn: &rusqlite::Connection) -> Result<usize>
     if subjects.is_empty() {
         return Ok(0);
     }
-    let count = upsert_subjects(conn, &subjects, "PEP_AT")?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepAtParliament.code())?;
+    record_dataset_version(conn, HitSource::PepAtParliament.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -455,7 +506,8 @@ async fn fetch_and_ingest_belgium(conn: &rusqlite::Connection) -> Result<usize>
     if subjects.is_empty() {
         return Ok(0);
     }
-    let count = upsert_subjects(conn, &subjects, "PEP_BE")?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepBeParliament.code())?;
+    record_dataset_version(conn, HitSource::PepBeParliament.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -464,7 +516,8 @@ async fn fetch_and_ingest_spain(conn: &rusqlite::Connection) -> Result<usize> {
     if subjects.is_empty() {
         return Ok(0);
     }
-    let count = upsert_subjects(conn, &subjects, "PEP_ES")?;
+    let count = upsert_subjects(conn, &subjects, HitSource::PepEsCongress.code())?;
+    record_dataset_version(conn, HitSource::PepEsCongress.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -477,8 +530,8 @@ async fn fetch_and_ingest_canada(conn: &rusqlite::Connection) -> Result<usize> {
     if subjects.is_empty() {
         return Ok(0);
     }
-    let count = upsert_subjects(conn, &subjects, "CANADA")?;
-    record_dataset_version(conn, "CANADA", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::Canada.code())?;
+    record_dataset_version(conn, HitSource::Canada.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -491,8 +544,8 @@ async fn fetch_and_ingest_switzerland(conn: &rusqlite::Connection) -> Result<usi
     if subjects.is_empty() {
         return Ok(0);
     }
-    let count = upsert_subjects(conn, &subjects, "SWITZERLAND")?;
-    record_dataset_version(conn, "SWITZERLAND", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::Switzerland.code())?;
+    record_dataset_version(conn, HitSource::Switzerland.code(), count as i64, None)?;
     Ok(count)
 }
 
@@ -505,8 +558,8 @@ async fn fetch_and_ingest_australia(conn: &rusqlite::Connection) -> Result<usize
     if subjects.is_empty() {
         return Ok(0);
     }
-    let count = upsert_subjects(conn, &subjects, "AUSTRALIA")?;
-    record_dataset_version(conn, "AUSTRALIA", count as i64, None)?;
+    let count = upsert_subjects(conn, &subjects, HitSource::Australia.code())?;
+    record_dataset_version(conn, HitSource::Australia.code(), count as i64, None)?;
     Ok(count)
 }
 
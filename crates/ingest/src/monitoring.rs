@@ -1,5 +1,9 @@
+use aegistry_core::{Hit, RiskLevel, SubjectKind};
 use anyhow::Result;
-use rusqlite::Connection;
+use matching_core::{BirthDate, MatchingEngine, ScoreThresholds, ScoreWeights, ScreeningQuery};
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Subject that needs to be re-screened when lists update
 #[derive(Debug, Clone)]
@@ -13,6 +17,46 @@ pub struct MonitoredSubject {
     pub last_screened_at: String,
     pub last_result_hash: Option<String>,
     pub callback_url: Option<String>,
+    pub kind: SubjectKind,
+    /// Full or partial date as supplied (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`)
+    pub date_of_birth: Option<String>,
+    pub nationality: Option<String>,
+    /// Tenant-defined key/value pairs carried along for the tenant's own use
+    pub metadata: BTreeMap<String, String>,
+    /// Paused subjects stay enrolled but are not re-screened
+    pub paused: bool,
+    /// When re-screening last detected a change
+    pub last_changed_at: Option<String>,
+}
+
+impl MonitoredSubject {
+    /// The most precise date of birth known for the subject
+    pub fn birth_date(&self) -> Option<BirthDate> {
+        self.date_of_birth
+            .as_deref()
+            .and_then(BirthDate::parse)
+            .or(self.dob_year.map(BirthDate::year))
+    }
+}
+
+/// What a tenant supplies when enrolling or updating a monitored subject
+#[derive(Debug, Clone)]
+pub struct SubjectAttributes {
+    pub name: String,
+    pub kind: SubjectKind,
+    pub country: Option<String>,
+    pub date_of_birth: Option<String>,
+    pub nationality: Option<String>,
+    pub callback_url: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Narrows `list_monitored_subjects`; unset fields don't filter
+#[derive(Debug, Clone, Default)]
+pub struct SubjectFilter {
+    /// Only subjects whose last detected change is at or after this SQLite datetime
+    pub changed_since: Option<String>,
+    pub paused: Option<bool>,
 }
 
 /// Result of a monitoring check
@@ -24,6 +68,33 @@ pub struct MonitoringResult {
     pub new_result_hash: String,
     pub hit_count: usize,
     pub highest_score: f32,
+    /// Full hit set of the screening; `None` for results recorded before hits were stored
+    pub hits: Option<Vec<Hit>>,
+}
+
+/// What changed between two screenings of the same monitored subject
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HitDiff {
+    pub added: Vec<Hit>,
+    pub removed: Vec<Hit>,
+    pub changed: Vec<ChangedHit>,
+}
+
+/// A listed subject matched by both screenings with a different score or risk level
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedHit {
+    pub subject_id: String,
+    pub previous_score: f32,
+    pub current_score: f32,
+    pub previous_risk_level: RiskLevel,
+    pub current_risk_level: RiskLevel,
+    pub hit: Hit,
+}
+
+impl HitDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
 }
 
 /// Initialize monitoring tables in the database
@@ -63,11 +134,68 @@ pub fn init_monitoring_schema(conn: &Connection) -> Result<()> {
             ON monitoring_result(subject_id, screened_at DESC);
         "
     )?;
+    crate::db::add_column_if_missing(conn, "monitoring_result", "hits", "TEXT")?;
+    crate::db::add_column_if_missing(conn, "monitored_subject", "kind", "TEXT NOT NULL DEFAULT 'person'")?;
+    crate::db::add_column_if_missing(conn, "monitored_subject", "date_of_birth", "TEXT")?;
+    crate::db::add_column_if_missing(conn, "monitored_subject", "nationality", "TEXT")?;
+    crate::db::add_column_if_missing(conn, "monitored_subject", "metadata", "TEXT")?;
+    crate::db::add_column_if_missing(conn, "monitored_subject", "paused", "INTEGER NOT NULL DEFAULT 0")?;
+    crate::db::add_column_if_missing(conn, "monitored_subject", "last_changed_at", "TEXT")?;
+    crate::db::add_column_if_missing(conn, "monitoring_result", "recorded", "INTEGER NOT NULL DEFAULT 0")?;
 
     tracing::info!("monitoring schema initialized");
     Ok(())
 }
 
+/// Columns read into a `MonitoredSubject`, in `subject_from_row` order
+const SUBJECT_COLUMNS: [&str; 15] = [
+    "id",
+    "tenant_id",
+    "reference_id",
+    "name",
+    "country",
+    "dob_year",
+    "last_screened_at",
+    "last_result_hash",
+    "callback_url",
+    "kind",
+    "date_of_birth",
+    "nationality",
+    "metadata",
+    "paused",
+    "last_changed_at",
+];
+
+fn subject_columns(alias: &str) -> String {
+    SUBJECT_COLUMNS
+        .iter()
+        .map(|c| format!("{}.{}", alias, c))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn subject_from_row(row: &Row) -> rusqlite::Result<MonitoredSubject> {
+    let kind: String = row.get(9)?;
+    let metadata: Option<String> = row.get(12)?;
+    Ok(MonitoredSubject {
+        id: row.get(0)?,
+        tenant_id: row.get(1)?,
+        reference_id: row.get(2)?,
+        name: row.get(3)?,
+        country: row.get(4)?,
+        dob_year: row.get(5)?,
+        last_screened_at: row.get(6)?,
+        last_result_hash: row.get(7)?,
+        callback_url: row.get(8)?,
+        kind: SubjectKind::from_code(&kind).unwrap_or(SubjectKind::Person),
+        date_of_birth: row.get(10)?,
+        nationality: row.get(11)?,
+        metadata: metadata.and_then(|m| serde_json::from_str(&m).ok()).unwrap_or_default(),
+        paused: row.get::<_, i32>(13)? == 1,
+        last_changed_at: row.get(14)?,
+    })
+}
+
 /// Add a subject to monitoring
 pub fn add_monitored_subject(
     conn: &Connection,
@@ -78,16 +206,90 @@ pub fn add_monitored_subject(
     dob_year: Option<i32>,
     callback_url: Option<&str>,
 ) -> Result<i64> {
-    conn.execute(
-        "INSERT OR REPLACE INTO monitored_subject 
-         (tenant_id, reference_id, name, country, dob_year, callback_url, active)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1)",
-        rusqlite::params![tenant_id, reference_id, name, country, dob_year, callback_url],
+    let attributes = SubjectAttributes {
+        name: name.to_string(),
+        kind: SubjectKind::Person,
+        country: country.map(str::to_string),
+        date_of_birth: dob_year.map(|y| y.to_string()),
+        nationality: None,
+        callback_url: callback_url.map(str::to_string),
+        metadata: BTreeMap::new(),
+    };
+    let (id, _) = upsert_monitored_subject(conn, tenant_id, reference_id, &attributes)?;
+    Ok(id)
+}
+
+/// Enrol a subject, or update the attributes of the one already enrolled
+/// under `reference_id`. The subject keeps its id and result history; a
+/// previously removed subject is re-enrolled unpaused. Returns the id and
+/// whether the subject was newly enrolled.
+pub fn upsert_monitored_subject(
+    conn: &Connection,
+    tenant_id: &str,
+    reference_id: &str,
+    attributes: &SubjectAttributes,
+) -> Result<(i64, bool)> {
+    let was_active: Option<bool> = conn
+        .query_row(
+            "SELECT active FROM monitored_subject WHERE tenant_id = ?1 AND reference_id = ?2",
+            rusqlite::params![tenant_id, reference_id],
+            |row| row.get::<_, i32>(0).map(|a| a == 1),
+        )
+        .optional()?;
+
+    let dob_year = attributes
+        .date_of_birth
+        .as_deref()
+        .and_then(BirthDate::parse)
+        .map(|d| d.year);
+    let kind = attributes.kind.as_str();
+    let metadata = serde_json::to_string(&attributes.metadata)?;
+
+    let id = conn.query_row(
+        "INSERT INTO monitored_subject
+         (tenant_id, reference_id, name, kind, country, dob_year, date_of_birth, nationality, callback_url, metadata, active)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 1)
+         ON CONFLICT(tenant_id, reference_id) DO UPDATE SET
+             name = excluded.name, kind = excluded.kind, country = excluded.country,
+             dob_year = excluded.dob_year, date_of_birth = excluded.date_of_birth,
+             nationality = excluded.nationality, callback_url = excluded.callback_url,
+             metadata = excluded.metadata,
+             paused = CASE WHEN active = 1 THEN paused ELSE 0 END,
+             active = 1
+         RETURNING id",
+        rusqlite::params![
+            tenant_id,
+            reference_id,
+            attributes.name,
+            kind,
+            attributes.country,
+            dob_year,
+            attributes.date_of_birth,
+            attributes.nationality,
+            attributes.callback_url,
+            metadata,
+        ],
+        |row| row.get(0),
     )?;
 
-    let id = conn.last_insert_rowid();
-    tracing::debug!(id, reference_id, "added subject to monitoring");
-    Ok(id)
+    let created = was_active != Some(true);
+    tracing::debug!(id, reference_id, created, "upserted monitored subject");
+    Ok((id, created))
+}
+
+/// Upsert many subjects in one transaction; results are in input order
+pub fn upsert_monitored_subjects(
+    conn: &Connection,
+    tenant_id: &str,
+    subjects: &[(String, SubjectAttributes)],
+) -> Result<Vec<(i64, bool)>> {
+    let tx = conn.unchecked_transaction()?;
+    let results = subjects
+        .iter()
+        .map(|(reference_id, attributes)| upsert_monitored_subject(&tx, tenant_id, reference_id, attributes))
+        .collect::<Result<Vec<_>>>()?;
+    tx.commit()?;
+    Ok(results)
 }
 
 /// Remove a subject from monitoring
@@ -104,63 +306,95 @@ pub fn remove_monitored_subject(
     Ok(rows > 0)
 }
 
+/// Pause or resume re-screening of an enrolled subject. False when the
+/// tenant has no such subject.
+pub fn set_subject_paused(conn: &Connection, tenant_id: &str, reference_id: &str, paused: bool) -> Result<bool> {
+    let rows = conn.execute(
+        "UPDATE monitored_subject SET paused = ?3
+         WHERE tenant_id = ?1 AND reference_id = ?2 AND active = 1",
+        rusqlite::params![tenant_id, reference_id, paused as i32],
+    )?;
+    Ok(rows > 0)
+}
+
+/// One enrolled subject of a tenant
+pub fn get_monitored_subject(
+    conn: &Connection,
+    tenant_id: &str,
+    reference_id: &str,
+) -> Result<Option<MonitoredSubject>> {
+    let subject = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM monitored_subject s
+                 WHERE s.tenant_id = ?1 AND s.reference_id = ?2 AND s.active = 1",
+                subject_columns("s")
+            ),
+            rusqlite::params![tenant_id, reference_id],
+            subject_from_row,
+        )
+        .optional()?;
+    Ok(subject)
+}
+
 /// Get all active monitored subjects for a tenant
 pub fn get_monitored_subjects(
     conn: &Connection,
     tenant_id: &str,
 ) -> Result<Vec<MonitoredSubject>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, tenant_id, reference_id, name, country, dob_year, 
-                last_screened_at, last_result_hash, callback_url
-         FROM monitored_subject 
-         WHERE tenant_id = ?1 AND active = 1"
-    )?;
+    list_monitored_subjects(conn, tenant_id, &SubjectFilter::default(), i64::MAX, 0)
+}
 
-    let subjects = stmt.query_map([tenant_id], |row| {
-        Ok(MonitoredSubject {
-            id: row.get(0)?,
-            tenant_id: row.get(1)?,
-            reference_id: row.get(2)?,
-            name: row.get(3)?,
-            country: row.get(4)?,
-            dob_year: row.get(5)?,
-            last_screened_at: row.get(6)?,
-            last_result_hash: row.get(7)?,
-            callback_url: row.get(8)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+/// A page of a tenant's enrolled subjects, most recently changed first
+pub fn list_monitored_subjects(
+    conn: &Connection,
+    tenant_id: &str,
+    filter: &SubjectFilter,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<MonitoredSubject>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM monitored_subject s
+         WHERE s.tenant_id = ?1 AND s.active = 1
+           AND (?2 IS NULL OR s.last_changed_at >= ?2)
+           AND (?3 IS NULL OR s.paused = ?3)
+         ORDER BY s.last_changed_at IS NULL, s.last_changed_at DESC, s.id
+         LIMIT ?4 OFFSET ?5",
+        subject_columns("s")
+    ))?;
+
+    let subjects = stmt
+        .query_map(
+            rusqlite::params![
+                tenant_id,
+                filter.changed_since,
+                filter.paused.map(|p| p as i32),
+                limit,
+                offset
+            ],
+            subject_from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(subjects)
 }
 
-/// Get all active subjects across all tenants (for batch re-screening)
+/// Get all active, unpaused subjects across all tenants (for batch re-screening)
 pub fn get_all_active_subjects(conn: &Connection) -> Result<Vec<MonitoredSubject>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, tenant_id, reference_id, name, country, dob_year, 
-                last_screened_at, last_result_hash, callback_url
-         FROM monitored_subject 
-         WHERE active = 1
-         ORDER BY last_screened_at ASC"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM monitored_subject s
+         WHERE s.active = 1 AND s.paused = 0
+         ORDER BY s.last_screened_at ASC",
+        subject_columns("s")
+    ))?;
 
-    let subjects = stmt.query_map([], |row| {
-        Ok(MonitoredSubject {
-            id: row.get(0)?,
-            tenant_id: row.get(1)?,
-            reference_id: row.get(2)?,
-            name: row.get(3)?,
-            country: row.get(4)?,
-            dob_year: row.get(5)?,
-            last_screened_at: row.get(6)?,
-            last_result_hash: row.get(7)?,
-            callback_url: row.get(8)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+    let subjects = stmt.query_map([], subject_from_row)?.collect::<Result<Vec<_>, _>>()?;
 
     Ok(subjects)
 }
 
-/// Record a monitoring result
+/// Record a monitoring result. `hits` is stored alongside so later callbacks can
+/// diff against it; callers that only have a hash pass `None`.
 pub fn record_monitoring_result(
     conn: &Connection,
     subject_id: i64,
@@ -168,20 +402,23 @@ pub fn record_monitoring_result(
     hit_count: usize,
     highest_score: f32,
     has_changes: bool,
+    hits: Option<&[Hit]>,
 ) -> Result<()> {
+    let hits_json = hits.map(serde_json::to_string).transpose()?;
     conn.execute(
         "INSERT INTO monitoring_result 
-         (subject_id, result_hash, hit_count, highest_score, has_changes)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        rusqlite::params![subject_id, result_hash, hit_count as i64, highest_score, has_changes as i32],
+         (subject_id, result_hash, hit_count, highest_score, has_changes, hits)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![subject_id, result_hash, hit_count as i64, highest_score, has_changes as i32, hits_json],
     )?;
 
-    // Update last_screened_at and last_result_hash
+    // Update last_screened_at and last_result_hash, and last_changed_at on a change
     conn.execute(
         "UPDATE monitored_subject 
-         SET last_screened_at = datetime('now'), last_result_hash = ?2
+         SET last_screened_at = datetime('now'), last_result_hash = ?2,
+             last_changed_at = CASE WHEN ?3 THEN datetime('now') ELSE last_changed_at END
          WHERE id = ?1",
-        rusqlite::params![subject_id, result_hash],
+        rusqlite::params![subject_id, result_hash, has_changes],
     )?;
 
     Ok(())
@@ -190,43 +427,89 @@ pub fn record_monitoring_result(
 /// Get subjects with changes that haven't been notified
 pub fn get_pending_notifications(conn: &Connection) -> Result<Vec<(MonitoredSubject, MonitoringResult, i64)>> {
     let mut stmt = conn.prepare(
-        "SELECT s.id, s.tenant_id, s.reference_id, s.name, s.country, s.dob_year,
-                s.last_screened_at, s.last_result_hash, s.callback_url,
-                r.id, r.result_hash, r.hit_count, r.highest_score, r.has_changes
-         FROM monitored_subject s
-         JOIN monitoring_result r ON s.id = r.subject_id
-         WHERE r.has_changes = 1 AND r.notified = 0
-         ORDER BY r.screened_at DESC"
+        &format!(
+            "SELECT {},
+                    r.id, r.result_hash, r.hit_count, r.highest_score, r.has_changes, r.hits
+             FROM monitored_subject s
+             JOIN monitoring_result r ON s.id = r.subject_id
+             WHERE r.has_changes = 1 AND r.notified = 0
+             ORDER BY r.screened_at DESC",
+            subject_columns("s")
+        ),
     )?;
 
     let results = stmt.query_map([], |row| {
+        // Result columns follow the subject's
+        let r = SUBJECT_COLUMNS.len();
         Ok((
-            MonitoredSubject {
-                id: row.get(0)?,
-                tenant_id: row.get(1)?,
-                reference_id: row.get(2)?,
-                name: row.get(3)?,
-                country: row.get(4)?,
-                dob_year: row.get(5)?,
-                last_screened_at: row.get(6)?,
-                last_result_hash: row.get(7)?,
-                callback_url: row.get(8)?,
-            },
+            subject_from_row(row)?,
             MonitoringResult {
                 subject_id: row.get(0)?,
                 reference_id: row.get(2)?,
-                has_changes: row.get::<_, i32>(13)? == 1,
-                new_result_hash: row.get(10)?,
-                hit_count: row.get::<_, i64>(11)? as usize,
-                highest_score: row.get(12)?,
+                has_changes: row.get::<_, i32>(r + 4)? == 1,
+                new_result_hash: row.get(r + 1)?,
+                hit_count: row.get::<_, i64>(r + 2)? as usize,
+                highest_score: row.get(r + 3)?,
+                hits: parse_hits(row.get(r + 5)?),
             },
-            row.get::<_, i64>(9)?, // result_id
+            row.get::<_, i64>(r)?, // result_id
         ))
     })?.collect::<Result<Vec<_>, _>>()?;
 
     Ok(results)
 }
 
+/// Hits of the most recent result recorded for a subject before `result_id`,
+/// i.e. the screening a change should be compared against. `None` when there
+/// is no earlier result or it predates stored hits.
+pub fn get_previous_hits(conn: &Connection, subject_id: i64, result_id: i64) -> Result<Option<Vec<Hit>>> {
+    let hits: Option<Option<String>> = conn
+        .query_row(
+            "SELECT hits FROM monitoring_result
+             WHERE subject_id = ?1 AND id < ?2
+             ORDER BY id DESC LIMIT 1",
+            rusqlite::params![subject_id, result_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(parse_hits(hits.flatten()))
+}
+
+fn parse_hits(json: Option<String>) -> Option<Vec<Hit>> {
+    json.and_then(|j| serde_json::from_str(&j).ok())
+}
+
+/// Compare two hit sets by listed subject. Scores are compared at the same
+/// two-decimal precision as the result hash.
+pub fn diff_hits(previous: &[Hit], current: &[Hit]) -> HitDiff {
+    let rounded = |score: f32| (score * 100.0) as i32;
+    let mut diff = HitDiff::default();
+
+    for hit in current {
+        match previous.iter().find(|p| p.subject_id == hit.subject_id) {
+            None => diff.added.push(hit.clone()),
+            Some(prev) if rounded(prev.score) != rounded(hit.score) || prev.risk_level != hit.risk_level => {
+                diff.changed.push(ChangedHit {
+                    subject_id: hit.subject_id.clone(),
+                    previous_score: prev.score,
+                    current_score: hit.score,
+                    previous_risk_level: prev.risk_level,
+                    current_risk_level: hit.risk_level,
+                    hit: hit.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    diff.removed = previous
+        .iter()
+        .filter(|p| !current.iter().any(|h| h.subject_id == p.subject_id))
+        .cloned()
+        .collect();
+
+    diff
+}
+
 /// Mark a notification as sent
 pub fn mark_notified(conn: &Connection, result_id: i64) -> Result<()> {
     conn.execute(
@@ -236,18 +519,176 @@ pub fn mark_notified(conn: &Connection, result_id: i64) -> Result<()> {
     Ok(())
 }
 
-/// Compute a hash of screening results for change detection
+/// Claim a pending result for its audit entry and case alerts. Only the first
+/// call for a result returns true, so a result whose callback keeps failing
+/// and is retried is still recorded once.
+pub fn claim_recording(conn: &Connection, result_id: i64) -> Result<bool> {
+    let claimed = conn.execute(
+        "UPDATE monitoring_result SET recorded = 1 WHERE id = ?1 AND recorded = 0",
+        [result_id],
+    )?;
+    Ok(claimed == 1)
+}
+
+/// Compute a hash of screening results for change detection. SHA-256 rather
+/// than `DefaultHasher`, so stored hashes stay comparable across builds.
 pub fn compute_result_hash(hits: &[(String, f32)]) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
     for (id, score) in hits {
-        id.hash(&mut hasher);
+        hasher.update(id.as_bytes());
+        hasher.update([0]);
         // Round score to 2 decimals for stability
-        ((score * 100.0) as i32).hash(&mut hasher);
+        hasher.update(((score * 100.0) as i32).to_be_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Whether a re-screen hashing to `new_hash` is a change for `subject`. A
+/// subject whose last result cannot be compared, because it carries a
+/// pre-SHA-256 hash or was stored without its hits, is re-baselined instead;
+/// otherwise every current hit would be reported as new after an upgrade.
+pub fn detect_change(conn: &Connection, subject: &MonitoredSubject, new_hash: &str) -> Result<bool> {
+    let Some(previous) = subject.last_result_hash.as_deref() else {
+        return Ok(true);
+    };
+    if previous.len() != 64 {
+        return Ok(false);
+    }
+    let has_hits: Option<bool> = conn
+        .query_row(
+            "SELECT hits IS NOT NULL FROM monitoring_result WHERE subject_id = ?1 ORDER BY id DESC LIMIT 1",
+            [subject.id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(has_hits == Some(true) && previous != new_hash)
+}
+
+/// Hits that count towards change detection: those at or above the tenant's
+/// review threshold, i.e. any risk level other than `None`, that the tenant
+/// has not cleared for the customer.
+pub fn reportable_hits(hits: Vec<Hit>) -> Vec<Hit> {
+    hits.into_iter()
+        .filter(|h| h.risk_level != RiskLevel::None && !h.suppressed)
+        .collect()
+}
+
+/// Result hash over a set of reportable hits
+pub fn hits_result_hash(hits: &[Hit]) -> String {
+    let hit_data: Vec<(String, f32)> = hits.iter().map(|h| (h.subject_id.clone(), h.score)).collect();
+    compute_result_hash(&hit_data)
+}
+
+/// A tenant's thresholds and weights, as the screening API stores them in
+/// its `risk_config` table. Tenants without a row use the API's defaults.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TenantRiskConfig {
+    pub thresholds: ScoreThresholds,
+    pub weights: ScoreWeights,
+}
+
+impl TenantRiskConfig {
+    pub fn risk_level(&self, score: f32) -> RiskLevel {
+        if score >= self.thresholds.hit {
+            RiskLevel::Hit
+        } else if score >= self.thresholds.review {
+            RiskLevel::Review
+        } else {
+            RiskLevel::None
+        }
     }
-    format!("{:016x}", hasher.finish())
+}
+
+/// Every tenant's risk configuration from the screening API's risk database
+pub fn load_risk_configs(conn: &Connection) -> Result<HashMap<String, TenantRiskConfig>> {
+    let mut stmt = conn.prepare(
+        "SELECT tenant_id, hit_threshold, review_threshold, name_weight, dob_weight, country_weight
+         FROM risk_config",
+    )?;
+    let configs = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                TenantRiskConfig {
+                    thresholds: ScoreThresholds {
+                        hit: row.get(1)?,
+                        review: row.get(2)?,
+                    },
+                    weights: ScoreWeights {
+                        name: row.get(3)?,
+                        dob: row.get(4)?,
+                        country: row.get(5)?,
+                    },
+                },
+            ))
+        })?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(configs)
+}
+
+/// Active suppressions of monitored customers, as the screening API stores
+/// them in its `suppression` table. A suppression only covers the subject
+/// record version it was made against; the API invalidates stale ones the
+/// next time it sees the hit.
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    /// (tenant_id, reference_id, subject_id, record_version)
+    keys: HashSet<(String, String, String, String)>,
+}
+
+impl Suppressions {
+    /// Read from the screening API's tenant database. Suppressions keyed by
+    /// input name rather than `reference_id` never apply to monitored subjects.
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let mut stmt = conn.prepare(
+            "SELECT tenant_id, reference_id, subject_id, record_version FROM suppression
+             WHERE invalidated_at IS NULL AND reference_id IS NOT NULL",
+        )?;
+        let keys = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(Self { keys })
+    }
+
+    fn covers(&self, subject: &MonitoredSubject, hit: &Hit) -> bool {
+        self.keys.contains(&(
+            subject.tenant_id.clone(),
+            subject.reference_id.clone(),
+            hit.subject_id.clone(),
+            hit.record_version.clone().unwrap_or_default(),
+        ))
+    }
+}
+
+/// Score a monitored subject against the index the way the screening API
+/// does, keeping only the hits that count towards change detection. Hits
+/// the tenant has cleared for the subject are left out.
+pub fn screen_monitored_subject(
+    engine: &MatchingEngine,
+    subject: &MonitoredSubject,
+    risk: &TenantRiskConfig,
+    suppressions: &Suppressions,
+) -> Result<Vec<Hit>> {
+    let query = ScreeningQuery {
+        name: subject.name.clone(),
+        country: subject.country.clone(),
+        dob: subject.birth_date(),
+        kind: Some(subject.kind),
+        identifiers: Vec::new(),
+    };
+    let hits = engine
+        .search_and_score(&query, 10, &risk.weights, &risk.thresholds)?
+        .into_iter()
+        .map(|m| {
+            let risk_level = risk.risk_level(m.score);
+            let mut hit = m.into_hit(risk_level);
+            hit.suppressed = suppressions.covers(subject, &hit);
+            hit
+        })
+        .collect();
+    Ok(reportable_hits(hits))
 }
 
 #[cfg(test)]
@@ -277,7 +718,7 @@ mod tests {
         assert_eq!(subjects[0].name, "John Doe");
 
         // Record result
-        record_monitoring_result(&conn, id, "hash123", 2, 0.85, false).unwrap();
+        record_monitoring_result(&conn, id, "hash123", 2, 0.85, false, None).unwrap();
 
         // Remove subject
         let removed = remove_monitored_subject(&conn, "tenant1", "ref1").unwrap();
@@ -288,6 +729,239 @@ mod tests {
         assert_eq!(subjects.len(), 0);
     }
 
+    fn attributes(name: &str) -> SubjectAttributes {
+        SubjectAttributes {
+            name: name.to_string(),
+            kind: SubjectKind::Person,
+            country: None,
+            date_of_birth: None,
+            nationality: None,
+            callback_url: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn upsert_updates_in_place_and_pause_skips_rescreening() {
+        let dir = tempdir().unwrap();
+        let conn = open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        init_monitoring_schema(&conn).unwrap();
+
+        let acme = SubjectAttributes {
+            kind: SubjectKind::Entity,
+            metadata: BTreeMap::from([("segment".to_string(), "corporate".to_string())]),
+            ..attributes("Acme Trading Ltd")
+        };
+        let results = upsert_monitored_subjects(
+            &conn,
+            "tenant1",
+            &[("c-1".to_string(), attributes("John Doe")), ("c-2".to_string(), acme)],
+        )
+        .unwrap();
+        assert!(results.iter().all(|(_, created)| *created));
+        let (id, _) = results[0];
+        record_monitoring_result(&conn, id, "h1", 0, 0.0, true, Some(&[])).unwrap();
+
+        // Updating keeps the id, so result history stays attached
+        let updated = SubjectAttributes {
+            date_of_birth: Some("1970-04-28".to_string()),
+            nationality: Some("GB".to_string()),
+            ..attributes("John A. Doe")
+        };
+        assert_eq!(upsert_monitored_subject(&conn, "tenant1", "c-1", &updated).unwrap(), (id, false));
+        let subject = get_monitored_subject(&conn, "tenant1", "c-1").unwrap().unwrap();
+        assert_eq!(subject.name, "John A. Doe");
+        assert_eq!(subject.dob_year, Some(1970));
+        assert_eq!(subject.birth_date(), BirthDate::parse("1970-04-28"));
+        assert!(subject.last_changed_at.is_some());
+
+        let entity = get_monitored_subject(&conn, "tenant1", "c-2").unwrap().unwrap();
+        assert_eq!(entity.kind, SubjectKind::Entity);
+        assert_eq!(entity.metadata["segment"], "corporate");
+
+        assert!(set_subject_paused(&conn, "tenant1", "c-2", true).unwrap());
+        assert!(!set_subject_paused(&conn, "tenant2", "c-2", true).unwrap());
+        let active: Vec<_> = get_all_active_subjects(&conn).unwrap().into_iter().map(|s| s.reference_id).collect();
+        assert_eq!(active, vec!["c-1"]);
+
+        let paused = SubjectFilter { paused: Some(true), ..SubjectFilter::default() };
+        assert_eq!(list_monitored_subjects(&conn, "tenant1", &paused, 10, 0).unwrap().len(), 1);
+        let changed = SubjectFilter { changed_since: Some("2000-01-01 00:00:00".to_string()), ..SubjectFilter::default() };
+        let changed = list_monitored_subjects(&conn, "tenant1", &changed, 10, 0).unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].reference_id, "c-1");
+    }
+
+    fn hit(subject_id: &str, score: f32, risk_level: RiskLevel) -> Hit {
+        Hit {
+            subject_id: subject_id.to_string(),
+            matched_name: "John Doe".to_string(),
+            source: aegistry_core::HitSource::Ofac,
+            kind: aegistry_core::SubjectKind::Person,
+            score,
+            risk_level,
+            components: aegistry_core::ScoreComponents {
+                name_similarity: score,
+                dob_similarity: 0.0,
+                country_match: 0.0,
+                phonetic_match: false,
+                identifier_match: 0.0,
+            },
+            explanation: Vec::new(),
+            matched_alias: None,
+            record_version: None,
+            suppressed: false,
+            suppression_reason: None,
+        }
+    }
+
+    #[test]
+    fn pending_notifications_carry_stored_hits() {
+        let dir = tempdir().unwrap();
+        let conn = open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        init_monitoring_schema(&conn).unwrap();
+
+        let id = add_monitored_subject(&conn, "tenant1", "ref1", "John Doe", None, None, None).unwrap();
+        let before = vec![hit("a", 0.9, RiskLevel::Hit)];
+        let after = vec![hit("a", 0.9, RiskLevel::Hit), hit("b", 0.8, RiskLevel::Review)];
+        record_monitoring_result(&conn, id, "h1", 1, 0.9, false, Some(&before)).unwrap();
+        record_monitoring_result(&conn, id, "h2", 2, 0.9, true, Some(&after)).unwrap();
+
+        let pending = get_pending_notifications(&conn).unwrap();
+        assert_eq!(pending.len(), 1);
+        let (_, result, result_id) = &pending[0];
+        assert_eq!(result.hits.as_ref().unwrap().len(), 2);
+
+        let previous = get_previous_hits(&conn, id, *result_id).unwrap().unwrap();
+        assert_eq!(previous.len(), 1);
+        assert_eq!(previous[0].subject_id, "a");
+
+        // Recorded once however often delivery is retried
+        assert!(claim_recording(&conn, *result_id).unwrap());
+        assert!(!claim_recording(&conn, *result_id).unwrap());
+        assert_eq!(get_pending_notifications(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn legacy_results_are_rebaselined_not_changed() {
+        let dir = tempdir().unwrap();
+        let conn = open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&conn).unwrap();
+        init_monitoring_schema(&conn).unwrap();
+        let id = add_monitored_subject(&conn, "tenant1", "ref1", "John Doe", None, None, None).unwrap();
+        let subject = || get_monitored_subject(&conn, "tenant1", "ref1").unwrap().unwrap();
+        let hits = vec![hit("a", 0.9, RiskLevel::Hit)];
+        let new_hash = hits_result_hash(&hits);
+
+        // A DefaultHasher hash from before the SHA-256 switch
+        record_monitoring_result(&conn, id, "9f86d081884c7d65", 1, 0.9, false, None).unwrap();
+        assert!(!detect_change(&conn, &subject(), &new_hash).unwrap());
+
+        // A SHA-256 hash whose result predates stored hits
+        record_monitoring_result(&conn, id, &hits_result_hash(&[]), 0, 0.0, false, None).unwrap();
+        assert!(!detect_change(&conn, &subject(), &new_hash).unwrap());
+
+        record_monitoring_result(&conn, id, &hits_result_hash(&[]), 0, 0.0, false, Some(&[])).unwrap();
+        assert!(detect_change(&conn, &subject(), &new_hash).unwrap());
+        assert!(!detect_change(&conn, &subject(), &hits_result_hash(&[])).unwrap());
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_hits() {
+        let previous = vec![
+            hit("kept", 0.91, RiskLevel::Hit),
+            hit("rescored", 0.75, RiskLevel::Review),
+            hit("delisted", 0.88, RiskLevel::Hit),
+        ];
+        let current = vec![
+            hit("kept", 0.911, RiskLevel::Hit),
+            hit("rescored", 0.86, RiskLevel::Hit),
+            hit("listed", 0.8, RiskLevel::Review),
+        ];
+
+        let diff = diff_hits(&previous, &current);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].subject_id, "listed");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].subject_id, "delisted");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].subject_id, "rescored");
+        assert_eq!(diff.changed[0].previous_risk_level, RiskLevel::Review);
+        assert_eq!(diff.changed[0].current_risk_level, RiskLevel::Hit);
+
+        assert!(diff_hits(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn reportable_hits_drop_scores_below_review() {
+        let hits = vec![
+            hit("a", 0.97, RiskLevel::Hit),
+            hit("b", 0.91, RiskLevel::Review),
+            hit("c", 0.60, RiskLevel::None),
+        ];
+        let reportable = reportable_hits(hits.clone());
+        assert_eq!(reportable.len(), 2);
+
+        // A hit the tenant cleared does not count either
+        let mut cleared = hits.clone();
+        cleared[1].suppressed = true;
+        assert_eq!(reportable_hits(cleared).len(), 1);
+
+        // A candidate moving around below the review threshold is not a change
+        let mut fluctuated = hits;
+        fluctuated[2] = hit("d", 0.55, RiskLevel::None);
+        assert_eq!(hits_result_hash(&reportable), hits_result_hash(&reportable_hits(fluctuated)));
+    }
+
+    #[test]
+    fn suppressions_cover_the_cleared_record_version_only() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE suppression (tenant_id TEXT, reference_id TEXT, normalized_name TEXT,
+                                       subject_id TEXT, record_version TEXT, invalidated_at TEXT);
+             INSERT INTO suppression VALUES ('tenant1', 'ref1', NULL, 'a', 'v1', NULL);
+             INSERT INTO suppression VALUES ('tenant1', 'ref1', NULL, 'b', 'v1', datetime('now'));
+             INSERT INTO suppression VALUES ('tenant1', NULL, 'john doe', 'c', 'v1', NULL);",
+        )
+        .unwrap();
+        let suppressions = Suppressions::load(&conn).unwrap();
+
+        let dir = tempdir().unwrap();
+        let db = open_db(&dir.path().join("test.db")).unwrap();
+        crate::db::init_schema(&db).unwrap();
+        init_monitoring_schema(&db).unwrap();
+        add_monitored_subject(&db, "tenant1", "ref1", "John Doe", None, None, None).unwrap();
+        let subject = get_monitored_subject(&db, "tenant1", "ref1").unwrap().unwrap();
+
+        let versioned = |id: &str, version: &str| Hit {
+            record_version: Some(version.to_string()),
+            ..hit(id, 0.97, RiskLevel::Hit)
+        };
+        assert!(suppressions.covers(&subject, &versioned("a", "v1")));
+        assert!(!suppressions.covers(&subject, &versioned("a", "v2")));
+        // Revoked, and keyed by name only
+        assert!(!suppressions.covers(&subject, &versioned("b", "v1")));
+        assert!(!suppressions.covers(&subject, &versioned("c", "v1")));
+    }
+
+    #[test]
+    fn tenant_risk_configs_load_from_the_api_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE risk_config (tenant_id TEXT PRIMARY KEY, hit_threshold REAL, review_threshold REAL,
+                                       name_weight REAL, dob_weight REAL, country_weight REAL);
+             INSERT INTO risk_config VALUES ('strict', 0.85, 0.70, 0.80, 0.10, 0.10);",
+        )
+        .unwrap();
+
+        let configs = load_risk_configs(&conn).unwrap();
+        let strict = configs["strict"];
+        assert_eq!(strict.risk_level(0.75), RiskLevel::Review);
+        assert_eq!(TenantRiskConfig::default().risk_level(0.75), RiskLevel::None);
+    }
+
     #[test]
     fn result_hash_stability() {
         let hits1 = vec![("id1".to_string(), 0.95f32), ("id2".to_string(), 0.80f32)];
@@ -296,6 +970,11 @@ mod tests {
 
         assert_eq!(compute_result_hash(&hits1), compute_result_hash(&hits2));
         assert_ne!(compute_result_hash(&hits1), compute_result_hash(&hits3));
+        // Pinned: the hash must not change between builds
+        assert_eq!(
+            compute_result_hash(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
     }
 }
 
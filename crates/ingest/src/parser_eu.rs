@@ -13,12 +13,96 @@ pub struct ParsedSubject {
     pub date_of_birth_year: Option<i32>,
     pub country: Option<String>,
     pub nationalities: Vec<String>,
+    #[serde(default)]
+    pub details: SubjectDetails,
+}
+
+/// What a list publishes about a subject beyond its names, used by analysts
+/// to adjudicate a hit. Sources that publish none of it leave this empty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SubjectDetails {
+    pub identifiers: Vec<ParsedIdentifier>,
+    pub addresses: Vec<ParsedAddress>,
+    pub places_of_birth: Vec<String>,
+    /// Sanctions programmes or regimes the subject is listed under
+    pub programs: Vec<String>,
+    /// Date of (first) listing as published, usually `YYYY-MM-DD`
+    pub listed_on: Option<String>,
+    pub remarks: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vessel: Option<VesselDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aircraft: Option<AircraftDetails>,
+}
+
+/// Registry particulars of a listed ship. Its IMO number is kept with the
+/// other identifiers and its former names as `fka` aliases.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct VesselDetails {
+    /// Flag state as published, usually a country name
+    pub flag: Option<String>,
+    pub call_sign: Option<String>,
+    pub vessel_type: Option<String>,
+    pub tonnage: Option<String>,
+    pub owner: Option<String>,
+}
+
+/// Particulars of a listed aircraft. Serial and tail numbers are also kept
+/// with the other identifiers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AircraftDetails {
+    pub tail_number: Option<String>,
+    /// Manufacturer's serial number (MSN)
+    pub serial_number: Option<String>,
+    pub model: Option<String>,
+    pub operator: Option<String>,
+}
+
+/// A passport, national ID or other document number
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParsedIdentifier {
+    /// Document type as the source names it, e.g. "Passport" or "National ID No."
+    pub id_type: String,
+    pub number: String,
+    pub country: Option<String>,
+    pub issued_on: Option<String>,
+    pub expires_on: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ParsedAddress {
+    pub street: Option<String>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+}
+
+impl ParsedAddress {
+    pub fn is_empty(&self) -> bool {
+        *self == ParsedAddress::default()
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SubjectKind {
     Person,
     Entity,
+    Vessel,
+    Aircraft,
+}
+
+impl SubjectKind {
+    /// Value stored in the `kind` column and the index
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubjectKind::Person => "person",
+            SubjectKind::Entity => "entity",
+            SubjectKind::Vessel => "vessel",
+            SubjectKind::Aircraft => "aircraft",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,15 +120,23 @@ pub fn parse_eu_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
 
     let mut in_sanction_entity = false;
     let mut current_builder: Option<SubjectBuilder> = None;
+    // Regulations and remarks also appear nested under names, citizenships etc.;
+    // only those directly under <sanctionEntity> describe the listing itself
+    let mut depth = 0usize;
+    let mut entity_depth = 0usize;
+    let mut in_entity_remark = false;
 
     loop {
-        match reader.read_event_into(&mut buf) {
+        let event = reader.read_event_into(&mut buf);
+        match &event {
             Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                 let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                let entity_child = in_sanction_entity && depth == entity_depth + 1;
                 
                 match tag_name.as_str() {
                     "sanctionEntity" => {
                         in_sanction_entity = true;
+                        entity_depth = depth;
                         let mut builder = SubjectBuilder::default();
                         
                         for attr in e.attributes().flatten() {
@@ -57,11 +149,97 @@ pub fn parse_eu_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                                         builder.source_ref = Some(value);
                                     }
                                 }
+                                "designationDate" if !value.is_empty() => builder.details.listed_on = Some(value),
                                 _ => {}
                             }
                         }
                         current_builder = Some(builder);
                     }
+                    "regulation" if entity_child => {
+                        if let Some(ref mut builder) = current_builder {
+                            for attr in e.attributes().flatten() {
+                                let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                                let value = String::from_utf8_lossy(&attr.value).to_string();
+                                if value.is_empty() {
+                                    continue;
+                                }
+                                match key.as_str() {
+                                    "programme" if !builder.details.programs.contains(&value) => {
+                                        builder.details.programs.push(value);
+                                    }
+                                    // The earliest regulation is the one that listed the subject
+                                    "publicationDate" if builder.details.listed_on.as_ref().is_none_or(|d| value < *d) => {
+                                        builder.details.listed_on = Some(value);
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    "remark" if entity_child && matches!(event, Ok(Event::Start(_))) => {
+                        in_entity_remark = true;
+                    }
+                    "identification" if in_sanction_entity => {
+                        if let Some(ref mut builder) = current_builder {
+                            let mut id_type = None;
+                            let mut number = None;
+                            let mut country = None;
+                            let mut issued_on = None;
+                            for attr in e.attributes().flatten() {
+                                let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                                let value = String::from_utf8_lossy(&attr.value).to_string();
+                                if value.is_empty() {
+                                    continue;
+                                }
+                                match key.as_str() {
+                                    "identificationTypeDescription" => id_type = Some(value),
+                                    "identificationTypeCode" if id_type.is_none() => id_type = Some(value),
+                                    "number" => number = Some(value),
+                                    "countryIso2Code" if value != "00" => country = Some(value.to_uppercase()),
+                                    "issueDate" => issued_on = Some(value),
+                                    _ => {}
+                                }
+                            }
+                            if let (Some(id_type), Some(number)) = (id_type, number) {
+                                builder.details.identifiers.push(ParsedIdentifier {
+                                    id_type,
+                                    number,
+                                    country,
+                                    issued_on,
+                                    expires_on: None,
+                                    note: None,
+                                });
+                            }
+                        }
+                    }
+                    "address" if in_sanction_entity => {
+                        if let Some(ref mut builder) = current_builder {
+                            let mut address = ParsedAddress::default();
+                            let mut po_box = None;
+                            for attr in e.attributes().flatten() {
+                                let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                                let value = String::from_utf8_lossy(&attr.value).to_string();
+                                if value.is_empty() {
+                                    continue;
+                                }
+                                match key.as_str() {
+                                    "street" => address.street = Some(value),
+                                    "poBox" => po_box = Some(value),
+                                    "city" => address.city = Some(value),
+                                    "region" => address.region = Some(value),
+                                    "zipCode" => address.postal_code = Some(value),
+                                    "countryIso2Code" if value != "00" => address.country = Some(value.to_uppercase()),
+                                    _ => {}
+                                }
+                            }
+                            if address.street.is_none() {
+                                address.street = po_box.map(|b| format!("P.O. Box {}", b));
+                            }
+                            if !address.is_empty() {
+                                builder.details.addresses.push(address);
+                            }
+                        }
+                    }
                     "subjectType" if in_sanction_entity => {
                         if let Some(ref mut builder) = current_builder {
                             for attr in e.attributes().flatten() {
@@ -71,7 +249,14 @@ pub fn parse_eu_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                                     let lower = value.to_lowercase();
                                     if lower == "person" || lower == "p" {
                                         builder.kind = Some(SubjectKind::Person);
-                                    } else if lower == "enterprise" || lower == "e" {
+                                    } else if lower == "vessel" || lower == "ship" {
+                                        builder.kind = Some(SubjectKind::Vessel);
+                                    } else if lower == "aircraft" {
+                                        builder.kind = Some(SubjectKind::Aircraft);
+                                    } else if (lower == "enterprise" || lower == "e")
+                                        // A vessel's classification code is still "E"
+                                        && !matches!(builder.kind, Some(SubjectKind::Vessel | SubjectKind::Aircraft))
+                                    {
                                         builder.kind = Some(SubjectKind::Entity);
                                     }
                                 }
@@ -128,6 +313,7 @@ pub fn parse_eu_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                     }
                     "birthdate" if in_sanction_entity => {
                         if let Some(ref mut builder) = current_builder {
+                            let mut place = Vec::new();
                             for attr in e.attributes().flatten() {
                                 let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
                                 let value = String::from_utf8_lossy(&attr.value).to_string();
@@ -154,16 +340,39 @@ pub fn parse_eu_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                                             builder.country = Some(value.to_uppercase());
                                         }
                                     }
+                                    "city" | "place" | "region" | "countryDescription"
+                                        if !value.is_empty() && value != "UNKNOWN" && !place.contains(&value) =>
+                                    {
+                                        place.push(value);
+                                    }
                                     _ => {}
                                 }
                             }
+                            let place = place.join(", ");
+                            if !place.is_empty() && !builder.details.places_of_birth.contains(&place) {
+                                builder.details.places_of_birth.push(place);
+                            }
                         }
                     }
                     _ => {}
                 }
             }
+            Ok(Event::Text(e)) if in_entity_remark => {
+                if let Some(ref mut builder) = current_builder {
+                    let text = e.unescape().unwrap_or_default().trim().to_string();
+                    if !text.is_empty() {
+                        builder.details.remarks = Some(match builder.details.remarks.take() {
+                            Some(remarks) => format!("{}\n{}", remarks, text),
+                            None => text,
+                        });
+                    }
+                }
+            }
             Ok(Event::End(e)) => {
                 let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if tag_name == "remark" {
+                    in_entity_remark = false;
+                }
                 if tag_name == "sanctionEntity" {
                     in_sanction_entity = false;
                     if let Some(builder) = current_builder.take() {
@@ -179,6 +388,11 @@ pub fn parse_eu_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
             }
             _ => {}
         }
+        match event {
+            Ok(Event::Start(_)) => depth += 1,
+            Ok(Event::End(_)) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
         buf.clear();
     }
 
@@ -205,6 +419,7 @@ struct SubjectBuilder {
     date_of_birth_year: Option<i32>,
     country: Option<String>,
     nationalities: Vec<String>,
+    details: SubjectDetails,
 }
 
 impl SubjectBuilder {
@@ -230,6 +445,7 @@ impl SubjectBuilder {
             date_of_birth_year: self.date_of_birth_year,
             country,
             nationalities: self.nationalities,
+            details: self.details,
         })
     }
 }
@@ -263,4 +479,42 @@ mod tests {
         assert_eq!(s.date_of_birth_year, Some(1937));
         assert_eq!(s.country, Some("IQ".to_string()));
     }
+
+    #[test]
+    fn parse_eu_details() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<export xmlns="http://eu.europa.ec/fpi/fsd/export">
+    <sanctionEntity designationDetails="" euReferenceNumber="EU.27.28" logicalId="13">
+        <regulation regulationType="amendment" publicationDate="2004-01-10" programme="IRQ" logicalId="2"/>
+        <regulation regulationType="regulation" publicationDate="2003-07-08" programme="IRQ" logicalId="1">
+            <publicationUrl>http://eur-lex.europa.eu/LexUriServ/LexUriServ.do?uri=OJ:L:2003:169:0006:0023:EN:PDF</publicationUrl>
+        </regulation>
+        <subjectType code="person" classificationCode="P"/>
+        <nameAlias wholeName="Saddam Hussein Al-Tikriti" logicalId="17">
+            <regulation regulationType="amendment" publicationDate="1999-01-01" programme="OTHER"/>
+            <remark>Name remark</remark>
+        </nameAlias>
+        <citizenship countryIso2Code="IQ" countryDescription="IRAQ"/>
+        <birthdate year="1937" birthdate="1937-04-28" city="al-Awja, near Tikrit" countryIso2Code="IQ" countryDescription="IRAQ"/>
+        <identification number="A0012345" countryIso2Code="IQ" identificationTypeCode="passport" identificationTypeDescription="National passport"/>
+        <address city="Baghdad" street="Palace Street" zipCode="" countryIso2Code="IQ" countryDescription="IRAQ"/>
+        <remark>Former President of Iraq.</remark>
+    </sanctionEntity>
+</export>"#;
+
+        let subjects = parse_eu_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 1);
+        let details = &subjects[0].details;
+        assert_eq!(details.programs, vec!["IRQ".to_string()]);
+        assert_eq!(details.listed_on.as_deref(), Some("2003-07-08"));
+        assert_eq!(details.remarks.as_deref(), Some("Former President of Iraq."));
+        assert_eq!(details.places_of_birth, vec!["al-Awja, near Tikrit, IRAQ".to_string()]);
+        assert_eq!(details.identifiers.len(), 1);
+        assert_eq!(details.identifiers[0].id_type, "National passport");
+        assert_eq!(details.identifiers[0].number, "A0012345");
+        assert_eq!(details.identifiers[0].country.as_deref(), Some("IQ"));
+        assert_eq!(details.addresses.len(), 1);
+        assert_eq!(details.addresses[0].street.as_deref(), Some("Palace Street"));
+        assert_eq!(details.addresses[0].postal_code, None);
+    }
 }
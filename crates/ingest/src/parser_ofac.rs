@@ -2,7 +2,10 @@ use anyhow::Result;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use crate::parser_eu::{ParsedAlias, ParsedSubject, SubjectKind};
+use crate::parser_eu::{
+    AircraftDetails, ParsedAddress, ParsedAlias, ParsedIdentifier, ParsedSubject, SubjectDetails, SubjectKind,
+    VesselDetails,
+};
 
 /// Parse OFAC SDN list XML
 pub fn parse_ofac_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
@@ -15,6 +18,10 @@ pub fn parse_ofac_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
     // Track current element context
     let mut in_sdn_entry = false;
     let mut current_builder: Option<SubjectBuilder> = None;
+    let mut current_aka: Option<AkaBuilder> = None;
+    let mut current_id: Option<IdBuilder> = None;
+    let mut current_address: Option<ParsedAddress> = None;
+    let mut in_nationality = false;
     let mut current_element = String::new();
 
     loop {
@@ -26,6 +33,14 @@ pub fn parse_ofac_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                 if name == "sdnEntry" {
                     in_sdn_entry = true;
                     current_builder = Some(SubjectBuilder::new());
+                } else if name == "aka" && in_sdn_entry {
+                    current_aka = Some(AkaBuilder::default());
+                } else if name == "id" && in_sdn_entry {
+                    current_id = Some(IdBuilder::default());
+                } else if name == "address" && in_sdn_entry {
+                    current_address = Some(ParsedAddress::default());
+                } else if name == "nationality" || name == "citizenship" {
+                    in_nationality = true;
                 }
             }
             Ok(Event::End(ref e)) => {
@@ -38,45 +53,115 @@ pub fn parse_ofac_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                         }
                     }
                     in_sdn_entry = false;
+                } else if name == "aka" {
+                    if let (Some(aka), Some(builder)) = (current_aka.take(), current_builder.as_mut()) {
+                        aka.finish(builder);
+                    }
+                } else if name == "id" {
+                    if let (Some(id), Some(builder)) = (current_id.take(), current_builder.as_mut()) {
+                        id.finish(builder);
+                    }
+                } else if name == "address" {
+                    if let (Some(address), Some(builder)) = (current_address.take(), current_builder.as_mut()) {
+                        if !address.is_empty() {
+                            builder.details.addresses.push(address);
+                        }
+                    }
+                } else if name == "nationality" || name == "citizenship" {
+                    in_nationality = false;
                 }
                 current_element.clear();
             }
             Ok(Event::Text(ref e)) => {
-                if in_sdn_entry && current_builder.is_some() {
+                if let (true, Some(builder)) = (in_sdn_entry, current_builder.as_mut()) {
                     let text = e.unescape().unwrap_or_default().trim().to_string();
                     if text.is_empty() {
                         continue;
                     }
                     
-                    let builder = current_builder.as_mut().unwrap();
-                    
-                    match current_element.as_str() {
-                        "uid" => {
-                            builder.source_ref = Some(text);
-                        }
-                        "sdnType" => {
-                            builder.sdn_type = Some(text);
+                    // Names inside <aka> belong to the alias, not the primary name
+                    if let Some(ref mut aka) = current_aka {
+                        match current_element.as_str() {
+                            "type" => aka.aka_type = Some(text),
+                            "category" => aka.category = Some(text),
+                            "firstName" => aka.first_name = Some(text),
+                            "lastName" => aka.last_name = Some(text),
+                            _ => {}
                         }
-                        "firstName" => {
-                            builder.first_name = Some(text);
+                    } else if let Some(ref mut id) = current_id {
+                        match current_element.as_str() {
+                            "idType" => id.id_type = Some(text),
+                            "idNumber" => id.number = Some(text),
+                            "idCountry" => id.country = Some(text),
+                            "issueDate" => id.issued_on = Some(text),
+                            "expirationDate" => id.expires_on = Some(text),
+                            _ => {}
                         }
-                        "lastName" => {
-                            builder.last_name = Some(text);
-                        }
-                        "aka" => {
-                            builder.add_alias(&text);
-                        }
-                        "country" => {
-                            // OFAC uses full country names
-                            builder.add_country(&text);
+                    } else if let Some(ref mut address) = current_address {
+                        match current_element.as_str() {
+                            "address1" | "address2" | "address3" => {
+                                address.street = Some(match address.street.take() {
+                                    Some(street) => format!("{}, {}", street, text),
+                                    None => text,
+                                });
+                            }
+                            "city" => address.city = Some(text),
+                            "stateOrProvince" => address.region = Some(text),
+                            "postalCode" => address.postal_code = Some(text),
+                            "country" => {
+                                if builder.country.is_none() {
+                                    builder.country = Some(country_to_iso(&text));
+                                }
+                                address.country = Some(text);
+                            }
+                            _ => {}
                         }
-                        "dateOfBirth" => {
-                            builder.date_of_birth = Some(text.clone());
-                            if let Some(year) = extract_year(&text) {
-                                builder.date_of_birth_year = Some(year);
+                    } else {
+                        match current_element.as_str() {
+                            // Nested list items carry their own uids; the entry's comes first
+                            "uid" if builder.source_ref.is_none() => {
+                                builder.source_ref = Some(text);
+                            }
+                            "sdnType" => {
+                                builder.sdn_type = Some(text);
+                            }
+                            "firstName" => {
+                                builder.first_name = Some(text);
                             }
+                            "lastName" => {
+                                builder.last_name = Some(text);
+                            }
+                            "country" if in_nationality => {
+                                // OFAC uses full country names
+                                builder.add_country(&text);
+                            }
+                            "program" if !builder.details.programs.contains(&text) => {
+                                builder.details.programs.push(text);
+                            }
+                            "placeOfBirth" => builder.details.places_of_birth.push(text),
+                            "remarks" => builder.details.remarks = Some(text),
+                            // <vesselInfo> of a Vessel entry
+                            "vesselFlag" => {
+                                if builder.country.is_none() {
+                                    builder.country = Some(country_to_iso(&text));
+                                }
+                                builder.vessel().flag = Some(text);
+                            }
+                            "callSign" => builder.vessel().call_sign = Some(text),
+                            "vesselType" => builder.vessel().vessel_type = Some(text),
+                            "vesselOwner" => builder.vessel().owner = Some(text),
+                            "grossRegisteredTonnage" => builder.vessel().tonnage = Some(text),
+                            "tonnage" if builder.vessel().tonnage.is_none() => {
+                                builder.vessel().tonnage = Some(text);
+                            }
+                            "dateOfBirth" => {
+                                builder.date_of_birth = Some(text.clone());
+                                if let Some(year) = extract_year(&text) {
+                                    builder.date_of_birth_year = Some(year);
+                                }
+                            }
+                            _ => {}
                         }
-                        _ => {}
                     }
                 }
             }
@@ -118,16 +203,88 @@ fn extract_year(date_str: &str) -> Option<i32> {
     None
 }
 
+/// An `<aka>` element of an SDN entry
+#[derive(Default)]
+struct AkaBuilder {
+    aka_type: Option<String>,
+    category: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+impl AkaBuilder {
+    fn finish(self, builder: &mut SubjectBuilder) {
+        let name = match (self.first_name, self.last_name) {
+            (Some(first), Some(last)) => format!("{} {}", first, last),
+            (Some(first), None) => first,
+            (None, Some(last)) => last,
+            (None, None) => return,
+        };
+
+        // OFAC flags weak a.k.a.s as low quality; they should not drive a hit on their own
+        let alias_type = match (self.aka_type.as_deref(), self.category.as_deref()) {
+            (Some(t), _) if t.eq_ignore_ascii_case("f.k.a.") => "fka",
+            (_, Some(c)) if c.eq_ignore_ascii_case("weak") => "low_quality_aka",
+            _ => "aka",
+        };
+
+        builder.add_alias(&name, alias_type);
+    }
+}
+
+/// An `<id>` element of an SDN entry: passports, national IDs, registration numbers, ...
+#[derive(Default)]
+struct IdBuilder {
+    id_type: Option<String>,
+    number: Option<String>,
+    country: Option<String>,
+    issued_on: Option<String>,
+    expires_on: Option<String>,
+}
+
+impl IdBuilder {
+    fn finish(self, builder: &mut SubjectBuilder) {
+        let (Some(id_type), Some(number)) = (self.id_type, self.number) else {
+            return;
+        };
+        // Aircraft particulars are published as ids; model and operator are not identifiers
+        match id_type.as_str() {
+            "Aircraft Model" => {
+                builder.aircraft().model = Some(number);
+                return;
+            }
+            "Aircraft Operator" => {
+                builder.aircraft().operator = Some(number);
+                return;
+            }
+            t if t.starts_with("Aircraft Manufacturer's Serial Number") => {
+                builder.aircraft().serial_number = Some(number.clone());
+            }
+            "Aircraft Tail Number" => builder.aircraft().tail_number = Some(number.clone()),
+            _ => {}
+        }
+        builder.details.identifiers.push(ParsedIdentifier {
+            id_type,
+            number,
+            country: self.country,
+            issued_on: self.issued_on,
+            expires_on: self.expires_on,
+            note: None,
+        });
+    }
+}
+
 struct SubjectBuilder {
     source_ref: Option<String>,
     sdn_type: Option<String>,
     first_name: Option<String>,
     last_name: Option<String>,
-    aliases: Vec<String>,
+    aliases: Vec<ParsedAlias>,
     date_of_birth: Option<String>,
     date_of_birth_year: Option<i32>,
     country: Option<String>,
     nationalities: Vec<String>,
+    details: SubjectDetails,
 }
 
 impl SubjectBuilder {
@@ -142,25 +299,42 @@ impl SubjectBuilder {
             date_of_birth_year: None,
             country: None,
             nationalities: Vec::new(),
+            details: SubjectDetails::default(),
         }
     }
 
-    fn add_alias(&mut self, alias: &str) {
+    fn add_alias(&mut self, alias: &str, alias_type: &str) {
         if !alias.is_empty() {
-            self.aliases.push(alias.to_string());
+            self.aliases.push(ParsedAlias {
+                name: alias.to_string(),
+                alias_type: alias_type.to_string(),
+            });
         }
     }
 
+    fn vessel(&mut self) -> &mut VesselDetails {
+        self.details.vessel.get_or_insert_with(VesselDetails::default)
+    }
+
+    fn aircraft(&mut self) -> &mut AircraftDetails {
+        self.details.aircraft.get_or_insert_with(AircraftDetails::default)
+    }
+
     fn add_country(&mut self, country: &str) {
-        if !country.is_empty() && self.country.is_none() {
-            // Try to extract ISO code from country name
-            let iso = country_to_iso(country);
+        if country.is_empty() {
+            return;
+        }
+        // Try to extract ISO code from country name
+        let iso = country_to_iso(country);
+        if self.country.is_none() {
             self.country = Some(iso.clone());
+        }
+        if !self.nationalities.contains(&iso) {
             self.nationalities.push(iso);
         }
     }
 
-    fn build(self) -> Option<ParsedSubject> {
+    fn build(mut self) -> Option<ParsedSubject> {
         // Build primary name from first + last
         let primary_name = match (&self.first_name, &self.last_name) {
             (Some(first), Some(last)) => format!("{} {}", first, last),
@@ -178,25 +352,27 @@ impl SubjectBuilder {
             None => format!("ofac_{}", primary_name.chars().filter(|c| c.is_alphanumeric()).take(20).collect::<String>()),
         };
 
-        let kind = match self.sdn_type.as_deref() {
-            Some(t) if t.to_uppercase() == "INDIVIDUAL" => SubjectKind::Person,
+        let kind = match self.sdn_type.as_deref().map(str::to_uppercase).as_deref() {
+            Some("INDIVIDUAL") => SubjectKind::Person,
+            Some("VESSEL") => SubjectKind::Vessel,
+            Some("AIRCRAFT") => SubjectKind::Aircraft,
             _ => SubjectKind::Entity,
         };
-
-        let aliases = self.aliases
-            .into_iter()
-            .map(|name| ParsedAlias { name, alias_type: "aka".to_string() })
-            .collect();
+        // Aircraft are listed under their registration mark
+        if kind == SubjectKind::Aircraft && self.aircraft().tail_number.is_none() {
+            self.aircraft().tail_number = Some(primary_name.clone());
+        }
 
         Some(ParsedSubject {
             source_ref,
             kind,
             primary_name,
-            aliases,
+            aliases: self.aliases,
             date_of_birth: self.date_of_birth,
             date_of_birth_year: self.date_of_birth_year,
             country: self.country,
             nationalities: self.nationalities,
+            details: self.details,
         })
     }
 }
@@ -258,6 +434,194 @@ mod tests {
         assert_eq!(subjects[0].date_of_birth_year, Some(1970));
     }
 
+    #[test]
+    fn parse_ofac_aka_types() {
+        let xml = r#"<?xml version="1.0"?>
+        <sdnList>
+            <sdnEntry>
+                <uid>67890</uid>
+                <sdnType>Individual</sdnType>
+                <firstName>Muammar</firstName>
+                <lastName>QADHAFI</lastName>
+                <akaList>
+                    <aka>
+                        <uid>1</uid>
+                        <type>a.k.a.</type>
+                        <category>strong</category>
+                        <lastName>GADDAFI</lastName>
+                        <firstName>Moammar</firstName>
+                    </aka>
+                    <aka>
+                        <uid>2</uid>
+                        <type>a.k.a.</type>
+                        <category>weak</category>
+                        <lastName>Brother Leader</lastName>
+                    </aka>
+                    <aka>
+                        <uid>3</uid>
+                        <type>f.k.a.</type>
+                        <category>strong</category>
+                        <lastName>KADAFI</lastName>
+                    </aka>
+                </akaList>
+            </sdnEntry>
+        </sdnList>"#;
+
+        let subjects = parse_ofac_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 1);
+        let s = &subjects[0];
+        assert_eq!(s.primary_name, "Muammar QADHAFI");
+        assert_eq!(s.aliases.len(), 3);
+        assert_eq!(s.aliases[0].name, "Moammar GADDAFI");
+        assert_eq!(s.aliases[0].alias_type, "aka");
+        assert_eq!(s.aliases[1].alias_type, "low_quality_aka");
+        assert_eq!(s.aliases[2].alias_type, "fka");
+    }
+
+    #[test]
+    fn parse_ofac_details() {
+        let xml = r#"<?xml version="1.0"?>
+        <sdnList>
+            <sdnEntry>
+                <uid>7890</uid>
+                <lastName>HUSSEIN</lastName>
+                <firstName>Uday</firstName>
+                <sdnType>Individual</sdnType>
+                <remarks>Son of Saddam Hussein</remarks>
+                <programList>
+                    <program>IRAQ2</program>
+                    <program>IRAQ3</program>
+                </programList>
+                <idList>
+                    <id>
+                        <uid>101</uid>
+                        <idType>Passport</idType>
+                        <idNumber>A1234567</idNumber>
+                        <idCountry>Iraq</idCountry>
+                        <issueDate>01 Jan 2001</issueDate>
+                    </id>
+                </idList>
+                <addressList>
+                    <address>
+                        <uid>201</uid>
+                        <address1>Al-Mansour Street</address1>
+                        <city>Baghdad</city>
+                        <country>Iraq</country>
+                    </address>
+                </addressList>
+                <nationalityList>
+                    <nationality>
+                        <uid>301</uid>
+                        <country>Iraq</country>
+                        <mainEntry>true</mainEntry>
+                    </nationality>
+                </nationalityList>
+                <placeOfBirthList>
+                    <placeOfBirthItem>
+                        <uid>401</uid>
+                        <placeOfBirth>Tikrit, Iraq</placeOfBirth>
+                    </placeOfBirthItem>
+                </placeOfBirthList>
+            </sdnEntry>
+        </sdnList>"#;
+
+        let subjects = parse_ofac_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 1);
+        let s = &subjects[0];
+        assert_eq!(s.source_ref, "ofac_7890");
+        assert_eq!(s.nationalities, vec!["IQ".to_string()]);
+
+        let details = &s.details;
+        assert_eq!(details.remarks.as_deref(), Some("Son of Saddam Hussein"));
+        assert_eq!(details.programs, vec!["IRAQ2".to_string(), "IRAQ3".to_string()]);
+        assert_eq!(details.identifiers.len(), 1);
+        assert_eq!(details.identifiers[0].id_type, "Passport");
+        assert_eq!(details.identifiers[0].number, "A1234567");
+        assert_eq!(details.identifiers[0].country.as_deref(), Some("Iraq"));
+        assert_eq!(details.identifiers[0].issued_on.as_deref(), Some("01 Jan 2001"));
+        assert_eq!(details.addresses.len(), 1);
+        assert_eq!(details.addresses[0].street.as_deref(), Some("Al-Mansour Street"));
+        assert_eq!(details.addresses[0].city.as_deref(), Some("Baghdad"));
+        assert_eq!(details.places_of_birth, vec!["Tikrit, Iraq".to_string()]);
+    }
+
+    #[test]
+    fn parse_ofac_vessel_and_aircraft() {
+        let xml = r#"<?xml version="1.0"?>
+        <sdnList>
+            <sdnEntry>
+                <uid>9001</uid>
+                <lastName>ADRIAN DARYA 1</lastName>
+                <sdnType>Vessel</sdnType>
+                <programList><program>IRGC</program></programList>
+                <akaList>
+                    <aka><uid>1</uid><type>f.k.a.</type><category>strong</category><lastName>GRACE 1</lastName></aka>
+                </akaList>
+                <idList>
+                    <id>
+                        <uid>2</uid>
+                        <idType>Vessel Registration Identification</idType>
+                        <idNumber>IMO 9116412</idNumber>
+                    </id>
+                </idList>
+                <vesselInfo>
+                    <callSign>9HA4512</callSign>
+                    <vesselType>Crude Oil Tanker</vesselType>
+                    <vesselFlag>Iran</vesselFlag>
+                    <grossRegisteredTonnage>156880</grossRegisteredTonnage>
+                </vesselInfo>
+            </sdnEntry>
+            <sdnEntry>
+                <uid>9002</uid>
+                <lastName>EP-MNB</lastName>
+                <sdnType>Aircraft</sdnType>
+                <idList>
+                    <id>
+                        <uid>3</uid>
+                        <idType>Aircraft Manufacturer's Serial Number (MSN)</idType>
+                        <idNumber>550</idNumber>
+                    </id>
+                    <id>
+                        <uid>4</uid>
+                        <idType>Aircraft Model</idType>
+                        <idNumber>A310-304</idNumber>
+                    </id>
+                    <id>
+                        <uid>5</uid>
+                        <idType>Aircraft Operator</idType>
+                        <idNumber>MAHAN AIR</idNumber>
+                    </id>
+                </idList>
+            </sdnEntry>
+        </sdnList>"#;
+
+        let subjects = parse_ofac_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 2);
+
+        let vessel = &subjects[0];
+        assert_eq!(vessel.kind, SubjectKind::Vessel);
+        assert_eq!(vessel.country.as_deref(), Some("IR"));
+        assert_eq!(vessel.aliases[0].name, "GRACE 1");
+        assert_eq!(vessel.aliases[0].alias_type, "fka");
+        assert_eq!(vessel.details.identifiers[0].number, "IMO 9116412");
+        let info = vessel.details.vessel.as_ref().unwrap();
+        assert_eq!(info.flag.as_deref(), Some("Iran"));
+        assert_eq!(info.call_sign.as_deref(), Some("9HA4512"));
+        assert_eq!(info.vessel_type.as_deref(), Some("Crude Oil Tanker"));
+        assert_eq!(info.tonnage.as_deref(), Some("156880"));
+        assert!(vessel.details.aircraft.is_none());
+
+        let aircraft = &subjects[1];
+        assert_eq!(aircraft.kind, SubjectKind::Aircraft);
+        let info = aircraft.details.aircraft.as_ref().unwrap();
+        assert_eq!(info.tail_number.as_deref(), Some("EP-MNB"));
+        assert_eq!(info.serial_number.as_deref(), Some("550"));
+        assert_eq!(info.model.as_deref(), Some("A310-304"));
+        assert_eq!(info.operator.as_deref(), Some("MAHAN AIR"));
+        // Model and operator are not identifiers
+        assert_eq!(aircraft.details.identifiers.len(), 1);
+    }
+
     #[test]
     fn extract_year_various_formats() {
         assert_eq!(extract_year("1970-01-15"), Some(1970));
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use crate::parser_eu::{ParsedSubject, ParsedAlias, SubjectKind};
+use crate::parser_eu::{ParsedSubject, ParsedAlias, SubjectKind, SubjectDetails};
 
 pub fn parse_switzerland_sanctions(xml: &[u8]) -> Result<Vec<ParsedSubject>> {
     use quick_xml::events::Event;
@@ -33,6 +33,7 @@ pub fn parse_switzerland_sanctions(xml: &[u8]) -> Result<Vec<ParsedSubject>> {
                             date_of_birth_year: None,
                             country: None,
                             nationalities: Vec::new(),
+                            details: SubjectDetails::default(),
                         });
                     }
                     b"Name" | b"FullName" => in_name = true,
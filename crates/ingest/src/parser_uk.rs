@@ -2,7 +2,9 @@ use anyhow::Result;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use crate::parser_eu::{ParsedAlias, ParsedSubject, SubjectKind};
+use crate::parser_eu::{
+    ParsedAddress, ParsedAlias, ParsedIdentifier, ParsedSubject, SubjectDetails, SubjectKind, VesselDetails,
+};
 
 /// Parse UK Sanctions List XML
 pub fn parse_uk_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
@@ -15,6 +17,8 @@ pub fn parse_uk_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
     // Track current element context
     let mut in_designation = false;
     let mut current_builder: Option<SubjectBuilder> = None;
+    let mut current_address: Option<ParsedAddress> = None;
+    let mut current_birth_place: Option<Vec<String>> = None;
     let mut current_element = String::new();
 
     loop {
@@ -27,6 +31,10 @@ pub fn parse_uk_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                 if name == "Designation" {
                     in_designation = true;
                     current_builder = Some(SubjectBuilder::new());
+                } else if name == "Address" && in_designation {
+                    current_address = Some(ParsedAddress::default());
+                } else if name == "Location" && in_designation {
+                    current_birth_place = Some(Vec::new());
                 }
             }
             Ok(Event::End(ref e)) => {
@@ -39,6 +47,18 @@ pub fn parse_uk_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                         }
                     }
                     in_designation = false;
+                } else if name == "Address" {
+                    if let (Some(address), Some(builder)) = (current_address.take(), current_builder.as_mut()) {
+                        if !address.is_empty() {
+                            builder.details.addresses.push(address);
+                        }
+                    }
+                } else if name == "Location" {
+                    if let (Some(place), Some(builder)) = (current_birth_place.take(), current_builder.as_mut()) {
+                        if !place.is_empty() {
+                            builder.details.places_of_birth.push(place.join(", "));
+                        }
+                    }
                 }
                 current_element.clear();
             }
@@ -49,6 +69,29 @@ pub fn parse_uk_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                         continue;
                     }
                     
+                    if let Some(ref mut address) = current_address {
+                        match current_element.as_str() {
+                            "AddressLine1" | "AddressLine2" | "AddressLine3" | "AddressLine4" => {
+                                address.street = Some(match address.street.take() {
+                                    Some(street) => format!("{}, {}", street, text),
+                                    None => text,
+                                });
+                            }
+                            "AddressLine5" => address.city = Some(text),
+                            "AddressLine6" => address.region = Some(text),
+                            "AddressPostalCode" => address.postal_code = Some(text),
+                            "AddressCountry" => address.country = Some(text),
+                            _ => {}
+                        }
+                        continue;
+                    }
+                    if let Some(ref mut place) = current_birth_place {
+                        if matches!(current_element.as_str(), "TownOfBirth" | "CountryOfBirth") {
+                            place.push(text);
+                        }
+                        continue;
+                    }
+
                     let builder = current_builder.as_mut().unwrap();
                     
                     match current_element.as_str() {
@@ -78,6 +121,25 @@ pub fn parse_uk_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                                 builder.date_of_birth_year = Some(year);
                             }
                         }
+                        "RegimeName" if !builder.details.programs.contains(&text) => {
+                            builder.details.programs.push(text);
+                        }
+                        "DateDesignated" => builder.details.listed_on = Some(text),
+                        "OtherInformation" => builder.details.remarks = Some(text),
+                        "PassportNumber" => builder.add_identifier("Passport", text),
+                        // Ship designations
+                        "IMONumber" => builder.add_identifier("IMO number", text),
+                        "CurrentBelievedFlagOfShip" => builder.vessel().flag = Some(text),
+                        "CallSign" => builder.vessel().call_sign = Some(text),
+                        "TypeOfShip" => builder.vessel().vessel_type = Some(text),
+                        "TonnageOfShip" => builder.vessel().tonnage = Some(text),
+                        "CurrentOwnerOperator" => builder.vessel().owner = Some(text),
+                        "NationalIdentifierNumber" => builder.add_identifier("National ID", text),
+                        "PassportAdditionalInformation" | "NationalIdentifierAdditionalInformation" => {
+                            if let Some(identifier) = builder.details.identifiers.last_mut() {
+                                identifier.note = Some(text);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -141,6 +203,7 @@ struct SubjectBuilder {
     date_of_birth_year: Option<i32>,
     country: Option<String>,
     nationalities: Vec<String>,
+    details: SubjectDetails,
 }
 
 impl SubjectBuilder {
@@ -155,6 +218,7 @@ impl SubjectBuilder {
             date_of_birth_year: None,
             country: None,
             nationalities: Vec::new(),
+            details: SubjectDetails::default(),
         }
     }
 
@@ -170,6 +234,21 @@ impl SubjectBuilder {
         }
     }
 
+    fn add_identifier(&mut self, id_type: &str, number: String) {
+        self.details.identifiers.push(ParsedIdentifier {
+            id_type: id_type.to_string(),
+            number,
+            country: None,
+            issued_on: None,
+            expires_on: None,
+            note: None,
+        });
+    }
+
+    fn vessel(&mut self) -> &mut VesselDetails {
+        self.details.vessel.get_or_insert_with(VesselDetails::default)
+    }
+
     fn add_country(&mut self, country: &str) {
         if !country.is_empty() && self.country.is_none() {
             // UK uses ISO codes or full names
@@ -202,8 +281,10 @@ impl SubjectBuilder {
             None => format!("uk_{}", primary_name.chars().filter(|c| c.is_alphanumeric()).take(20).collect::<String>()),
         };
 
-        let kind = match self.group_type.as_deref() {
-            Some(t) if t.to_uppercase().contains("INDIVIDUAL") || t.to_uppercase().contains("PERSON") => SubjectKind::Person,
+        let kind = match self.group_type.as_deref().map(str::to_uppercase) {
+            Some(t) if t.contains("INDIVIDUAL") || t.contains("PERSON") => SubjectKind::Person,
+            Some(t) if t == "SHIP" || t.contains("VESSEL") => SubjectKind::Vessel,
+            Some(t) if t.contains("AIRCRAFT") => SubjectKind::Aircraft,
             _ => SubjectKind::Entity,
         };
 
@@ -221,6 +302,7 @@ impl SubjectBuilder {
             date_of_birth_year: self.date_of_birth_year,
             country: self.country,
             nationalities: self.nationalities,
+            details: self.details,
         })
     }
 }
@@ -252,6 +334,89 @@ mod tests {
         assert_eq!(subjects[0].country, Some("GB".to_string()));
     }
 
+    #[test]
+    fn parse_uk_details() {
+        let xml = r#"<?xml version="1.0"?>
+        <Designations>
+            <Designation>
+                <UniqueID>RUS0001</UniqueID>
+                <GroupTypeDescription>Individual</GroupTypeDescription>
+                <Name1>Ivan</Name1>
+                <Name6>Petrov</Name6>
+                <RegimeName>Russia</RegimeName>
+                <DateDesignated>15/03/2022</DateDesignated>
+                <OtherInformation>Member of the State Duma.</OtherInformation>
+                <Addresses>
+                    <Address>
+                        <AddressLine1>1 Red Square</AddressLine1>
+                        <AddressLine5>Moscow</AddressLine5>
+                        <AddressCountry>Russia</AddressCountry>
+                    </Address>
+                </Addresses>
+                <IndividualDetails>
+                    <Individual>
+                        <PassportDetails>
+                            <PassportDetail>
+                                <PassportNumber>721234567</PassportNumber>
+                                <PassportAdditionalInformation>Russian</PassportAdditionalInformation>
+                            </PassportDetail>
+                        </PassportDetails>
+                        <BirthDetails>
+                            <Location>
+                                <TownOfBirth>Kazan</TownOfBirth>
+                                <CountryOfBirth>Russia</CountryOfBirth>
+                            </Location>
+                        </BirthDetails>
+                    </Individual>
+                </IndividualDetails>
+            </Designation>
+        </Designations>"#;
+
+        let subjects = parse_uk_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 1);
+        let details = &subjects[0].details;
+        assert_eq!(details.programs, vec!["Russia".to_string()]);
+        assert_eq!(details.listed_on.as_deref(), Some("15/03/2022"));
+        assert_eq!(details.remarks.as_deref(), Some("Member of the State Duma."));
+        assert_eq!(details.addresses.len(), 1);
+        assert_eq!(details.addresses[0].street.as_deref(), Some("1 Red Square"));
+        assert_eq!(details.addresses[0].city.as_deref(), Some("Moscow"));
+        assert_eq!(details.identifiers.len(), 1);
+        assert_eq!(details.identifiers[0].id_type, "Passport");
+        assert_eq!(details.identifiers[0].number, "721234567");
+        assert_eq!(details.identifiers[0].note.as_deref(), Some("Russian"));
+        assert_eq!(details.places_of_birth, vec!["Kazan, Russia".to_string()]);
+    }
+
+    #[test]
+    fn parse_uk_ship() {
+        let xml = r#"<?xml version="1.0"?>
+        <Designations>
+            <Designation>
+                <UniqueID>RUS1800</UniqueID>
+                <GroupTypeDescription>Ship</GroupTypeDescription>
+                <Name6>NS CHAMPION</Name6>
+                <ShipDetails>
+                    <ShipDetail>
+                        <IMONumber>9299680</IMONumber>
+                        <CurrentBelievedFlagOfShip>Gabon</CurrentBelievedFlagOfShip>
+                        <TypeOfShip>Crude Oil Tanker</TypeOfShip>
+                    </ShipDetail>
+                </ShipDetails>
+            </Designation>
+        </Designations>"#;
+
+        let subjects = parse_uk_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 1);
+        assert_eq!(subjects[0].kind, SubjectKind::Vessel);
+        let details = &subjects[0].details;
+        assert_eq!(details.identifiers[0].id_type, "IMO number");
+        assert_eq!(details.identifiers[0].number, "9299680");
+        let vessel = details.vessel.as_ref().unwrap();
+        assert_eq!(vessel.flag.as_deref(), Some("Gabon"));
+        assert_eq!(vessel.vessel_type.as_deref(), Some("Crude Oil Tanker"));
+    }
+
     #[test]
     fn extract_year_uk_formats() {
         assert_eq!(extract_year_uk("15/01/1970"), Some(1970));
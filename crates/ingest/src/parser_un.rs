@@ -2,7 +2,7 @@ use anyhow::Result;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use crate::parser_eu::{ParsedAlias, ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedAddress, ParsedAlias, ParsedIdentifier, ParsedSubject, SubjectDetails, SubjectKind};
 
 /// Parse UN Security Council consolidated sanctions list XML
 pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
@@ -16,6 +16,9 @@ pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
     let mut in_individual = false;
     let mut in_entity = false;
     let mut current_subject: Option<SubjectBuilder> = None;
+    let mut current_document: Option<DocumentBuilder> = None;
+    let mut current_address: Option<ParsedAddress> = None;
+    let mut current_birth_place: Option<Vec<String>> = None;
     let mut current_element = String::new();
 
     loop {
@@ -33,6 +36,9 @@ pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                         in_entity = true;
                         current_subject = Some(SubjectBuilder::new(SubjectKind::Entity));
                     }
+                    "INDIVIDUAL_DOCUMENT" => current_document = Some(DocumentBuilder::default()),
+                    "INDIVIDUAL_ADDRESS" | "ENTITY_ADDRESS" => current_address = Some(ParsedAddress::default()),
+                    "INDIVIDUAL_PLACE_OF_BIRTH" => current_birth_place = Some(Vec::new()),
                     _ => {}
                 }
             }
@@ -49,6 +55,25 @@ pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                         in_individual = false;
                         in_entity = false;
                     }
+                    "INDIVIDUAL_DOCUMENT" => {
+                        if let (Some(document), Some(builder)) = (current_document.take(), current_subject.as_mut()) {
+                            document.finish(builder);
+                        }
+                    }
+                    "INDIVIDUAL_ADDRESS" | "ENTITY_ADDRESS" => {
+                        if let (Some(address), Some(builder)) = (current_address.take(), current_subject.as_mut()) {
+                            if !address.is_empty() {
+                                builder.details.addresses.push(address);
+                            }
+                        }
+                    }
+                    "INDIVIDUAL_PLACE_OF_BIRTH" => {
+                        if let (Some(place), Some(builder)) = (current_birth_place.take(), current_subject.as_mut()) {
+                            if !place.is_empty() {
+                                builder.details.places_of_birth.push(place.join(", "));
+                            }
+                        }
+                    }
                     _ => {}
                 }
                 current_element.clear();
@@ -60,6 +85,35 @@ pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                         continue;
                     }
                     
+                    if let Some(ref mut document) = current_document {
+                        match current_element.as_str() {
+                            "TYPE_OF_DOCUMENT" => document.id_type = Some(text),
+                            "NUMBER" => document.number = Some(text),
+                            "ISSUING_COUNTRY" | "COUNTRY_OF_ISSUE" => document.country = Some(text),
+                            "DATE_OF_ISSUE" => document.issued_on = Some(text),
+                            "NOTE" => document.note = Some(text),
+                            _ => {}
+                        }
+                        continue;
+                    }
+                    if let Some(ref mut address) = current_address {
+                        match current_element.as_str() {
+                            "STREET" => address.street = Some(text),
+                            "CITY" => address.city = Some(text),
+                            "STATE_PROVINCE" => address.region = Some(text),
+                            "ZIP_CODE" => address.postal_code = Some(text),
+                            "COUNTRY" => address.country = Some(text),
+                            _ => {}
+                        }
+                        continue;
+                    }
+                    if let Some(ref mut place) = current_birth_place {
+                        if matches!(current_element.as_str(), "CITY" | "STATE_PROVINCE" | "COUNTRY") {
+                            place.push(text);
+                        }
+                        continue;
+                    }
+
                     let builder = current_subject.as_mut().unwrap();
                     
                     match current_element.as_str() {
@@ -95,6 +149,9 @@ pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
                                 builder.date_of_birth_year = Some(year);
                             }
                         }
+                        "UN_LIST_TYPE" => builder.details.programs.push(text),
+                        "LISTED_ON" => builder.details.listed_on = Some(text),
+                        "COMMENTS1" => builder.details.remarks = Some(text),
                         _ => {}
                     }
                 }
@@ -112,6 +169,32 @@ pub fn parse_un_xml(xml_data: &[u8]) -> Result<Vec<ParsedSubject>> {
     Ok(subjects)
 }
 
+/// An `<INDIVIDUAL_DOCUMENT>`: passport, national identification number, ...
+#[derive(Default)]
+struct DocumentBuilder {
+    id_type: Option<String>,
+    number: Option<String>,
+    country: Option<String>,
+    issued_on: Option<String>,
+    note: Option<String>,
+}
+
+impl DocumentBuilder {
+    fn finish(self, builder: &mut SubjectBuilder) {
+        let (Some(id_type), Some(number)) = (self.id_type, self.number) else {
+            return;
+        };
+        builder.details.identifiers.push(ParsedIdentifier {
+            id_type,
+            number,
+            country: self.country,
+            issued_on: self.issued_on,
+            expires_on: None,
+            note: self.note,
+        });
+    }
+}
+
 struct SubjectBuilder {
     source_ref: Option<String>,
     kind: SubjectKind,
@@ -121,6 +204,7 @@ struct SubjectBuilder {
     date_of_birth_year: Option<i32>,
     country: Option<String>,
     nationalities: Vec<String>,
+    details: SubjectDetails,
 }
 
 impl SubjectBuilder {
@@ -134,6 +218,7 @@ impl SubjectBuilder {
             date_of_birth_year: None,
             country: None,
             nationalities: Vec::new(),
+            details: SubjectDetails::default(),
         }
     }
 
@@ -183,6 +268,7 @@ impl SubjectBuilder {
             date_of_birth_year: self.date_of_birth_year,
             country: self.country,
             nationalities: self.nationalities,
+            details: self.details,
         })
     }
 }
@@ -213,4 +299,48 @@ mod tests {
         assert_eq!(subjects[0].country, Some("US".to_string()));
         assert_eq!(subjects[0].date_of_birth_year, Some(1970));
     }
+
+    #[test]
+    fn parse_un_details() {
+        let xml = r#"<?xml version="1.0"?>
+        <CONSOLIDATED_LIST>
+            <INDIVIDUALS>
+                <INDIVIDUAL>
+                    <DATAID>6908555</DATAID>
+                    <FIRST_NAME>RI</FIRST_NAME>
+                    <SECOND_NAME>WON HO</SECOND_NAME>
+                    <UN_LIST_TYPE>DPRK</UN_LIST_TYPE>
+                    <REFERENCE_NUMBER>KPi.001</REFERENCE_NUMBER>
+                    <LISTED_ON>2016-11-30</LISTED_ON>
+                    <COMMENTS1>Ri Won Ho is a DPRK Ministry of State Security Official.</COMMENTS1>
+                    <INDIVIDUAL_ADDRESS>
+                        <CITY>Pyongyang</CITY>
+                        <COUNTRY>Democratic People's Republic of Korea</COUNTRY>
+                    </INDIVIDUAL_ADDRESS>
+                    <INDIVIDUAL_PLACE_OF_BIRTH>
+                        <CITY>Hamhung</CITY>
+                        <COUNTRY>Democratic People's Republic of Korea</COUNTRY>
+                    </INDIVIDUAL_PLACE_OF_BIRTH>
+                    <INDIVIDUAL_DOCUMENT>
+                        <TYPE_OF_DOCUMENT>Passport</TYPE_OF_DOCUMENT>
+                        <NUMBER>381310014</NUMBER>
+                        <ISSUING_COUNTRY>Democratic People's Republic of Korea</ISSUING_COUNTRY>
+                    </INDIVIDUAL_DOCUMENT>
+                </INDIVIDUAL>
+            </INDIVIDUALS>
+        </CONSOLIDATED_LIST>"#;
+
+        let subjects = parse_un_xml(xml.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 1);
+        let details = &subjects[0].details;
+        assert_eq!(details.programs, vec!["DPRK".to_string()]);
+        assert_eq!(details.listed_on.as_deref(), Some("2016-11-30"));
+        assert!(details.remarks.as_deref().unwrap().starts_with("Ri Won Ho"));
+        assert_eq!(details.addresses.len(), 1);
+        assert_eq!(details.addresses[0].city.as_deref(), Some("Pyongyang"));
+        assert_eq!(details.places_of_birth, vec!["Hamhung, Democratic People's Republic of Korea".to_string()]);
+        assert_eq!(details.identifiers.len(), 1);
+        assert_eq!(details.identifiers[0].id_type, "Passport");
+        assert_eq!(details.identifiers[0].number, "381310014");
+    }
 }
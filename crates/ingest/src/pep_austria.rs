@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 pub async fn fetch_austria_parliament() -> Result<Vec<ParsedSubject>> {
     let client = reqwest::Client::builder()
@@ -36,6 +36,7 @@ pub async fn fetch_austria_parliament() -> Result<Vec<ParsedSubject>> {
                     date_of_birth_year: None,
                     country: Some("AT".to_string()),
                     nationalities: vec!["AT".to_string()],
+                    details: SubjectDetails::default(),
                 });
             }
         }
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 const TWEEDE_KAMER_URL: &str = "https://www.tweedekamer.nl";
 
@@ -70,6 +70,7 @@ fn parse_tweede_kamer_html(html: &str) -> Vec<ParsedSubject> {
                                 date_of_birth_year: None,
                                 country: Some("NL".to_string()),
                                 nationalities: vec!["NL".to_string()],
+                                details: SubjectDetails::default(),
                             });
                         }
                     }
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 // Try multiple URLs for European Commission
 const COMMISSION_URLS: &[&str] = &[
@@ -122,6 +122,7 @@ fn parse_commission_html(html: &str) -> Vec<ParsedSubject> {
                 date_of_birth_year: None,
                 country: Some(country.to_string()),
                 nationalities: vec![country.to_string()],
+                details: SubjectDetails::default(),
             });
             
             tracing::debug!(name, role, "added commissioner");
@@ -167,6 +168,7 @@ fn parse_commissioners_from_html(html: &str) -> Vec<ParsedSubject> {
                             date_of_birth_year: None,
                             country,
                             nationalities: vec![],
+                            details: SubjectDetails::default(),
                         });
                     }
                 }
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedAlias, ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedAlias, ParsedSubject, SubjectKind, SubjectDetails};
 
 const MEP_API_URL: &str = "https://www.europarl.europa.eu/meps/en/full-list/all";
 const MEP_XML_URL: &str = "https://www.europarl.europa.eu/meps/en/xml/";
@@ -105,6 +105,7 @@ fn parse_mep_line(line: &str) -> Option<ParsedSubject> {
         date_of_birth_year: None,
         country,
         nationalities: Vec::new(),
+        details: SubjectDetails::default(),
     })
 }
 
@@ -167,6 +168,7 @@ fn parse_mep_xml_structured(xml: &str) -> Result<Vec<ParsedSubject>> {
                             date_of_birth_year: None,
                             country: country_name_to_iso(&current_country),
                             nationalities: Vec::new(),
+                            details: SubjectDetails::default(),
                         });
                     }
                     in_mep = false;
@@ -242,6 +244,7 @@ fn parse_mep_html(html: &str) -> Vec<ParsedSubject> {
                         date_of_birth_year: None,
                         country,
                         nationalities: Vec::new(),
+                        details: SubjectDetails::default(),
                     });
                 }
             }
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 const ASSEMBLEE_URL: &str = "https://www.assemblee-nationale.fr";
 
@@ -70,6 +70,7 @@ fn parse_assemblee_html(html: &str) -> Vec<ParsedSubject> {
                                 date_of_birth_year: None,
                                 country: Some("FR".to_string()),
                                 nationalities: vec!["FR".to_string()],
+                                details: SubjectDetails::default(),
                             });
                         }
                     }
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 const BUNDESTAG_API_URL: &str = "https://www.bundestag.de/api";
 const BUNDESTAG_MEMBERS_URL: &str = "https://www.bundestag.de/abgeordnete";
@@ -62,6 +62,7 @@ fn parse_bundestag_api(json: &str) -> Result<Vec<ParsedSubject>> {
                         date_of_birth_year: None,
                         country: Some("DE".to_string()),
                         nationalities: vec!["DE".to_string()],
+                        details: SubjectDetails::default(),
                     });
                 }
             }
@@ -125,6 +126,7 @@ fn parse_bundestag_html(html: &str) -> Vec<ParsedSubject> {
                                 date_of_birth_year: None,
                                 country: Some("DE".to_string()),
                                 nationalities: vec!["DE".to_string()],
+                                details: SubjectDetails::default(),
                             });
                         }
                     }
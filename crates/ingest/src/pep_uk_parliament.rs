@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 const UK_PARLIAMENT_API_URL: &str = "https://members-api.parliament.uk/api";
 const UK_PARLIAMENT_MEMBERS_URL: &str = "https://members.parliament.uk/members/commons";
@@ -115,6 +115,7 @@ fn parse_uk_parliament_api(json: &str, chamber: &str) -> Result<Vec<ParsedSubjec
                         date_of_birth_year: None,
                         country: Some("GB".to_string()),
                         nationalities: vec!["GB".to_string()],
+                        details: SubjectDetails::default(),
                     });
                 }
             }
@@ -196,6 +197,7 @@ fn parse_uk_parliament_html(html: &str, chamber: &str) -> Vec<ParsedSubject> {
                                 date_of_birth_year: None,
                                 country: Some("GB".to_string()),
                                 nationalities: vec!["GB".to_string()],
+                                details: SubjectDetails::default(),
                             });
                         }
                     }
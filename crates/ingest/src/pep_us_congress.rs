@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::time::Duration;
 
-use crate::parser_eu::{ParsedSubject, SubjectKind};
+use crate::parser_eu::{ParsedSubject, SubjectKind, SubjectDetails};
 
 const CONGRESS_API_URL: &str = "https://www.congress.gov/api";
 const CONGRESS_MEMBERS_URL: &str = "https://www.congress.gov/members";
@@ -156,6 +156,7 @@ fn parse_congress_html(html: &str, chamber: &str) -> Vec<ParsedSubject> {
                         date_of_birth_year: None,
                         country: Some("US".to_string()),
                         nationalities: vec!["US".to_string()],
+                        details: SubjectDetails::default(),
                     });
                 }
             }
@@ -193,6 +194,7 @@ fn parse_congress_html(html: &str, chamber: &str) -> Vec<ParsedSubject> {
                                 date_of_birth_year: None,
                                 country: Some("US".to_string()),
                                 nationalities: vec!["US".to_string()],
+                                details: SubjectDetails::default(),
                             });
                         }
                     }
@@ -0,0 +1,83 @@
+//! Dates of birth as lists and customers actually supply them: often just a
+//! year, sometimes a year and month, ideally a full date.
+
+/// A date of birth known to year precision at least. Month and day are
+/// compared only when both sides know them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BirthDate {
+    pub year: i32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+impl BirthDate {
+    pub fn year(year: i32) -> Self {
+        Self {
+            year,
+            month: None,
+            day: None,
+        }
+    }
+
+    /// Parse `YYYY`, `YYYY-MM` or `YYYY-MM-DD`. Out-of-range months or days
+    /// are dropped rather than rejecting the year.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().splitn(3, '-');
+        let year = parts.next()?.parse::<i32>().ok()?;
+        let month = parts
+            .next()
+            .and_then(|m| m.parse::<u32>().ok())
+            .filter(|m| (1..=12).contains(m));
+        let day = month
+            .and(parts.next())
+            .and_then(|d| d.get(..2).unwrap_or(d).parse::<u32>().ok())
+            .filter(|d| (1..=31).contains(d));
+        Some(Self { year, month, day })
+    }
+
+    /// 1.0 for the same date as far as both sides know it, 0.5 for a date
+    /// within two years (or a known month/day that disagrees), 0.0 otherwise.
+    pub fn similarity(&self, other: &BirthDate) -> f32 {
+        let conflicts = |a: Option<u32>, b: Option<u32>| matches!((a, b), (Some(a), Some(b)) if a != b);
+        let year_gap = (self.year - other.year).abs();
+        let exact = year_gap == 0 && !conflicts(self.month, other.month) && !conflicts(self.day, other.day);
+
+        if exact {
+            1.0
+        } else if year_gap <= 2 {
+            0.5
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_partial_dates() {
+        assert_eq!(BirthDate::parse("1970"), Some(BirthDate::year(1970)));
+        assert_eq!(
+            BirthDate::parse("1970-04"),
+            Some(BirthDate { year: 1970, month: Some(4), day: None })
+        );
+        assert_eq!(
+            BirthDate::parse("1970-04-28T00:00:00"),
+            Some(BirthDate { year: 1970, month: Some(4), day: Some(28) })
+        );
+        assert_eq!(BirthDate::parse("1970-13-01"), Some(BirthDate::year(1970)));
+        assert_eq!(BirthDate::parse("unknown"), None);
+    }
+
+    #[test]
+    fn full_dates_must_agree_for_an_exact_match() {
+        let listed = BirthDate::parse("1937-04-28").unwrap();
+        assert_eq!(BirthDate::parse("1937-04-28").unwrap().similarity(&listed), 1.0);
+        assert_eq!(BirthDate::parse("1937-11-02").unwrap().similarity(&listed), 0.5);
+        // A year-only side cannot contradict the month and day
+        assert_eq!(BirthDate::year(1937).similarity(&listed), 1.0);
+        assert_eq!(BirthDate::year(1941).similarity(&listed), 0.0);
+    }
+}
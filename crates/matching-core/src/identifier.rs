@@ -0,0 +1,151 @@
+//! Identifier keys shared by the index and the matching engine.
+//!
+//! Lists describe document types in free text ("National passport",
+//! "Digital Currency Address - XBT", ...); [`classify`] maps those onto
+//! [`IdentifierType`]. A key is the type plus the normalized number, so two
+//! identifiers match exactly when their keys are equal.
+
+use aegistry_core::IdentifierType;
+
+/// The identifier type a source's document description refers to
+pub fn classify(source_type: &str) -> IdentifierType {
+    let lower = source_type.to_lowercase();
+    if lower.starts_with("digital currency address") || lower.contains("wallet") {
+        IdentifierType::CryptoWallet
+    } else if lower.contains("imo") || lower.contains("vessel registration") {
+        IdentifierType::Imo
+    } else if lower == "lei" || lower.contains("legal entity") {
+        IdentifierType::Lei
+    } else if lower.contains("passport") {
+        IdentifierType::Passport
+    } else if lower.contains("national id")
+        || lower.contains("national identification")
+        || lower.contains("identity card")
+        || lower.contains("id card")
+        || lower == "national_id"
+    {
+        IdentifierType::NationalId
+    } else if lower.contains("tax") || lower == "inn" || lower.contains("ssn") {
+        IdentifierType::TaxId
+    } else {
+        IdentifierType::Other
+    }
+}
+
+/// Compare-ready form of a document number: letters and digits only, upper
+/// case, with the "IMO" prefix lists put in front of ship numbers removed.
+/// Wallet addresses follow [`normalize_crypto_address`] instead.
+pub fn normalize_identifier(id_type: IdentifierType, number: &str) -> String {
+    if id_type == IdentifierType::CryptoWallet {
+        return normalize_crypto_address(number);
+    }
+    let normalized: String = number
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_uppercase)
+        .collect();
+    match id_type {
+        IdentifierType::Imo => normalized.trim_start_matches("IMO").to_string(),
+        _ => normalized,
+    }
+}
+
+/// The chain named by an OFAC "Digital Currency Address - XBT" style
+/// description, upper case; `None` for other identifier types
+pub fn crypto_chain(source_type: &str) -> Option<String> {
+    let rest = source_type
+        .trim()
+        .strip_prefix("Digital Currency Address")?
+        .trim_start_matches([' ', '-']);
+    Some(normalize_chain(rest))
+}
+
+/// Upper-case ticker, with BTC spelled the way OFAC lists it
+pub fn normalize_chain(chain: &str) -> String {
+    match chain.trim().to_uppercase().as_str() {
+        "BTC" => "XBT".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Compare-ready form of a wallet address. Hex (`0x...`) and bech32
+/// (`bc1...`, `ltc1...`) addresses are case-insensitive and are lower-cased;
+/// base58 addresses are case-sensitive and kept as written.
+pub fn normalize_crypto_address(address: &str) -> String {
+    let address: String = address.chars().filter(|c| !c.is_whitespace()).collect();
+    let lower = address.to_lowercase();
+    let case_insensitive = lower.starts_with("0x")
+        || ["bc1", "tb1", "ltc1", "bcrt1"].iter().any(|hrp| lower.starts_with(hrp));
+    if case_insensitive {
+        lower
+    } else {
+        address
+    }
+}
+
+/// Index term for an identifier, e.g. `passport:A1234567`
+pub fn identifier_key(id_type: IdentifierType, number: &str) -> String {
+    format!("{}:{}", id_type.as_str(), normalize_identifier(id_type, number))
+}
+
+/// The type part of a key built by [`identifier_key`]
+pub fn key_type(key: &str) -> &str {
+    key.split_once(':').map_or(key, |(id_type, _)| id_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_descriptions_are_classified() {
+        assert_eq!(classify("Passport"), IdentifierType::Passport);
+        assert_eq!(classify("National passport"), IdentifierType::Passport);
+        assert_eq!(classify("National ID No."), IdentifierType::NationalId);
+        assert_eq!(classify("Tax ID No."), IdentifierType::TaxId);
+        assert_eq!(classify("Vessel Registration Identification"), IdentifierType::Imo);
+        assert_eq!(classify("Legal Entity Number"), IdentifierType::Lei);
+        assert_eq!(classify("Digital Currency Address - XBT"), IdentifierType::CryptoWallet);
+        assert_eq!(classify("Registration Number"), IdentifierType::Other);
+    }
+
+    #[test]
+    fn keys_ignore_formatting() {
+        assert_eq!(
+            identifier_key(IdentifierType::Passport, "a 123-456.7"),
+            identifier_key(IdentifierType::Passport, "A1234567"),
+        );
+        assert_eq!(identifier_key(IdentifierType::Imo, "IMO 9187629"), "imo:9187629");
+        assert_ne!(
+            identifier_key(IdentifierType::Passport, "A1234567"),
+            identifier_key(IdentifierType::NationalId, "A1234567"),
+        );
+        assert_eq!(key_type("passport:A1234567"), "passport");
+    }
+
+    #[test]
+    fn crypto_addresses_keep_case_only_where_it_matters() {
+        assert_eq!(crypto_chain("Digital Currency Address - XBT").as_deref(), Some("XBT"));
+        assert_eq!(crypto_chain("Digital Currency Address - USDT").as_deref(), Some("USDT"));
+        assert_eq!(crypto_chain("Passport"), None);
+        assert_eq!(normalize_chain("btc"), "XBT");
+
+        assert_eq!(
+            normalize_crypto_address("0x7F367cC41522cE07553e823bf3be79A889DEbe1B"),
+            "0x7f367cc41522ce07553e823bf3be79a889debe1b",
+        );
+        assert_eq!(
+            normalize_crypto_address("BC1QSXDSFG3EUEHX2GLHY2S4U4VDRZQWJ6AHEAD7M0"),
+            "bc1qsxdsfg3euehx2glhy2s4u4vdrzqwj6ahead7m0",
+        );
+        // Base58: a different case is a different address
+        assert_eq!(
+            normalize_crypto_address(" 1AjZPMsnmpdK2Rv9KQNfMurTXinscVro9V "),
+            "1AjZPMsnmpdK2Rv9KQNfMurTXinscVro9V",
+        );
+        assert_ne!(
+            identifier_key(IdentifierType::CryptoWallet, "1AjZPMsnmpdK2Rv9KQNfMurTXinscVro9V"),
+            identifier_key(IdentifierType::CryptoWallet, "1ajzpmsnmpdk2rv9kqnfmurtxinscvro9v"),
+        );
+    }
+}
@@ -1,23 +1,108 @@
-use aegistry_core::{HitSource, ScoreComponents, SubjectKind};
+use aegistry_core::{AliasType, Hit, HitSource, MatchedAlias, RiskLevel, ScoreComponents, ScreenIdentifier, SubjectKind};
 use serde::Serialize;
 use std::collections::HashSet;
 use std::path::Path;
 use strsim::jaro_winkler;
 use tantivy::collector::TopDocs;
-use tantivy::query::{BooleanQuery, FuzzyTermQuery, Occur, Query};
-use tantivy::schema::{Field, Value};
+use tantivy::query::{BooleanQuery, FuzzyTermQuery, Occur, Query, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Value};
 use tantivy::{Index, TantivyDocument, Term};
 use unicode_normalization::UnicodeNormalization;
 
+pub mod birth_date;
+pub mod identifier;
+pub mod phonetic;
+pub mod transliterate;
+
+pub use birth_date::BirthDate;
+pub use identifier::identifier_key;
+pub use phonetic::{double_metaphone, phonetic_keys};
+pub use transliterate::transliterate_variants;
+
+/// Relative weight of each score component; the caller's risk configuration
+/// decides these, the engine only applies them.
+#[derive(Clone, Copy, Debug)]
+pub struct ScoreWeights {
+    pub name: f32,
+    pub dob: f32,
+    pub country: f32,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            name: 0.70,
+            dob: 0.10,
+            country: 0.20,
+        }
+    }
+}
+
+/// Score bands of the caller's risk configuration. The engine leaves
+/// classifying matches to the caller but keeps its boosts and caps on the
+/// intended side of these.
+#[derive(Clone, Copy, Debug)]
+pub struct ScoreThresholds {
+    /// Scores at or above this are hits
+    pub hit: f32,
+    /// Scores at or above this, and below `hit`, need review
+    pub review: f32,
+}
+
+impl Default for ScoreThresholds {
+    fn default() -> Self {
+        Self { hit: 0.95, review: 0.90 }
+    }
+}
+
+impl ScoreThresholds {
+    /// Highest score that is a review rather than a hit. Always below `hit`,
+    /// even for configs stored before equal thresholds were rejected.
+    fn review_ceiling(&self) -> f32 {
+        (self.hit - 0.01).max(self.review).min(self.hit - f32::EPSILON)
+    }
+}
+
+/// Taken off the score when the subject lists a different identifier of an input's type
+const IDENTIFIER_CONFLICT_PENALTY: f32 = 0.15;
+
+/// What to screen: a name plus whatever else the caller knows about the customer
+#[derive(Clone, Debug, Default)]
+pub struct ScreeningQuery {
+    pub name: String,
+    pub country: Option<String>,
+    pub dob: Option<BirthDate>,
+    /// Only subjects of this kind are considered, and entity names are
+    /// compared without their legal-form words ("Ltd", "GmbH", ...)
+    pub kind: Option<SubjectKind>,
+    pub identifiers: Vec<ScreenIdentifier>,
+}
+
+impl ScreeningQuery {
+    pub fn name(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+}
+
 pub struct MatchingEngine {
     index: Index,
     subject_id: Field,
     primary_name: Field,
     aliases: Field,
+    alias_names: Field,
+    alias_types: Field,
+    translit: Field,
+    phonetic: Field,
     country: Field,
     dob_year: Field,
+    date_of_birth: Field,
     source: Field,
     kind: Field,
+    record_hash: Field,
+    identifiers: Field,
 }
 
 impl MatchingEngine {
@@ -30,76 +115,80 @@ impl MatchingEngine {
             subject_id: schema.get_field("subject_id").unwrap(),
             primary_name: schema.get_field("primary_name").unwrap(),
             aliases: schema.get_field("aliases").unwrap(),
+            alias_names: schema.get_field("alias_names")?,
+            alias_types: schema.get_field("alias_types")?,
+            translit: schema.get_field("translit")?,
+            phonetic: schema.get_field("phonetic")?,
             country: schema.get_field("country").unwrap(),
             dob_year: schema.get_field("dob_year").unwrap(),
+            date_of_birth: schema.get_field("date_of_birth")?,
             source: schema.get_field("source").unwrap(),
             kind: schema.get_field("kind").unwrap(),
+            record_hash: schema.get_field("record_hash")?,
+            identifiers: schema.get_field("identifiers")?,
         })
     }
 
+    /// Score the query against the index: by name, and by exact identifier
+    /// for subjects whose names are too different to be found otherwise.
+    /// An index that cannot be searched is an error, never an empty result.
     pub fn search_and_score(
         &self,
-        name: &str,
-        country: Option<&str>,
-        dob_year: Option<i32>,
+        query: &ScreeningQuery,
         max_results: usize,
-    ) -> Vec<MatchResult> {
+        weights: &ScoreWeights,
+        thresholds: &ScoreThresholds,
+    ) -> anyhow::Result<Vec<MatchResult>> {
+        let (name, country, dob, kind) = (query.name.as_str(), query.country.as_deref(), query.dob, query.kind);
+        let entity = matches!(kind, Some(SubjectKind::Entity));
+        let input_identifiers: Vec<(String, &ScreenIdentifier)> = query
+            .identifiers
+            .iter()
+            .map(|id| (identifier_key(id.id_type, &id.number), id))
+            .collect();
+        let identifier_keys: Vec<&str> = input_identifiers.iter().map(|(key, _)| key.as_str()).collect();
+
         // Get more candidates to ensure we find good matches
-        let candidates = match self.search_candidates(name, max_results * 10) {
-            Ok(c) => c,
-            Err(e) => {
-                tracing::warn!(error = %e, "search failed, returning empty");
-                return Vec::new();
-            }
-        };
+        let candidates = self.search_candidates(name, kind, &identifier_keys, max_results * 10)?;
+
+        // The input as typed plus its romanisations; each is scored and the best wins
+        let input_forms = name_forms(name, entity);
 
-        let norm_input = normalize_name(name);
-        let input_parts: Vec<&str> = norm_input.split_whitespace().collect();
-        
         let mut results: Vec<MatchResult> = candidates
             .into_iter()
             .map(|candidate| {
-                let norm_subject = normalize_name(&candidate.primary_name);
-                
-                // Use improved name similarity that considers both full name and parts
-                let name_similarity = compute_name_similarity(&norm_input, &input_parts, &norm_subject);
+                let (name_similarity, matched_alias) = best_name_match(&input_forms, &candidate, entity);
 
                 let country_match = match (country, candidate.country.as_deref()) {
                     (Some(c_in), Some(c_subj)) if c_in.eq_ignore_ascii_case(c_subj) => 1.0,
                     _ => 0.0,
                 };
 
-                let dob_similarity = match (dob_year, candidate.dob_year) {
-                    (Some(inp), Some(subj)) if inp == subj => 1.0,
-                    (Some(inp), Some(subj)) if (inp - subj).abs() <= 2 => 0.5,
+                let dob_similarity = match (dob, candidate.dob) {
+                    (Some(inp), Some(subj)) => inp.similarity(&subj),
                     _ => 0.0,
                 };
 
+                let matched_name = matched_alias
+                    .as_ref()
+                    .map_or(candidate.primary_name.as_str(), |a| a.name.as_str());
+                let phonetic_match = forms_sound_alike(&input_forms, matched_name, entity);
+                let (identifier_match, matched_identifier) =
+                    compare_identifiers(&input_identifiers, &candidate.identifiers);
+
                 let components = ScoreComponents {
                     name_similarity,
                     dob_similarity,
                     country_match,
+                    phonetic_match,
+                    identifier_match,
                 };
 
                 // Weighted score: name is most important
-                // Base score calculation
-                let mut score = 0.70 * name_similarity + 0.20 * country_match + 0.10 * dob_similarity;
-                
-                // Boost for perfect matches:
-                // - Perfect name match (1.0) + country match = guaranteed high score
-                if name_similarity >= 0.99 && country_match >= 1.0 {
-                    score = score.max(0.95); // Guarantee Hit level for perfect matches
-                }
-                
-                // Stricter requirements for high confidence (but allow perfect matches):
-                // - If country provided but doesn't match, cap at Review level (unless perfect name match)
-                if country.is_some() && country_match < 1.0 && name_similarity < 0.99 && score > 0.90 {
-                    score = score.min(0.89); // Cap at Review level
-                }
-                // - If DOB provided but doesn't match, cap at Review level (unless perfect name+country match)
-                if dob_year.is_some() && dob_similarity < 1.0 && (name_similarity < 0.99 || country_match < 1.0) && score > 0.90 {
-                    score = score.min(0.89); // Cap at Review level
-                }
+                let score = weights.name * name_similarity
+                    + weights.country * country_match
+                    + weights.dob * dob_similarity;
+                let score = adjust_score(score, &components, country.is_some(), dob.is_some(), thresholds);
 
                 MatchResult {
                     subject_id: candidate.subject_id,
@@ -107,24 +196,35 @@ impl MatchingEngine {
                     source: parse_source(&candidate.source),
                     kind: parse_kind(&candidate.kind),
                     country: candidate.country,
-                    dob_year: candidate.dob_year,
+                    dob_year: candidate.dob.map(|d| d.year),
                     score,
                     components,
+                    matched_alias,
+                    matched_identifier,
+                    record_version: candidate.record_version,
                 }
             })
             .collect();
 
         results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
         results.truncate(max_results);
-        results
+        Ok(results)
     }
 
-    fn search_candidates(&self, query: &str, limit: usize) -> anyhow::Result<Vec<Candidate>> {
+    fn search_candidates(
+        &self,
+        query: &str,
+        kind: Option<SubjectKind>,
+        identifier_keys: &[&str],
+        limit: usize,
+    ) -> anyhow::Result<Vec<Candidate>> {
         let reader = self.index.reader()?;
         let searcher = reader.searcher();
 
-        let normalized_query = normalize_name(query);
-        let words: Vec<&str> = normalized_query.split_whitespace().collect();
+        let forms = name_forms(query, matches!(kind, Some(SubjectKind::Entity)));
+        let mut words: Vec<&str> = forms.iter().flat_map(|f| f.split_whitespace()).collect();
+        words.sort_unstable();
+        words.dedup();
         
         // Build a query that requires ALL words to match (with fuzzy tolerance)
         // This ensures "vladimir putin" finds entries containing both words
@@ -141,11 +241,36 @@ impl MatchingEngine {
                 let alias_term = Term::from_field_text(self.aliases, word);
                 let alias_fuzzy = FuzzyTermQuery::new(alias_term, 1, true);
                 should_clauses.push((Occur::Should, Box::new(alias_fuzzy)));
+
+                // And in the romanised variants of every name
+                let translit_term = Term::from_field_text(self.translit, word);
+                let translit_fuzzy = FuzzyTermQuery::new(translit_term, 1, true);
+                should_clauses.push((Occur::Should, Box::new(translit_fuzzy)));
             }
         }
 
-        let combined_query = BooleanQuery::new(should_clauses);
-        let top_docs = searcher.search(&combined_query, &TopDocs::with_limit(limit))?;
+        // Names that sound alike but are spelled too differently for the fuzzy terms
+        for code in forms.iter().flat_map(|f| phonetic_keys(f)).collect::<HashSet<_>>() {
+            let term = Term::from_field_text(self.phonetic, &code);
+            should_clauses.push((Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic))));
+        }
+
+        let name_query = self.restrict_to_kind(BooleanQuery::new(should_clauses), kind);
+
+        // Exact identifier matches come first so a flood of name matches cannot crowd them out
+        let mut top_docs = Vec::new();
+        if !identifier_keys.is_empty() {
+            let identifier_clauses: Vec<(Occur, Box<dyn Query>)> = identifier_keys
+                .iter()
+                .map(|key| {
+                    let term = Term::from_field_text(self.identifiers, key);
+                    (Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn Query>)
+                })
+                .collect();
+            let identifier_query = self.restrict_to_kind(BooleanQuery::new(identifier_clauses), kind);
+            top_docs.extend(searcher.search(&identifier_query, &TopDocs::with_limit(limit))?);
+        }
+        top_docs.extend(searcher.search(&name_query, &TopDocs::with_limit(limit))?);
 
         let mut seen_ids = HashSet::new();
         let mut candidates = Vec::new();
@@ -175,10 +300,17 @@ impl MatchingEngine {
                 .and_then(|v| v.as_str())
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string());
-            let dob_year = doc
-                .get_first(self.dob_year)
+            // Full date where the list gave one, else just the year
+            let dob = doc
+                .get_first(self.date_of_birth)
                 .and_then(|v| v.as_str())
-                .and_then(|s| s.parse().ok());
+                .and_then(BirthDate::parse)
+                .or_else(|| {
+                    doc.get_first(self.dob_year)
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.parse().ok())
+                        .map(BirthDate::year)
+                });
             let source = doc
                 .get_first(self.source)
                 .and_then(|v| v.as_str())
@@ -189,19 +321,193 @@ impl MatchingEngine {
                 .and_then(|v| v.as_str())
                 .unwrap_or("person")
                 .to_string();
+            let record_version = doc
+                .get_first(self.record_hash)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string());
+            let aliases = doc
+                .get_all(self.alias_names)
+                .zip(doc.get_all(self.alias_types))
+                .filter_map(|(name, alias_type)| {
+                    Some((name.as_str()?.to_string(), parse_alias_type(alias_type.as_str()?)))
+                })
+                .collect();
+            let identifiers = doc
+                .get_all(self.identifiers)
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
 
             candidates.push(Candidate {
                 subject_id,
                 primary_name,
+                aliases,
                 country,
-                dob_year,
+                dob,
                 source,
                 kind,
+                record_version,
+                identifiers,
             });
         }
 
         Ok(candidates)
     }
+
+    /// `query`, limited to subjects of `kind` when one is given
+    fn restrict_to_kind(&self, query: BooleanQuery, kind: Option<SubjectKind>) -> BooleanQuery {
+        let Some(kind) = kind else {
+            return query;
+        };
+        let kind_values: &[&str] = match kind {
+            SubjectKind::Person => &["person"],
+            SubjectKind::Entity => &["entity", "enterprise"],
+            SubjectKind::Vessel => &["vessel"],
+            SubjectKind::Aircraft => &["aircraft"],
+        };
+        let kind_clauses: Vec<(Occur, Box<dyn Query>)> = kind_values
+            .iter()
+            .map(|value| {
+                let term = Term::from_field_text(self.kind, value);
+                (Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn Query>)
+            })
+            .collect();
+        BooleanQuery::new(vec![
+            (Occur::Must, Box::new(query)),
+            (Occur::Must, Box::new(BooleanQuery::new(kind_clauses))),
+        ])
+    }
+}
+
+/// Adjust a weighted score for what its components say beyond their weights.
+/// An exact name and country match is a hit; a name contradicted by the
+/// country or date of birth supplied is at most a review; a shared document
+/// number is a hit even when the name barely matches, a different one counts
+/// against the match.
+fn adjust_score(
+    mut score: f32,
+    components: &ScoreComponents,
+    country_given: bool,
+    dob_given: bool,
+    thresholds: &ScoreThresholds,
+) -> f32 {
+    let exact_name = components.name_similarity >= 0.99;
+    let country_matches = components.country_match >= 1.0;
+
+    if exact_name && country_matches {
+        score = score.max(thresholds.hit);
+    }
+    let country_conflict = country_given && !country_matches && !exact_name;
+    let dob_conflict = dob_given && components.dob_similarity < 1.0 && !(exact_name && country_matches);
+    if country_conflict || dob_conflict {
+        score = score.min(thresholds.review_ceiling());
+    }
+
+    if components.identifier_match > 0.0 {
+        score = score.max(thresholds.hit);
+    } else if components.identifier_match < 0.0 {
+        score = (score - IDENTIFIER_CONFLICT_PENALTY).max(0.0);
+    }
+    score
+}
+
+/// Compare the input identifiers with the subject's index keys. An equal key
+/// is a match (1.0, with the input identifier that matched); failing that, a
+/// subject key of the same type as an input identifier is a conflict (-1.0).
+fn compare_identifiers(
+    input: &[(String, &ScreenIdentifier)],
+    subject_keys: &[String],
+) -> (f32, Option<ScreenIdentifier>) {
+    if let Some((_, id)) = input.iter().find(|(key, _)| subject_keys.contains(key)) {
+        return (1.0, Some((*id).clone()));
+    }
+    let conflict = input.iter().any(|(_, id)| {
+        subject_keys
+            .iter()
+            .any(|key| identifier::key_type(key) == id.id_type.as_str())
+    });
+    (if conflict { -1.0 } else { 0.0 }, None)
+}
+
+/// Score the input against the primary name and every alias, keeping the best.
+/// Returns the winning similarity and, if an alias won, which one.
+fn best_name_match(input_forms: &[String], candidate: &Candidate, entity: bool) -> (f32, Option<MatchedAlias>) {
+    let mut best = forms_similarity(input_forms, &candidate.primary_name, entity);
+    let mut matched_alias = None;
+
+    for (alias, alias_type) in &candidate.aliases {
+        let mut similarity = forms_similarity(input_forms, alias, entity);
+        // Low-quality AKAs are too vague to carry the same weight as a strong name
+        if matches!(alias_type, AliasType::LowQualityAka) {
+            similarity *= 0.9;
+        }
+        // On a tie, the name that matches as written beats one that only
+        // matches through a transliteration variant; otherwise the primary
+        // name wins ties
+        let wins = if similarity == best {
+            let current = matched_alias.as_ref().map_or(&candidate.primary_name, |m: &MatchedAlias| &m.name);
+            literal_similarity(input_forms, alias, entity) > literal_similarity(input_forms, current, entity)
+        } else {
+            similarity > best
+        };
+        if wins {
+            best = similarity;
+            matched_alias = Some(MatchedAlias {
+                name: alias.clone(),
+                alias_type: *alias_type,
+            });
+        }
+    }
+
+    (best, matched_alias)
+}
+
+/// The normalized name followed by its transliteration variants, with
+/// legal-form words removed from each when matching entities
+fn name_forms(name: &str, entity: bool) -> Vec<String> {
+    let mut forms = vec![normalize_name(name)];
+    forms.extend(transliterate_variants(name));
+    if entity {
+        for form in &mut forms {
+            *form = strip_legal_forms(form);
+        }
+    }
+    forms
+}
+
+/// Best similarity between any input form and any form of `subject_name`
+fn forms_similarity(input_forms: &[String], subject_name: &str, entity: bool) -> f32 {
+    let subject_forms = name_forms(subject_name, entity);
+    let mut best = 0.0f32;
+    for input in input_forms {
+        let input_parts: Vec<&str> = input.split_whitespace().collect();
+        for subject in &subject_forms {
+            best = best.max(compute_name_similarity(input, &input_parts, subject));
+        }
+    }
+    best
+}
+
+/// Similarity of the input as written to `subject_name` as listed, leaving
+/// out transliteration variants
+fn literal_similarity(input_forms: &[String], subject_name: &str, entity: bool) -> f32 {
+    let Some(input) = input_forms.first() else {
+        return 0.0;
+    };
+    let mut subject = normalize_name(subject_name);
+    if entity {
+        subject = strip_legal_forms(&subject);
+    }
+    let input_parts: Vec<&str> = input.split_whitespace().collect();
+    compute_name_similarity(input, &input_parts, &subject)
+}
+
+/// Whether any input form sounds like any form of `subject_name`
+fn forms_sound_alike(input_forms: &[String], subject_name: &str, entity: bool) -> bool {
+    let subject_forms = name_forms(subject_name, entity);
+    input_forms
+        .iter()
+        .any(|input| subject_forms.iter().any(|subject| phonetic::sounds_alike(input, subject)))
 }
 
 /// Compute name similarity using parts-based matching as primary strategy
@@ -299,10 +605,14 @@ fn compute_parts_match_score(input_parts: &[&str], subject_parts: &[&str]) -> f3
 struct Candidate {
     subject_id: String,
     primary_name: String,
+    aliases: Vec<(String, AliasType)>,
     country: Option<String>,
-    dob_year: Option<i32>,
+    dob: Option<BirthDate>,
     source: String,
     kind: String,
+    record_version: Option<String>,
+    /// Identifier keys (see `identifier::identifier_key`)
+    identifiers: Vec<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -315,26 +625,56 @@ pub struct MatchResult {
     pub dob_year: Option<i32>,
     pub score: f32,
     pub components: ScoreComponents,
+    pub matched_alias: Option<MatchedAlias>,
+    /// The input identifier the subject also lists
+    pub matched_identifier: Option<ScreenIdentifier>,
+    pub record_version: Option<String>,
 }
 
-fn parse_source(s: &str) -> HitSource {
-    match s.to_uppercase().as_str() {
-        "EU" | "EU_CONSOLIDATED" => HitSource::EuConsolidated,
-        "UN" | "UN_SC" => HitSource::UnSc,
-        "OFAC" => HitSource::Ofac,
-        "UK" => HitSource::Uk,
-        "PEP_EU" => HitSource::PepEu,
-        _ => HitSource::Stub,
+impl MatchResult {
+    /// The API-facing hit for this match, at the risk level the caller's
+    /// thresholds assign to its score.
+    pub fn into_hit(self, risk_level: RiskLevel) -> Hit {
+        let explanation = self.components.explain(
+            self.kind,
+            &self.primary_name,
+            self.country.as_deref(),
+            self.matched_alias.as_ref(),
+            self.matched_identifier.as_ref(),
+        );
+        Hit {
+            subject_id: self.subject_id,
+            matched_name: self.primary_name,
+            source: self.source,
+            kind: self.kind,
+            score: self.score,
+            risk_level,
+            components: self.components,
+            explanation,
+            matched_alias: self.matched_alias,
+            record_version: self.record_version,
+            suppressed: false,
+            suppression_reason: None,
+        }
     }
 }
 
-fn parse_kind(s: &str) -> SubjectKind {
+fn parse_source(s: &str) -> HitSource {
+    HitSource::from_code(s).unwrap_or(HitSource::Stub)
+}
+
+fn parse_alias_type(s: &str) -> AliasType {
     match s.to_lowercase().as_str() {
-        "entity" | "enterprise" => SubjectKind::Entity,
-        _ => SubjectKind::Person,
+        "fka" => AliasType::Fka,
+        "low_quality_aka" => AliasType::LowQualityAka,
+        _ => AliasType::Aka,
     }
 }
 
+fn parse_kind(s: &str) -> SubjectKind {
+    SubjectKind::from_code(s).unwrap_or(SubjectKind::Person)
+}
+
 pub fn normalize_name(value: &str) -> String {
     value
         .nfd()
@@ -346,6 +686,30 @@ pub fn normalize_name(value: &str) -> String {
         .join(" ")
 }
 
+/// Company-type words that say nothing about which company is meant
+const LEGAL_FORMS: &[&str] = &[
+    "ag", "bv", "co", "company", "corp", "corporation", "gmbh", "inc", "incorporated", "jsc", "kg", "limited",
+    "llc", "llp", "ltd", "nv", "oao", "ooo", "pjsc", "plc", "pte", "pty", "sa", "sarl", "sas", "spa", "srl",
+];
+
+/// Drop punctuation and legal-form words from a normalized entity name.
+/// Names consisting only of such words are returned unchanged.
+fn strip_legal_forms(name: &str) -> String {
+    let cleaned = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c.is_whitespace() { c } else { ' ' })
+        .collect::<String>();
+    let words: Vec<&str> = cleaned
+        .split_whitespace()
+        .filter(|w| !LEGAL_FORMS.contains(w))
+        .collect();
+    if words.is_empty() {
+        name.to_string()
+    } else {
+        words.join(" ")
+    }
+}
+
 trait NonSpacingMark {
     fn is_mark_nonspacing(&self) -> bool;
 }
@@ -397,12 +761,16 @@ pub const STUB_SUBJECTS: &[StubSubject] = &[
 pub fn score_against_stub(
     name: &str,
     country: Option<&str>,
-    dob_year: Option<i32>,
+    dob: Option<BirthDate>,
+    kind: Option<SubjectKind>,
     max_results: usize,
+    weights: &ScoreWeights,
 ) -> Vec<StubMatchResult> {
     let norm_input = normalize_name(name);
+    let dob_year = dob.map(|d| d.year);
     let mut results = STUB_SUBJECTS
         .iter()
+        .filter(|subject| kind.is_none_or(|k| k == subject.kind))
         .map(|subject| {
             let norm_subject = normalize_name(subject.name);
             let name_similarity = jaro_winkler(&norm_input, &norm_subject) as f32;
@@ -419,8 +787,12 @@ pub fn score_against_stub(
                 name_similarity,
                 dob_similarity,
                 country_match,
+                phonetic_match: phonetic::sounds_alike(&norm_input, &norm_subject),
+                identifier_match: 0.0,
             };
-            let score = 0.75 * name_similarity + 0.15 * country_match + 0.10 * dob_similarity;
+            let score = weights.name * name_similarity
+                + weights.country * country_match
+                + weights.dob * dob_similarity;
             StubMatchResult {
                 subject,
                 score,
@@ -452,9 +824,138 @@ mod tests {
         assert_eq!(norm, "alvaro nunez");
     }
 
+    fn candidate(primary_name: &str, aliases: &[(&str, AliasType)]) -> Candidate {
+        Candidate {
+            subject_id: "ofac_1".to_string(),
+            primary_name: primary_name.to_string(),
+            aliases: aliases.iter().map(|(n, t)| (n.to_string(), *t)).collect(),
+            country: None,
+            dob: None,
+            source: "OFAC".to_string(),
+            kind: "person".to_string(),
+            record_version: None,
+            identifiers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn adjustments_follow_the_tenant_thresholds() {
+        let components = |name_similarity: f32, country_match: f32, identifier_match: f32| ScoreComponents {
+            name_similarity,
+            dob_similarity: 0.0,
+            country_match,
+            phonetic_match: false,
+            identifier_match,
+        };
+        let assert_close = |score: f32, expected: f32| assert!((score - expected).abs() < 1e-6, "score was {score}");
+        let strict = ScoreThresholds::default();
+        let lenient = ScoreThresholds { hit: 0.80, review: 0.60 };
+
+        // An exact name and country match is a hit under either configuration
+        assert_close(adjust_score(0.5, &components(1.0, 1.0, 0.0), true, false, &strict), 0.95);
+        assert_close(adjust_score(0.5, &components(1.0, 1.0, 0.0), true, false, &lenient), 0.80);
+
+        // A conflicting country keeps a strong name match just below a hit
+        assert_close(adjust_score(0.97, &components(0.97, 0.0, 0.0), true, false, &strict), 0.94);
+        assert_close(adjust_score(0.85, &components(0.97, 0.0, 0.0), true, false, &lenient), 0.79);
+        assert_close(adjust_score(0.85, &components(0.97, 0.0, 0.0), false, false, &lenient), 0.85);
+
+        // So does a date of birth that differs
+        assert_close(adjust_score(0.97, &components(0.97, 1.0, 0.0), false, true, &strict), 0.94);
+        let no_review_band = ScoreThresholds { hit: 0.90, review: 0.90 };
+        assert!(adjust_score(0.97, &components(0.97, 0.0, 0.0), true, false, &no_review_band) < 0.90);
+
+        // A shared identifier is a hit whatever the name
+        assert_close(adjust_score(0.2, &components(0.3, 0.0, 1.0), false, false, &lenient), 0.80);
+    }
+
+    #[test]
+    fn alias_match_beats_primary_name() {
+        let c = candidate(
+            "Muammar Qadhafi",
+            &[("Moammar Gaddafi", AliasType::Aka), ("Brother Leader", AliasType::LowQualityAka)],
+        );
+        let (similarity, matched) = best_name_match(&name_forms("Moammar Gaddafi", false), &c, false);
+        assert!(similarity >= 0.99);
+        let matched = matched.expect("alias should win");
+        assert_eq!(matched.name, "Moammar Gaddafi");
+        assert!(matches!(matched.alias_type, AliasType::Aka));
+
+        let components = ScoreComponents {
+            name_similarity: similarity,
+            dob_similarity: 0.0,
+            country_match: 0.0,
+            phonetic_match: false,
+            identifier_match: 0.0,
+        };
+        let explanations = components.explain(SubjectKind::Person, &c.primary_name, None, Some(&matched), None);
+        assert!(explanations.contains(&"Matched on a.k.a. alias of 'Muammar Qadhafi'".to_string()));
+    }
+
+    #[test]
+    fn primary_name_wins_ties() {
+        let c = candidate("John Doe", &[("John Doe", AliasType::Fka)]);
+        let (_, matched) = best_name_match(&name_forms("John Doe", false), &c, false);
+        assert!(matched.is_none());
+    }
+
+    #[test]
+    fn cyrillic_input_scores_against_latin_name() {
+        let c = candidate("Vladimir Vladimirovich PUTIN", &[]);
+        let (similarity, _) = best_name_match(&name_forms("Владимир Путин", false), &c, false);
+        assert!(similarity >= 0.9, "similarity was {similarity}");
+    }
+
+    #[test]
+    fn entity_names_ignore_legal_forms() {
+        let c = candidate("ACME TRADING LIMITED", &[]);
+        let (similarity, _) = best_name_match(&name_forms("Acme Trading Ltd.", true), &c, true);
+        assert!(similarity >= 0.99, "similarity was {similarity}");
+        assert_eq!(strip_legal_forms("limited company"), "limited company");
+    }
+
+    #[test]
+    fn identifiers_match_or_conflict_by_type() {
+        let passport = ScreenIdentifier {
+            id_type: aegistry_core::IdentifierType::Passport,
+            number: "a1234567".to_string(),
+        };
+        let input = vec![(identifier_key(passport.id_type, &passport.number), &passport)];
+
+        let (component, matched) = compare_identifiers(&input, &["passport:A1234567".to_string()]);
+        assert_eq!(component, 1.0);
+        assert_eq!(matched, Some(passport.clone()));
+
+        let (component, matched) = compare_identifiers(
+            &input,
+            &["passport:B7654321".to_string(), "national_id:A1234567".to_string()],
+        );
+        assert_eq!(component, -1.0);
+        assert!(matched.is_none());
+
+        let (component, _) = compare_identifiers(&input, &["national_id:A1234567".to_string()]);
+        assert_eq!(component, 0.0);
+    }
+
+    #[test]
+    fn every_ingested_source_is_recognised() {
+        for source in HitSource::INGESTED {
+            assert_eq!(parse_source(source.code()), source);
+        }
+        assert_eq!(parse_source("PEP_DE_BUNDESTAG"), HitSource::PepDeBundestag);
+        assert_eq!(parse_source("eu_consolidated"), HitSource::EuConsolidated);
+        assert_eq!(parse_source("SOMETHING_NEW"), HitSource::Stub);
+    }
+
+    #[test]
+    fn stub_scoring_respects_kind() {
+        let hits = score_against_stub("John Doe", None, None, Some(SubjectKind::Entity), 3, &ScoreWeights::default());
+        assert!(hits.is_empty());
+    }
+
     #[test]
     fn scoring_prefers_country_and_dob() {
-        let hits = score_against_stub("John Doe", Some("US"), Some(1970), 3);
+        let hits = score_against_stub("John Doe", Some("US"), Some(BirthDate::year(1970)), None, 3, &ScoreWeights::default());
         assert!(!hits.is_empty());
         let top = &hits[0];
         assert_eq!(top.subject.subject_id, "ofac_0002");
@@ -0,0 +1,722 @@
+//! Double Metaphone phonetic encoding (Lawrence Philips), following the
+//! Apache commons-codec implementation.
+//!
+//! Used to retrieve candidates whose spelling differs by more than the fuzzy
+//! edit distance but which sound alike, e.g. "Gaddafi", "Qadhafi" and
+//! "Kadafi" all encode to `KTF`.
+
+const MAX_CODE_LEN: usize = 4;
+
+/// Primary and alternate Double Metaphone codes for a single word.
+/// Non-ASCII letters should be folded (see `normalize_name`) beforehand;
+/// anything outside A-Z is ignored.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    let value: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if value.is_empty() {
+        return (String::new(), String::new());
+    }
+    Encoder::new(value).encode()
+}
+
+/// Distinct phonetic codes for every word of `name` with at least three
+/// letters, primary and alternate alike. Used for both indexing and querying.
+pub fn phonetic_keys(name: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    for word in name.split_whitespace().filter(|w| w.chars().count() >= 3) {
+        let (primary, alternate) = double_metaphone(word);
+        for code in [primary, alternate] {
+            if !code.is_empty() && !keys.contains(&code) {
+                keys.push(code);
+            }
+        }
+    }
+    keys
+}
+
+/// True when every word of `input` shares a phonetic code with some word of `name`.
+pub fn sounds_alike(input: &str, name: &str) -> bool {
+    let name_keys = phonetic_keys(name);
+    let mut words = input.split_whitespace().filter(|w| w.chars().count() >= 3).peekable();
+    if words.peek().is_none() || name_keys.is_empty() {
+        return false;
+    }
+    words.all(|word| {
+        let (primary, alternate) = double_metaphone(word);
+        name_keys.iter().any(|k| *k == primary || (!alternate.is_empty() && *k == alternate))
+    })
+}
+
+struct Encoder {
+    value: Vec<char>,
+    primary: String,
+    alternate: String,
+    slavo_germanic: bool,
+}
+
+impl Encoder {
+    fn new(value: Vec<char>) -> Self {
+        let s: String = value.iter().collect();
+        let slavo_germanic =
+            s.contains('W') || s.contains('K') || s.contains("CZ") || s.contains("WITZ");
+        Self {
+            value,
+            primary: String::new(),
+            alternate: String::new(),
+            slavo_germanic,
+        }
+    }
+
+    fn len(&self) -> isize {
+        self.value.len() as isize
+    }
+
+    fn char_at(&self, index: isize) -> char {
+        if index < 0 || index >= self.len() {
+            '\0'
+        } else {
+            self.value[index as usize]
+        }
+    }
+
+    fn contains(&self, start: isize, length: isize, criteria: &[&str]) -> bool {
+        if start < 0 || start + length > self.len() {
+            return false;
+        }
+        let target: String = self.value[start as usize..(start + length) as usize].iter().collect();
+        criteria.iter().any(|c| *c == target)
+    }
+
+    fn is_vowel(c: char) -> bool {
+        matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= MAX_CODE_LEN && self.alternate.len() >= MAX_CODE_LEN
+    }
+
+    fn append_primary(&mut self, s: &str) {
+        let room = MAX_CODE_LEN.saturating_sub(self.primary.len());
+        self.primary.push_str(&s[..s.len().min(room)]);
+    }
+
+    fn append_alternate(&mut self, s: &str) {
+        let room = MAX_CODE_LEN.saturating_sub(self.alternate.len());
+        self.alternate.push_str(&s[..s.len().min(room)]);
+    }
+
+    fn append(&mut self, s: &str) {
+        self.append_primary(s);
+        self.append_alternate(s);
+    }
+
+    fn append_both(&mut self, primary: &str, alternate: &str) {
+        self.append_primary(primary);
+        self.append_alternate(alternate);
+    }
+
+    fn encode(mut self) -> (String, String) {
+        let mut index: isize = 0;
+        if self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            index = 1;
+        }
+        if self.char_at(0) == 'X' {
+            self.append("S");
+            index = 1;
+        }
+
+        while !self.is_complete() && index < self.len() {
+            index = match self.char_at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.append("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.append("P");
+                    if self.char_at(index + 1) == 'B' { index + 2 } else { index + 1 }
+                }
+                'C' => self.handle_c(index),
+                'D' => self.handle_d(index),
+                'F' => {
+                    self.append("F");
+                    if self.char_at(index + 1) == 'F' { index + 2 } else { index + 1 }
+                }
+                'G' => self.handle_g(index),
+                'H' => self.handle_h(index),
+                'J' => self.handle_j(index),
+                'K' => {
+                    self.append("K");
+                    if self.char_at(index + 1) == 'K' { index + 2 } else { index + 1 }
+                }
+                'L' => {
+                    if self.char_at(index + 1) == 'L' {
+                        if self.condition_l0(index) {
+                            self.append_primary("L");
+                        } else {
+                            self.append("L");
+                        }
+                        index + 2
+                    } else {
+                        self.append("L");
+                        index + 1
+                    }
+                }
+                'M' => {
+                    self.append("M");
+                    if self.condition_m0(index) { index + 2 } else { index + 1 }
+                }
+                'N' => {
+                    self.append("N");
+                    if self.char_at(index + 1) == 'N' { index + 2 } else { index + 1 }
+                }
+                'P' => {
+                    if self.char_at(index + 1) == 'H' {
+                        self.append("F");
+                        index + 2
+                    } else {
+                        self.append("P");
+                        if self.contains(index + 1, 1, &["P", "B"]) { index + 2 } else { index + 1 }
+                    }
+                }
+                'Q' => {
+                    self.append("K");
+                    if self.char_at(index + 1) == 'Q' { index + 2 } else { index + 1 }
+                }
+                'R' => self.handle_r(index),
+                'S' => self.handle_s(index),
+                'T' => self.handle_t(index),
+                'V' => {
+                    self.append("F");
+                    if self.char_at(index + 1) == 'V' { index + 2 } else { index + 1 }
+                }
+                'W' => self.handle_w(index),
+                'X' => self.handle_x(index),
+                'Z' => self.handle_z(index),
+                _ => index + 1,
+            };
+        }
+
+        (self.primary, self.alternate)
+    }
+
+    fn handle_c(&mut self, index: isize) -> isize {
+        if self.condition_c0(index) {
+            self.append("K");
+            index + 2
+        } else if index == 0 && self.contains(index, 6, &["CAESAR"]) {
+            self.append("S");
+            index + 2
+        } else if self.contains(index, 2, &["CH"]) {
+            self.handle_ch(index)
+        } else if self.contains(index, 2, &["CZ"]) && !self.contains(index - 2, 4, &["WICZ"]) {
+            self.append_both("S", "X");
+            index + 2
+        } else if self.contains(index + 1, 3, &["CIA"]) {
+            self.append("X");
+            index + 3
+        } else if self.contains(index, 2, &["CC"]) && !(index == 1 && self.char_at(0) == 'M') {
+            self.handle_cc(index)
+        } else if self.contains(index, 2, &["CK", "CG", "CQ"]) {
+            self.append("K");
+            index + 2
+        } else if self.contains(index, 2, &["CI", "CE", "CY"]) {
+            if self.contains(index, 3, &["CIO", "CIE", "CIA"]) {
+                self.append_both("S", "X");
+            } else {
+                self.append("S");
+            }
+            index + 2
+        } else {
+            self.append("K");
+            if self.contains(index + 1, 2, &[" C", " Q", " G"]) {
+                index + 3
+            } else if self.contains(index + 1, 1, &["C", "K", "Q"])
+                && !self.contains(index + 1, 2, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_cc(&mut self, index: isize) -> isize {
+        if self.contains(index + 2, 1, &["I", "E", "H"]) && !self.contains(index + 2, 2, &["HU"]) {
+            if (index == 1 && self.char_at(index - 1) == 'A')
+                || self.contains(index - 1, 5, &["UCCEE", "UCCES"])
+            {
+                self.append("KS");
+            } else {
+                self.append("X");
+            }
+            index + 3
+        } else {
+            self.append("K");
+            index + 2
+        }
+    }
+
+    fn handle_ch(&mut self, index: isize) -> isize {
+        if index > 0 && self.contains(index, 4, &["CHAE"]) {
+            self.append_both("K", "X");
+        } else if self.condition_ch0(index) || self.condition_ch1(index) {
+            self.append("K");
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.append("K");
+            } else {
+                self.append_both("X", "K");
+            }
+        } else {
+            self.append("X");
+        }
+        index + 2
+    }
+
+    fn handle_d(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["DG"]) {
+            if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+                self.append("J");
+                index + 3
+            } else {
+                self.append("TK");
+                index + 2
+            }
+        } else if self.contains(index, 2, &["DT", "DD"]) {
+            self.append("T");
+            index + 2
+        } else {
+            self.append("T");
+            index + 1
+        }
+    }
+
+    fn handle_g(&mut self, index: isize) -> isize {
+        let next = self.char_at(index + 1);
+        if next == 'H' {
+            self.handle_gh(index)
+        } else if next == 'N' {
+            if index == 1 && Self::is_vowel(self.char_at(0)) && !self.slavo_germanic {
+                self.append_both("KN", "N");
+            } else if !self.contains(index + 2, 2, &["EY"]) && !self.slavo_germanic {
+                self.append_both("N", "KN");
+            } else {
+                self.append("KN");
+            }
+            index + 2
+        } else if self.contains(index + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.append_both("KL", "L");
+            index + 2
+        } else if (index == 0
+            && (next == 'Y'
+                || self.contains(
+                    index + 1,
+                    2,
+                    &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"],
+                )))
+            || ((self.contains(index + 1, 2, &["ER"]) || next == 'Y')
+                && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+                && !self.contains(index - 1, 1, &["E", "I"])
+                && !self.contains(index - 1, 3, &["RGY", "OGY"]))
+        {
+            self.append_both("K", "J");
+            index + 2
+        } else if self.contains(index + 1, 1, &["E", "I", "Y"])
+            || self.contains(index - 1, 4, &["AGGI", "OGGI"])
+        {
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(index + 1, 2, &["ET"])
+            {
+                self.append("K");
+            } else if self.contains(index + 1, 3, &["IER"]) {
+                self.append("J");
+            } else {
+                self.append_both("J", "K");
+            }
+            index + 2
+        } else if next == 'G' {
+            self.append("K");
+            index + 2
+        } else {
+            self.append("K");
+            index + 1
+        }
+    }
+
+    fn handle_gh(&mut self, index: isize) -> isize {
+        if index > 0 && !Self::is_vowel(self.char_at(index - 1)) {
+            self.append("K");
+        } else if index == 0 {
+            if self.char_at(index + 2) == 'I' {
+                self.append("J");
+            } else {
+                self.append("K");
+            }
+        } else if (index > 1 && self.contains(index - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(index - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(index - 4, 1, &["B", "H"]))
+        {
+            // e.g. "Hugh", "bough", "broughton": silent
+        } else if index > 2
+            && self.char_at(index - 1) == 'U'
+            && self.contains(index - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            self.append("F");
+        } else if index > 0 && self.char_at(index - 1) != 'I' {
+            self.append("K");
+        }
+        index + 2
+    }
+
+    fn handle_h(&mut self, index: isize) -> isize {
+        if (index == 0 || Self::is_vowel(self.char_at(index - 1)))
+            && Self::is_vowel(self.char_at(index + 1))
+        {
+            self.append("H");
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_j(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            if (index == 0 && self.char_at(index + 4) == ' ')
+                || self.len() == 4
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.append("H");
+            } else {
+                self.append_both("J", "H");
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            self.append_both("J", "A");
+        } else if Self::is_vowel(self.char_at(index - 1))
+            && !self.slavo_germanic
+            && matches!(self.char_at(index + 1), 'A' | 'O')
+        {
+            self.append_both("J", "H");
+        } else if index == self.len() - 1 {
+            self.append_primary("J");
+        } else if !self.contains(index + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(index - 1, 1, &["S", "K", "L"])
+        {
+            self.append("J");
+        }
+
+        if self.char_at(index + 1) == 'J' { index + 2 } else { index + 1 }
+    }
+
+    fn handle_r(&mut self, index: isize) -> isize {
+        if index == self.len() - 1
+            && !self.slavo_germanic
+            && self.contains(index - 2, 2, &["IE"])
+            && !self.contains(index - 4, 2, &["ME", "MA"])
+        {
+            self.append_alternate("R");
+        } else {
+            self.append("R");
+        }
+        if self.char_at(index + 1) == 'R' { index + 2 } else { index + 1 }
+    }
+
+    fn handle_s(&mut self, index: isize) -> isize {
+        if self.contains(index - 1, 3, &["ISL", "YSL"]) {
+            index + 1
+        } else if index == 0 && self.contains(index, 5, &["SUGAR"]) {
+            self.append_both("X", "S");
+            index + 1
+        } else if self.contains(index, 2, &["SH"]) {
+            if self.contains(index + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.append("S");
+            } else {
+                self.append("X");
+            }
+            index + 2
+        } else if self.contains(index, 3, &["SIO", "SIA"]) || self.contains(index, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.append("S");
+            } else {
+                self.append_both("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(index + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(index + 1, 1, &["Z"])
+        {
+            self.append_both("S", "X");
+            if self.contains(index + 1, 1, &["Z"]) { index + 2 } else { index + 1 }
+        } else if self.contains(index, 2, &["SC"]) {
+            self.handle_sc(index)
+        } else {
+            if index == self.len() - 1 && self.contains(index - 2, 2, &["AI", "OI"]) {
+                self.append_alternate("S");
+            } else {
+                self.append("S");
+            }
+            if self.contains(index + 1, 1, &["S", "Z"]) { index + 2 } else { index + 1 }
+        }
+    }
+
+    fn handle_sc(&mut self, index: isize) -> isize {
+        if self.char_at(index + 2) == 'H' {
+            if self.contains(index + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                if self.contains(index + 3, 2, &["ER", "EN"]) {
+                    self.append_both("X", "SK");
+                } else {
+                    self.append("SK");
+                }
+            } else if index == 0 && !Self::is_vowel(self.char_at(3)) && self.char_at(3) != 'W' {
+                self.append_both("X", "S");
+            } else {
+                self.append("X");
+            }
+        } else if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+            self.append("S");
+        } else {
+            self.append("SK");
+        }
+        index + 3
+    }
+
+    fn handle_t(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["TION"]) || self.contains(index, 3, &["TIA", "TCH"]) {
+            self.append("X");
+            index + 3
+        } else if self.contains(index, 2, &["TH"]) || self.contains(index, 3, &["TTH"]) {
+            if self.contains(index + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                self.append("T");
+            } else {
+                self.append_both("0", "T");
+            }
+            index + 2
+        } else {
+            self.append("T");
+            if self.contains(index + 1, 1, &["T", "D"]) { index + 2 } else { index + 1 }
+        }
+    }
+
+    fn handle_w(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["WR"]) {
+            self.append("R");
+            return index + 2;
+        }
+        if index == 0 && (Self::is_vowel(self.char_at(index + 1)) || self.contains(index, 2, &["WH"])) {
+            if Self::is_vowel(self.char_at(index + 1)) {
+                self.append_both("A", "F");
+            } else {
+                self.append("A");
+            }
+            index + 1
+        } else if (index == self.len() - 1 && Self::is_vowel(self.char_at(index - 1)))
+            || self.contains(index - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            self.append_alternate("F");
+            index + 1
+        } else if self.contains(index, 4, &["WICZ", "WITZ"]) {
+            self.append_both("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_x(&mut self, index: isize) -> isize {
+        if index == 0 {
+            self.append("S");
+            return index + 1;
+        }
+        let silent_final = index == self.len() - 1
+            && (self.contains(index - 3, 3, &["IAU", "EAU"]) || self.contains(index - 2, 2, &["AU", "OU"]));
+        if !silent_final {
+            self.append("KS");
+        }
+        if self.contains(index + 1, 1, &["C", "X"]) { index + 2 } else { index + 1 }
+    }
+
+    fn handle_z(&mut self, index: isize) -> isize {
+        if self.char_at(index + 1) == 'H' {
+            self.append("J");
+            return index + 2;
+        }
+        if self.contains(index + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.char_at(index - 1) != 'T')
+        {
+            self.append_both("S", "TS");
+        } else {
+            self.append("S");
+        }
+        if self.char_at(index + 1) == 'Z' { index + 2 } else { index + 1 }
+    }
+
+    fn condition_c0(&self, index: isize) -> bool {
+        if self.contains(index, 4, &["CHIA"]) {
+            true
+        } else if index <= 1
+            || Self::is_vowel(self.char_at(index - 2))
+            || !self.contains(index - 1, 3, &["ACH"])
+        {
+            false
+        } else {
+            let c = self.char_at(index + 2);
+            (c != 'I' && c != 'E') || self.contains(index - 2, 6, &["BACHER", "MACHER"])
+        }
+    }
+
+    fn condition_ch0(&self, index: isize) -> bool {
+        index == 0
+            && (self.contains(index + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(index + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, index: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(index - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(index + 2, 1, &["T", "S"])
+            || ((self.contains(index - 1, 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.contains(index + 2, 1, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "])
+                    || index + 1 == self.len() - 1))
+    }
+
+    fn condition_l0(&self, index: isize) -> bool {
+        if index == self.len() - 3 && self.contains(index - 1, 4, &["ILLO", "ILLA", "ALLE"]) {
+            return true;
+        }
+        (self.contains(self.len() - 2, 2, &["AS", "OS"]) || self.contains(self.len() - 1, 1, &["A", "O"]))
+            && self.contains(index - 1, 4, &["ALLE"])
+    }
+
+    fn condition_m0(&self, index: isize) -> bool {
+        if self.char_at(index + 1) == 'M' {
+            return true;
+        }
+        self.contains(index - 1, 3, &["UMB"])
+            && (index + 1 == self.len() - 1 || self.contains(index + 2, 2, &["ER"]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(word, primary, alternate)` rows checked against commons-codec
+    fn assert_codes(cases: &[(&str, &str, &str)]) {
+        for (word, primary, alternate) in cases {
+            assert_eq!(
+                double_metaphone(word),
+                (primary.to_string(), alternate.to_string()),
+                "codes for {word}"
+            );
+        }
+    }
+
+    #[test]
+    fn known_codes() {
+        assert_eq!(double_metaphone("Smith"), ("SM0".to_string(), "XMT".to_string()));
+        assert_eq!(double_metaphone("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+    }
+
+    #[test]
+    fn silent_initial_letters() {
+        assert_codes(&[
+            ("Gnome", "NM", "NM"),
+            ("Knight", "NT", "NT"),
+            ("Pneumonia", "NMN", "NMN"),
+            ("Wright", "RT", "RT"),
+            ("Psalm", "SLM", "SLM"),
+            ("Xavier", "SF", "SFR"),
+        ]);
+    }
+
+    #[test]
+    fn ch_and_sch() {
+        assert_codes(&[
+            ("Charles", "XRLS", "XRLS"),
+            ("Christopher", "KRST", "KRST"),
+            ("Chemistry", "KMST", "KMST"),
+            ("Michael", "MKL", "MXL"),
+            ("Church", "XRX", "XRK"),
+            ("School", "SKL", "SKL"),
+            ("Schneider", "XNTR", "SNTR"),
+            ("Schenker", "XNKR", "SKNK"),
+        ]);
+    }
+
+    #[test]
+    fn gh_variants() {
+        assert_codes(&[
+            ("Ghost", "KST", "KST"),
+            ("Ghislane", "JLN", "JLN"),
+            ("Hugh", "H", "H"),
+            ("Night", "NT", "NT"),
+            ("Laugh", "LF", "LF"),
+            ("Tough", "TF", "TF"),
+        ]);
+    }
+
+    #[test]
+    fn c_as_s_or_k() {
+        assert_codes(&[
+            ("Carl", "KRL", "KRL"),
+            ("Cole", "KL", "KL"),
+            ("Cyrus", "SRS", "SRS"),
+            ("Cicero", "SSR", "SSR"),
+            ("Caesar", "SSR", "SSR"),
+            ("Accident", "AKST", "AKST"),
+        ]);
+    }
+
+    #[test]
+    fn silent_letters() {
+        assert_codes(&[
+            // Only UMB at the end or before ER drops its B
+            ("Thumb", "0M", "TM"),
+            ("Plumber", "PLMR", "PLMR"),
+            ("Lamb", "LMP", "LMP"),
+            ("Knife", "NF", "NF"),
+            ("Island", "ALNT", "ALNT"),
+        ]);
+    }
+
+    #[test]
+    fn alternate_codes() {
+        assert_codes(&[
+            ("Jankelowicz", "JNKL", "ANKL"),
+            ("Wasserman", "ASRM", "FSRM"),
+            ("Tagliaro", "TKLR", "TLR"),
+            ("Rogier", "RJ", "RJR"),
+            ("Womo", "AM", "FM"),
+            ("Arnow", "ARN", "ARNF"),
+            ("Filipowicz", "FLPT", "FLPF"),
+            ("Zhao", "J", "J"),
+        ]);
+    }
+
+    #[test]
+    fn gaddafi_spellings_share_a_code() {
+        let gaddafi = double_metaphone("Gaddafi").0;
+        assert_eq!(gaddafi, "KTF");
+        assert_eq!(double_metaphone("Qadhafi").0, gaddafi);
+        assert_eq!(double_metaphone("Kadafi").0, gaddafi);
+    }
+
+    #[test]
+    fn sounds_alike_requires_every_word() {
+        assert!(sounds_alike("muammar gaddafi", "moammar qadhafi"));
+        assert!(!sounds_alike("muammar smith", "moammar qadhafi"));
+    }
+}
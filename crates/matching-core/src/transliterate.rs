@@ -0,0 +1,398 @@
+//! Latin-script renderings of names written in other scripts.
+//!
+//! Sanctions lists publish most names romanised, but customers are often
+//! onboarded in their native script. Every name is expanded into a small set
+//! of Latin variants at index time and at query time so both sides meet on a
+//! common form:
+//!
+//! - Cyrillic: ICAO 9303 plus the common passport-style spelling (Й→Y, Ю→YU)
+//! - Greek: ICAO 9303 / ELOT 743
+//! - Arabic: dictionary of frequent name words, letter-level fallback
+//! - CJK and Hangul: per-syllable pinyin / romanisation
+//!
+//! Latin output is also folded onto canonical spellings of common Arabic
+//! names, so "Mohammed", "Mohamed" and "Muhammad" produce the same variant.
+
+use crate::normalize_name;
+use unicode_normalization::UnicodeNormalization;
+
+/// Return the Latin variants of `name`, normalized like [`normalize_name`].
+/// The plain normalized name itself is not included.
+pub fn transliterate_variants(name: &str) -> Vec<String> {
+    let normalized = normalize_name(name);
+    let mut variants = Vec::new();
+
+    if has_non_latin(name) {
+        // Romanise before accents are stripped: й and ё are base letters plus a combining mark
+        let lowered = name.nfc().collect::<String>().to_lowercase();
+        for scheme in [Scheme::Icao, Scheme::Common] {
+            let romanised = normalize_name(&romanise(&lowered, scheme));
+            push_unique(&mut variants, &normalized, romanised.clone());
+            push_unique(&mut variants, &normalized, canonicalize_arabic_names(&romanised));
+        }
+    } else {
+        push_unique(&mut variants, &normalized, canonicalize_arabic_names(&normalized));
+    }
+
+    variants
+}
+
+fn push_unique(variants: &mut Vec<String>, original: &str, candidate: String) {
+    let candidate = candidate.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !candidate.is_empty() && candidate != original && !variants.contains(&candidate) {
+        variants.push(candidate);
+    }
+}
+
+fn has_non_latin(s: &str) -> bool {
+    s.chars().any(|c| is_cyrillic(c) || is_greek(c) || is_arabic(c) || is_cjk(c))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Scheme {
+    Icao,
+    Common,
+}
+
+fn romanise(s: &str, scheme: Scheme) -> String {
+    let words: Vec<String> = s.split_whitespace().map(|w| romanise_word(w, scheme)).collect();
+    words.join(" ")
+}
+
+fn romanise_word(word: &str, scheme: Scheme) -> String {
+    if word.chars().any(is_arabic) {
+        return romanise_arabic(word);
+    }
+    if word.chars().any(is_cjk) {
+        return romanise_cjk(word);
+    }
+
+    // Greek tonos and dialytika carry no information for romanisation
+    let chars: Vec<char> = word
+        .chars()
+        .map(|c| if is_greek(c) { c.nfd().next().unwrap_or(c) } else { c })
+        .collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if is_greek(c) {
+            let (latin, consumed) = greek(c, next, i == 0);
+            out.push_str(latin);
+            i += consumed;
+            continue;
+        }
+        if is_cyrillic(c) {
+            out.push_str(cyrillic(c, scheme));
+        } else {
+            out.push(c);
+        }
+        i += 1;
+    }
+    out
+}
+
+fn is_cyrillic(c: char) -> bool {
+    matches!(c, '\u{0400}'..='\u{04FF}')
+}
+
+fn is_greek(c: char) -> bool {
+    matches!(c, '\u{0370}'..='\u{03FF}')
+}
+
+fn is_arabic(c: char) -> bool {
+    matches!(c, '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}')
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+    )
+}
+
+/// Cyrillic to Latin. Input is already lowercase.
+fn cyrillic(c: char, scheme: Scheme) -> &'static str {
+    let common = scheme == Scheme::Common;
+    match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'ґ' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ё' => if common { "yo" } else { "e" },
+        'є' => if common { "ye" } else { "ie" },
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'і' => "i",
+        'ї' => "i",
+        'й' => if common { "y" } else { "i" },
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ў' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' => if common { "" } else { "ie" },
+        'ы' => "y",
+        'ь' => "",
+        'э' => "e",
+        'ю' => if common { "yu" } else { "iu" },
+        'я' => if common { "ya" } else { "ia" },
+        _ => "",
+    }
+}
+
+/// Greek to Latin (ICAO 9303 / ELOT 743). Returns the Latin text and how many
+/// input characters it consumed, since a few digraphs map as a unit.
+fn greek(c: char, next: Option<char>, word_start: bool) -> (&'static str, usize) {
+    match (c, next) {
+        ('ο', Some('υ')) => return ("ou", 2),
+        ('α', Some('υ')) => return ("av", 2),
+        ('ε', Some('υ')) => return ("ev", 2),
+        ('μ', Some('π')) if word_start => return ("b", 2),
+        ('γ', Some('γ')) => return ("ng", 2),
+        _ => {}
+    }
+    let latin = match c {
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        _ => "",
+    };
+    (latin, 1)
+}
+
+/// Frequent Arabic name words. Unvocalised Arabic drops short vowels, so a
+/// letter-by-letter reading of these ("mhmd") would never match the list.
+const ARABIC_NAME_WORDS: &[(&str, &str)] = &[
+    ("محمد", "muhammad"),
+    ("احمد", "ahmad"),
+    ("أحمد", "ahmad"),
+    ("محمود", "mahmoud"),
+    ("مصطفى", "mustafa"),
+    ("علي", "ali"),
+    ("عمر", "omar"),
+    ("عثمان", "othman"),
+    ("حسن", "hassan"),
+    ("حسين", "hussein"),
+    ("خالد", "khalid"),
+    ("ابراهيم", "ibrahim"),
+    ("إبراهيم", "ibrahim"),
+    ("يوسف", "yusuf"),
+    ("سعيد", "said"),
+    ("صالح", "salih"),
+    ("اسامة", "osama"),
+    ("أسامة", "osama"),
+    ("عبد", "abd"),
+    ("عبدالله", "abdullah"),
+    ("الله", "allah"),
+    ("عبدالرحمن", "abdulrahman"),
+    ("عبدالعزيز", "abdulaziz"),
+    ("بن", "bin"),
+    ("ابن", "ibn"),
+    ("ابو", "abu"),
+    ("أبو", "abu"),
+    ("القذافي", "qadhafi"),
+    ("قذافي", "qadhafi"),
+    ("الأسد", "assad"),
+    ("الاسد", "assad"),
+    ("بشار", "bashar"),
+    ("معمر", "muammar"),
+    ("صدام", "saddam"),
+    ("نصر", "nasr"),
+    ("جمال", "jamal"),
+    ("فاطمة", "fatima"),
+    ("عائشة", "aisha"),
+    ("زينب", "zainab"),
+    ("مريم", "maryam"),
+];
+
+fn romanise_arabic(word: &str) -> String {
+    if let Some((_, latin)) = ARABIC_NAME_WORDS.iter().find(|(arabic, _)| *arabic == word) {
+        return latin.to_string();
+    }
+    // Definite article: "al-" followed by a known word reads better than a letter dump
+    if let Some(rest) = word.strip_prefix("ال") {
+        if let Some((_, latin)) = ARABIC_NAME_WORDS.iter().find(|(arabic, _)| *arabic == rest) {
+            return latin.to_string();
+        }
+        return format!("al{}", romanise_arabic_letters(rest));
+    }
+    romanise_arabic_letters(word)
+}
+
+fn romanise_arabic_letters(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'ا' | 'أ' | 'إ' | 'آ' => "a",
+            'ب' => "b",
+            'ت' | 'ط' => "t",
+            'ث' => "th",
+            'ج' => "j",
+            'ح' | 'ه' => "h",
+            'خ' => "kh",
+            'د' | 'ض' => "d",
+            'ذ' | 'ظ' => "dh",
+            'ر' => "r",
+            'ز' => "z",
+            'س' | 'ص' => "s",
+            'ش' => "sh",
+            'ع' | 'ء' | 'ئ' | 'ؤ' => "",
+            'غ' => "gh",
+            'ف' => "f",
+            'ق' => "q",
+            'ك' => "k",
+            'ل' => "l",
+            'م' => "m",
+            'ن' => "n",
+            'و' => "w",
+            'ي' | 'ى' => "y",
+            'ة' => "a",
+            // Harakat, when present
+            '\u{064E}' => "a",
+            '\u{064F}' => "u",
+            '\u{0650}' => "i",
+            _ => "",
+        })
+        .collect()
+}
+
+/// Chinese characters read as a surname followed by a run-together given
+/// name ("xi jinping"), which is how the lists romanise them. Kana and Hangul
+/// are romanised syllable by syllable.
+fn romanise_cjk(word: &str) -> String {
+    let syllables: Vec<String> = word
+        .chars()
+        .map(|c| any_ascii::any_ascii_char(c).to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let is_han = word.chars().all(|c| matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}'));
+    match syllables.split_first() {
+        Some((surname, given)) if is_han && !given.is_empty() => {
+            format!("{} {}", surname, given.concat())
+        }
+        _ => syllables.join(" "),
+    }
+}
+
+/// Spelling groups for common Arabic names; the first entry is canonical.
+const ARABIC_NAME_VARIANTS: &[&[&str]] = &[
+    &["muhammad", "mohammed", "mohammad", "mohamed", "muhammed", "mohamad", "muhamad", "mohamud", "mohd"],
+    &["ahmad", "ahmed", "ahmet"],
+    &["hussein", "husain", "hussain", "husayn", "hosein", "hossein", "husein"],
+    &["hassan", "hasan"],
+    &["abdullah", "abdallah", "abdulla"],
+    &["osama", "usama", "usamah"],
+    &["omar", "umar"],
+    &["othman", "uthman", "osman"],
+    &["yusuf", "yousef", "youssef", "yousuf", "yusif", "yussuf"],
+    &["qadhafi", "gaddafi", "kadhafi", "gadhafi", "qaddafi", "kadafi", "qadhdhafi", "gadafi"],
+    &["khalid", "khaled"],
+    &["mustafa", "mustapha", "moustafa", "mostafa"],
+    &["mahmoud", "mahmud"],
+    &["ibrahim", "ebrahim"],
+    &["abdul", "abdel", "abdoul"],
+    &["said", "saeed", "sayed", "sayyid", "saied"],
+    &["salih", "saleh"],
+    &["muammar", "moammar", "mouammar"],
+    &["assad", "asad"],
+    &["aisha", "aishah", "ayesha"],
+    &["zainab", "zaynab", "zeinab"],
+];
+
+/// Fold each word onto the canonical spelling of its name group.
+fn canonicalize_arabic_names(s: &str) -> String {
+    s.split_whitespace()
+        .map(|word| {
+            let bare = word.trim_start_matches("al-").trim_start_matches("el-");
+            ARABIC_NAME_VARIANTS
+                .iter()
+                .find(|group| group.contains(&bare))
+                .map(|group| group[0])
+                .unwrap_or(word)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cyrillic_icao_and_common() {
+        let variants = transliterate_variants("Владимир Путин");
+        assert!(variants.contains(&"vladimir putin".to_string()));
+
+        let variants = transliterate_variants("Юрий Ковальчук");
+        assert!(variants.contains(&"iurii kovalchuk".to_string()));
+        assert!(variants.contains(&"yuriy kovalchuk".to_string()));
+    }
+
+    #[test]
+    fn greek_icao() {
+        let variants = transliterate_variants("Γιώργος Παπαδόπουλος");
+        assert!(variants.contains(&"giorgos papadopoulos".to_string()));
+    }
+
+    #[test]
+    fn arabic_script_and_latin_variants_meet() {
+        let script = transliterate_variants("معمر القذافي");
+        let latin = transliterate_variants("Moammar Gaddafi");
+        assert!(script.contains(&"muammar qadhafi".to_string()));
+        assert!(latin.contains(&"muammar qadhafi".to_string()));
+
+        assert_eq!(transliterate_variants("Mohamed Ali"), vec!["muhammad ali".to_string()]);
+    }
+
+    #[test]
+    fn chinese_pinyin() {
+        let variants = transliterate_variants("习近平");
+        assert!(variants.contains(&"xi jinping".to_string()));
+    }
+
+    #[test]
+    fn plain_latin_has_no_variants() {
+        assert!(transliterate_variants("John Doe").is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+use aegistry_core::{new_request_id, Hit, HitSource, ScoreComponents, SubjectKind};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    Extension, Json,
+};
+use chrono::Utc;
+use ingest::CryptoAddressMatch;
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::auth::ApiKeyAuth;
+use crate::{audit, cases, risk, suppression, ApiError, AppState};
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ScreenAddressRequest {
+    pub reference_id: Option<String>,
+    #[validate(length(min = 1, max = 128))]
+    pub address: String,
+    /// Ticker as OFAC lists it ("XBT", "ETH", "USDT", ...); "BTC" is accepted
+    /// for "XBT". Without one, every chain is searched. Ignored for hex (`0x`)
+    /// addresses, which are the same account on every EVM chain and token.
+    #[validate(length(min = 1, max = 16))]
+    pub chain: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScreenAddressResponse {
+    pub request_id: String,
+    pub reference_id: Option<String>,
+    pub hits: Vec<Hit>,
+    pub checked_at: String,
+}
+
+/// Look a wallet address up among the digital currency addresses of listed
+/// subjects. Matching is exact; a match is always a hit.
+pub async fn screen_address(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    headers: HeaderMap,
+    Json(req): Json<ScreenAddressRequest>,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    let start = Instant::now();
+    counter!("screening_requests_total", "type" => "address").increment(1);
+
+    if let Err(e) = req.validate() {
+        counter!("screening_errors_total", "type" => "validation").increment(1);
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(ApiError::from_validation(e))));
+    }
+
+    let risk_config = state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map_err(risk::risk_store_error)?;
+
+    let matches = {
+        let db = state.monitoring_db.lock().await;
+        ingest::find_crypto_address(&db, &req.address, req.chain.as_deref()).map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    message: "address_lookup_error".to_string(),
+                    details: vec![format!("Failed to look up address: {}", e)],
+                }),
+            )
+        })?
+    };
+    let mut hits: Vec<Hit> = matches
+        .into_iter()
+        .map(|m| address_hit(m, risk_config.risk_level(1.0)))
+        .collect();
+    {
+        let db = state.tenant_db.lock().await;
+        if let Err(e) = suppression::apply(&db, &auth.tenant_id, req.reference_id.as_deref(), &req.address, &mut hits) {
+            tracing::error!(error = %e, tenant_id = %auth.tenant_id, "failed to apply suppressions");
+        }
+    }
+
+    let response = ScreenAddressResponse {
+        request_id: new_request_id(),
+        reference_id: req.reference_id.clone(),
+        hits,
+        checked_at: Utc::now().to_rfc3339(),
+    };
+
+    histogram!("screening_latency_seconds", "type" => "address").record(start.elapsed().as_secs_f64());
+
+    let entry = audit::AuditEntry::screening(
+        &auth.tenant_id,
+        "address",
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &response.hits,
+        start.elapsed(),
+    )
+    .with_payloads(&req, &response);
+    audit::record(&state, entry).await;
+    cases::record(
+        &state,
+        &auth.tenant_id,
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &req.address,
+        &response.hits,
+    )
+    .await;
+
+    crate::format_response(&headers, &response)
+}
+
+fn address_hit(m: CryptoAddressMatch, risk_level: aegistry_core::RiskLevel) -> Hit {
+    let kind = SubjectKind::from_code(&m.kind).unwrap_or(SubjectKind::Person);
+    Hit {
+        subject_id: m.subject_id,
+        explanation: vec![format!(
+            "{} address '{}' is listed for '{}'",
+            m.chain, m.address, m.primary_name
+        )],
+        matched_name: m.primary_name,
+        source: HitSource::from_code(&m.source).unwrap_or(HitSource::Stub),
+        kind,
+        score: 1.0,
+        risk_level,
+        components: ScoreComponents {
+            name_similarity: 0.0,
+            dob_similarity: 0.0,
+            country_match: 0.0,
+            phonetic_match: false,
+            identifier_match: 1.0,
+        },
+        matched_alias: None,
+        record_version: m.record_version,
+        suppressed: false,
+        suppression_reason: None,
+    }
+}
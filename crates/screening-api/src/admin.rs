@@ -0,0 +1,374 @@
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use validator::Validate;
+
+use crate::auth::Scope;
+use crate::tenant::Tenant;
+use crate::tenant_db::{self, ApiKeyInfo, UsageStat};
+use crate::{ApiError, AppState};
+
+/// SHA-256 of the admin credential, as held in `AppConfig::admin_key_hash`
+pub fn hash_admin_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Guards `/admin/v1`. Admin access uses its own credential in the
+/// `X-Admin-Key` header; tenant API keys are never accepted here. The admin
+/// API is disabled unless `ADMIN_API_KEY` is set.
+pub async fn admin_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    let Some(expected) = state.config.admin_key_hash.as_deref() else {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ApiError {
+                message: "admin_disabled".to_string(),
+                details: vec!["Set ADMIN_API_KEY to enable the admin API".to_string()],
+            }),
+        ));
+    };
+
+    let presented = request
+        .headers()
+        .get("x-admin-key")
+        .and_then(|v| v.to_str().ok())
+        .map(hash_admin_key);
+
+    if presented.as_deref() != Some(expected) {
+        tracing::warn!(path = %request.uri().path(), "rejected admin request");
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiError {
+                message: "invalid_admin_key".to_string(),
+                details: vec!["A valid X-Admin-Key header is required".to_string()],
+            }),
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTenantRequest {
+    /// Defaults to a generated UUID
+    #[validate(length(min = 1, max = 64))]
+    pub id: Option<String>,
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[validate(range(min = 1))]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateTenantRequest {
+    #[validate(length(min = 1))]
+    pub name: Option<String>,
+    /// Suspending a tenant rejects its keys from the next request on
+    pub is_active: Option<bool>,
+    #[validate(range(min = 1))]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateKeyRequest {
+    pub name: Option<String>,
+    /// RFC 3339 timestamp after which the key stops working
+    pub expires_at: Option<String>,
+    /// Defaults to every scope
+    pub scopes: Option<Vec<Scope>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantDetail {
+    pub tenant: Tenant,
+    pub keys: Vec<ApiKeyInfo>,
+}
+
+/// Response to key creation. `api_key` is shown here once and never again.
+#[derive(Debug, Serialize)]
+pub struct CreatedKey {
+    pub key: ApiKeyInfo,
+    pub api_key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatedTenant {
+    pub tenant: Tenant,
+    pub key: ApiKeyInfo,
+    pub api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    /// First day of the period, `YYYY-MM-DD`; defaults to the start of the current month
+    pub from_date: Option<String>,
+    /// Last day of the period, inclusive; defaults to today
+    pub to_date: Option<String>,
+}
+
+/// A tenant's billable usage over a period, per endpoint and API key
+#[derive(Debug, Serialize)]
+pub struct TenantUsage {
+    pub tenant_id: String,
+    pub from_date: String,
+    pub to_date: Option<String>,
+    /// Screenings billed in the period; a batch counts once per record
+    pub total: i64,
+    pub usage: Vec<UsageStat>,
+}
+
+pub async fn list_tenants(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Tenant>>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    tenant_db::list_tenants(&db).map(Json).map_err(admin_store_error)
+}
+
+pub async fn create_tenant(
+    State(state): State<AppState>,
+    Json(req): Json<CreateTenantRequest>,
+) -> Result<(StatusCode, Json<CreatedTenant>), (StatusCode, Json<ApiError>)> {
+    if let Err(e) = req.validate() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(ApiError::from_validation(e))));
+    }
+
+    let tenant_id = req.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let db = state.tenant_db.lock().await;
+    if tenant_db::get_tenant(&db, &tenant_id).is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiError {
+                message: "tenant_exists".to_string(),
+                details: vec![format!("Tenant {} already exists", tenant_id)],
+            }),
+        ));
+    }
+
+    let api_key = tenant_db::generate_api_key();
+    let key_id = tenant_db::create_tenant(&db, &tenant_id, &req.name, &api_key).map_err(admin_store_error)?;
+    if req.rate_limit_per_minute.is_some() {
+        tenant_db::update_tenant(&db, &tenant_id, None, None, req.rate_limit_per_minute)
+            .map_err(admin_store_error)?;
+    }
+
+    let tenant = tenant_db::get_tenant(&db, &tenant_id).ok_or_else(|| tenant_not_found(&tenant_id))?;
+    let key = find_key(&db, &tenant_id, &key_id)?;
+    tracing::info!(tenant_id = %tenant_id, key_id = %key_id, "admin created tenant");
+
+    Ok((StatusCode::CREATED, Json(CreatedTenant { tenant, key, api_key })))
+}
+
+pub async fn get_tenant(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<TenantDetail>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    let tenant = tenant_db::get_tenant(&db, &tenant_id).ok_or_else(|| tenant_not_found(&tenant_id))?;
+    let keys = tenant_db::list_api_keys(&db, &tenant_id).map_err(admin_store_error)?;
+    Ok(Json(TenantDetail { tenant, keys }))
+}
+
+pub async fn update_tenant(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    Json(req): Json<UpdateTenantRequest>,
+) -> Result<Json<Tenant>, (StatusCode, Json<ApiError>)> {
+    if let Err(e) = req.validate() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(ApiError::from_validation(e))));
+    }
+
+    let db = state.tenant_db.lock().await;
+    if tenant_db::get_tenant(&db, &tenant_id).is_none() {
+        return Err(tenant_not_found(&tenant_id));
+    }
+    tenant_db::update_tenant(
+        &db,
+        &tenant_id,
+        req.name.as_deref(),
+        req.is_active,
+        req.rate_limit_per_minute,
+    )
+    .map_err(admin_store_error)?;
+
+    let tenant = tenant_db::get_tenant(&db, &tenant_id).ok_or_else(|| tenant_not_found(&tenant_id))?;
+    tracing::info!(
+        tenant_id = %tenant_id,
+        is_active = tenant.is_active,
+        rate_limit_per_minute = tenant.rate_limit_per_minute,
+        "admin updated tenant"
+    );
+    Ok(Json(tenant))
+}
+
+pub async fn list_keys(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<Vec<ApiKeyInfo>>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    if tenant_db::get_tenant(&db, &tenant_id).is_none() {
+        return Err(tenant_not_found(&tenant_id));
+    }
+    tenant_db::list_api_keys(&db, &tenant_id).map(Json).map_err(admin_store_error)
+}
+
+pub async fn create_key(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    Json(req): Json<CreateKeyRequest>,
+) -> Result<(StatusCode, Json<CreatedKey>), (StatusCode, Json<ApiError>)> {
+    if let Some(expires_at) = &req.expires_at {
+        if chrono::DateTime::parse_from_rfc3339(expires_at).is_err() {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ApiError {
+                    message: "invalid_request".to_string(),
+                    details: vec!["expires_at: must be an RFC 3339 timestamp".to_string()],
+                }),
+            ));
+        }
+    }
+
+    let scopes = req.scopes.as_deref().unwrap_or(Scope::ALL);
+    if scopes.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                message: "invalid_request".to_string(),
+                details: vec!["scopes: at least one scope is required".to_string()],
+            }),
+        ));
+    }
+
+    let db = state.tenant_db.lock().await;
+    if tenant_db::get_tenant(&db, &tenant_id).is_none() {
+        return Err(tenant_not_found(&tenant_id));
+    }
+    let issued = tenant_db::create_api_key(
+        &db,
+        &tenant_id,
+        req.name.as_deref(),
+        req.expires_at.as_deref(),
+        scopes,
+    )
+    .map_err(admin_store_error)?;
+    let key = find_key(&db, &tenant_id, &issued.key_id)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedKey {
+            key,
+            api_key: issued.api_key,
+        }),
+    ))
+}
+
+pub async fn revoke_key(
+    State(state): State<AppState>,
+    Path((tenant_id, key_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    match tenant_db::revoke_api_key(&db, &tenant_id, &key_id) {
+        Ok(true) => {
+            tracing::info!(tenant_id = %tenant_id, key_id = %key_id, "admin revoked API key");
+            Ok(Json(serde_json::json!({ "status": "revoked", "key_id": key_id })))
+        }
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                message: "not_found".to_string(),
+                details: vec![format!("Active key {} not found for tenant {}", key_id, tenant_id)],
+            }),
+        )),
+        Err(e) => Err(admin_store_error(e)),
+    }
+}
+
+pub async fn tenant_usage(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<TenantUsage>, (StatusCode, Json<ApiError>)> {
+    // The period ends at the start of the day after to_date
+    let parse = |name: &str, value: &str, next_day: bool| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| if next_day { date.succ_opt() } else { Some(date) })
+            .ok_or_else(|| {
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(ApiError {
+                        message: "invalid_request".to_string(),
+                        details: vec![format!("{}: must be YYYY-MM-DD", name)],
+                    }),
+                )
+            })
+    };
+    let from = match &query.from_date {
+        Some(value) => parse("from_date", value, false)?,
+        None => Utc::now().date_naive().with_day(1).expect("every month has a first day"),
+    };
+    let until = match &query.to_date {
+        Some(value) => Some(parse("to_date", value, true)?),
+        None => None,
+    };
+    // usage_log timestamps are SQLite datetime('now') values
+    let format = |date: NaiveDate| format!("{} 00:00:00", date.format("%Y-%m-%d"));
+
+    let db = state.tenant_db.lock().await;
+    if tenant_db::get_tenant(&db, &tenant_id).is_none() {
+        return Err(tenant_not_found(&tenant_id));
+    }
+    let usage = tenant_db::get_usage_stats(&db, &tenant_id, &format(from), until.map(format).as_deref())
+        .map_err(admin_store_error)?;
+
+    Ok(Json(TenantUsage {
+        tenant_id,
+        from_date: from.to_string(),
+        to_date: query.to_date,
+        total: usage.iter().map(|u| u.request_count).sum(),
+        usage,
+    }))
+}
+
+fn find_key(
+    conn: &rusqlite::Connection,
+    tenant_id: &str,
+    key_id: &str,
+) -> Result<ApiKeyInfo, (StatusCode, Json<ApiError>)> {
+    tenant_db::list_api_keys(conn, tenant_id)
+        .map_err(admin_store_error)?
+        .into_iter()
+        .find(|k| k.id == key_id)
+        .ok_or_else(|| admin_store_error(anyhow::anyhow!("key {} missing after creation", key_id)))
+}
+
+fn tenant_not_found(tenant_id: &str) -> (StatusCode, Json<ApiError>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiError {
+            message: "not_found".to_string(),
+            details: vec![format!("Tenant {} not found", tenant_id)],
+        }),
+    )
+}
+
+fn admin_store_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "tenant admin failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "admin_error".to_string(),
+            details: vec![format!("Failed to access tenants: {}", e)],
+        }),
+    )
+}
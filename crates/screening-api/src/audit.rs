@@ -1,11 +1,16 @@
+use aegistry_core::Hit;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
+use chrono::{DateTime, Duration as TimeDelta, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
 
-use crate::{AppState, ApiError};
+use crate::auth::ApiKeyAuth;
+use crate::{tenant_db, AppState, ApiError};
 
 #[derive(Debug, Serialize)]
 pub struct AuditEntry {
@@ -19,35 +24,380 @@ pub struct AuditEntry {
     pub hit_count: i32,
     pub max_score: Option<f32>,
     pub processing_time_ms: i64,
+    /// `dataset_version` ids (one per source) the screening was matched against
+    pub dataset_versions: Vec<i64>,
     pub created_at: String,
+    /// Position in the tenant's hash chain, starting at 1
+    pub seq: i64,
+    /// `entry_hash` of the tenant's previous entry, `GENESIS_HASH` for the first
+    pub prev_hash: String,
+    /// SHA-256 of `prev_hash` followed by the entry's canonical JSON
+    pub entry_hash: String,
+}
+
+/// `prev_hash` of the first entry in every tenant's chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The hashed fields of an entry, in a fixed order. Hashes are computed over
+/// this rather than `AuditEntry` so adding display-only fields later cannot
+/// invalidate existing chains.
+#[derive(Serialize)]
+struct CanonicalEntry<'a> {
+    seq: i64,
+    id: &'a str,
+    tenant_id: &'a str,
+    request_id: &'a str,
+    reference_id: Option<&'a str>,
+    request_type: &'a str,
+    request_payload: &'a str,
+    response_payload: &'a str,
+    hit_count: i32,
+    max_score: Option<f32>,
+    processing_time_ms: i64,
+    dataset_versions: &'a [i64],
+    created_at: &'a str,
+}
+
+impl AuditEntry {
+    /// Entry for a finished screening; payloads are attached with `with_payloads`
+    /// and dataset versions are filled in by `record`.
+    pub fn screening(
+        tenant_id: &str,
+        request_type: &str,
+        request_id: &str,
+        reference_id: Option<&str>,
+        hits: &[Hit],
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tenant_id: tenant_id.to_string(),
+            request_id: request_id.to_string(),
+            reference_id: reference_id.map(|s| s.to_string()),
+            request_type: request_type.to_string(),
+            request_payload: String::new(),
+            response_payload: String::new(),
+            hit_count: hits.len() as i32,
+            max_score: hits.iter().map(|h| h.score).reduce(f32::max),
+            processing_time_ms: elapsed.as_millis() as i64,
+            dataset_versions: Vec::new(),
+            created_at: Utc::now().to_rfc3339(),
+            seq: 0,
+            prev_hash: String::new(),
+            entry_hash: String::new(),
+        }
+    }
+
+    pub fn canonical_json(&self) -> String {
+        let canonical = CanonicalEntry {
+            seq: self.seq,
+            id: &self.id,
+            tenant_id: &self.tenant_id,
+            request_id: &self.request_id,
+            reference_id: self.reference_id.as_deref(),
+            request_type: &self.request_type,
+            request_payload: &self.request_payload,
+            response_payload: &self.response_payload,
+            hit_count: self.hit_count,
+            max_score: self.max_score,
+            processing_time_ms: self.processing_time_ms,
+            dataset_versions: &self.dataset_versions,
+            created_at: &self.created_at,
+        };
+        serde_json::to_string(&canonical).unwrap_or_default()
+    }
+
+    /// The hash this entry should carry given its `prev_hash`
+    pub fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(self.canonical_json().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    pub fn with_payloads<Req: Serialize, Resp: Serialize>(mut self, request: &Req, response: &Resp) -> Self {
+        self.request_payload = serde_json::to_string(request).unwrap_or_default();
+        self.response_payload = serde_json::to_string(response).unwrap_or_default();
+        self
+    }
+}
+
+/// Stamp the entry with the dataset versions currently loaded and write it
+/// to the tenant DB. Failures are logged rather than failing the screening.
+pub async fn record(state: &AppState, mut entry: AuditEntry) {
+    entry.dataset_versions = {
+        let db = state.monitoring_db.lock().await;
+        ingest::current_dataset_versions(&db).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "failed to read dataset versions for audit");
+            Vec::new()
+        })
+    };
+
+    let db = state.tenant_db.lock().await;
+    if let Err(e) = tenant_db::insert_audit_entry(&db, &mut entry) {
+        tracing::error!(
+            error = %e,
+            tenant_id = %entry.tenant_id,
+            request_id = %entry.request_id,
+            "failed to write audit entry"
+        );
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct AuditQueryParams {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    /// `YYYY-MM-DD` or an RFC 3339 timestamp, inclusive
     pub from_date: Option<String>,
+    /// `YYYY-MM-DD` (the whole day) or an RFC 3339 timestamp, inclusive
     pub to_date: Option<String>,
 }
 
+/// A date filter as an instant. `from` bounds are inclusive; `to` bounds are
+/// returned exclusive, so a bare date covers its whole day and a timestamp
+/// includes entries written at exactly that instant.
+pub fn parse_date_bound(value: &str, upper: bool) -> Option<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let date = if upper { date.succ_opt()? } else { date };
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+    let instant = DateTime::parse_from_rfc3339(value).ok()?.with_timezone(&Utc);
+    Some(if upper { instant + TimeDelta::nanoseconds(1) } else { instant })
+}
+
+/// `parse_date_bound` for a query parameter, in the form `created_at` is
+/// stored in (`to_rfc3339` in UTC, `+00:00` offset) so the two compare as strings
+fn date_param(name: &str, value: Option<&str>, upper: bool) -> Result<Option<String>, (StatusCode, Json<ApiError>)> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match parse_date_bound(value, upper) {
+        Some(bound) => Ok(Some(bound.to_rfc3339())),
+        None => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                message: "invalid_request".to_string(),
+                details: vec![format!("{}: must be YYYY-MM-DD or an RFC 3339 timestamp", name)],
+            }),
+        )),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditPage {
+    pub entries: Vec<AuditEntry>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 500;
+
 pub async fn list_audit_entries(
-    State(_state): State<AppState>,
-    Query(_params): Query<AuditQueryParams>,
-) -> Result<Json<Vec<AuditEntry>>, (StatusCode, Json<ApiError>)> {
-    // TODO: Implement - call tenant_db::get_audit_entries
-    Ok(Json(vec![]))
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(params): Query<AuditQueryParams>,
+) -> Result<Json<AuditPage>, (StatusCode, Json<ApiError>)> {
+    let limit = params
+        .limit
+        .map_or(DEFAULT_PAGE_SIZE, |l| l as i64)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = params.offset.map_or(0, |o| o as i64).max(0);
+    let from = date_param("from_date", params.from_date.as_deref(), false)?;
+    let until = date_param("to_date", params.to_date.as_deref(), true)?;
+
+    let db = state.tenant_db.lock().await;
+    let (entries, total) = tenant_db::get_audit_entries(
+        &db,
+        &auth.tenant_id,
+        from.as_deref(),
+        until.as_deref(),
+        limit,
+        offset,
+    )
+    .map_err(audit_error)?;
+
+    Ok(Json(AuditPage {
+        entries,
+        total,
+        limit,
+        offset,
+    }))
 }
 
 pub async fn get_audit_entry(
-    State(_state): State<AppState>,
-    Path(_request_id): Path<String>,
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(request_id): Path<String>,
 ) -> Result<Json<AuditEntry>, (StatusCode, Json<ApiError>)> {
-    // TODO: Implement - call tenant_db::get_audit_entry_by_request_id
-    Err((
-        StatusCode::NOT_FOUND,
+    let db = state.tenant_db.lock().await;
+    match tenant_db::get_audit_entry_by_request_id(&db, &auth.tenant_id, &request_id) {
+        Ok(Some(entry)) => Ok(Json(entry)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                message: "not_found".to_string(),
+                details: vec![format!("Audit entry {} not found", request_id)],
+            }),
+        )),
+        Err(e) => Err(audit_error(e)),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChainVerification {
+    pub valid: bool,
+    pub entries_checked: usize,
+    /// Hash of the last entry; auditors can record it to detect truncation later
+    pub head_hash: Option<String>,
+    pub first_broken: Option<BrokenLink>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrokenLink {
+    pub seq: i64,
+    pub request_id: String,
+    pub reason: String,
+}
+
+/// Walk a tenant's chain in `seq` order and report the first entry that was
+/// edited, removed or reordered. Works on any list of entries, e.g. an export
+/// read back from disk, so the check can be repeated away from the live DB.
+pub fn verify_chain(entries: &[AuditEntry]) -> ChainVerification {
+    let mut expected_prev = GENESIS_HASH.to_string();
+
+    for (checked, entry) in entries.iter().enumerate() {
+        let expected_seq = checked as i64 + 1;
+        let reason = if entry.seq != expected_seq {
+            Some(format!("expected seq {}, found {} (entries missing or reordered)", expected_seq, entry.seq))
+        } else if entry.prev_hash != expected_prev {
+            Some("prev_hash does not match the previous entry's hash".to_string())
+        } else if entry.compute_hash() != entry.entry_hash {
+            Some("entry contents do not match entry_hash".to_string())
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            return ChainVerification {
+                valid: false,
+                entries_checked: checked,
+                head_hash: None,
+                first_broken: Some(BrokenLink {
+                    seq: entry.seq,
+                    request_id: entry.request_id.clone(),
+                    reason,
+                }),
+            };
+        }
+
+        expected_prev = entry.entry_hash.clone();
+    }
+
+    ChainVerification {
+        valid: true,
+        entries_checked: entries.len(),
+        head_hash: entries.last().map(|e| e.entry_hash.clone()),
+        first_broken: None,
+    }
+}
+
+pub async fn verify_audit_chain(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+) -> Result<Json<ChainVerification>, (StatusCode, Json<ApiError>)> {
+    let entries = {
+        let db = state.tenant_db.lock().await;
+        tenant_db::get_audit_chain(&db, &auth.tenant_id).map_err(audit_error)?
+    };
+
+    let verification = verify_chain(&entries);
+    if !verification.valid {
+        tracing::warn!(
+            tenant_id = %auth.tenant_id,
+            broken = ?verification.first_broken,
+            "audit chain verification failed"
+        );
+    }
+    Ok(Json(verification))
+}
+
+fn audit_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "audit query failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
         Json(ApiError {
-            message: "not_implemented".to_string(),
-            details: vec!["Audit trail not yet implemented".to_string()],
+            message: "audit_error".to_string(),
+            details: vec![format!("Failed to read audit trail: {}", e)],
         }),
-    ))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_bounds_compare_with_stored_timestamps() {
+        let stored = "2026-10-16T17:45:12.345678+00:00";
+        let bound = |value: &str, upper: bool| parse_date_bound(value, upper).unwrap().to_rfc3339();
+
+        // A bare upper date covers the whole day
+        assert!(stored >= bound("2026-10-16", false).as_str());
+        assert!(stored < bound("2026-10-16", true).as_str());
+        assert!(stored < bound("2026-10-17", false).as_str());
+
+        // "Z" and other offsets are normalised to the stored form
+        assert_eq!(bound("2026-10-16T17:45:12Z", false), "2026-10-16T17:45:12+00:00");
+        assert_eq!(bound("2026-10-16T19:45:12+02:00", false), "2026-10-16T17:45:12+00:00");
+        assert!(stored >= bound("2026-10-16T17:45:12Z", false).as_str());
+        assert!(stored < bound("2026-10-16T17:45:12.345678Z", true).as_str());
+        assert!(stored >= bound("2026-10-16T17:45:12.345677Z", true).as_str());
+
+        assert!(parse_date_bound("16/10/2026", false).is_none());
+    }
+
+    fn chain(len: usize) -> Vec<AuditEntry> {
+        let mut prev = GENESIS_HASH.to_string();
+        (1..=len as i64)
+            .map(|seq| {
+                let mut entry = AuditEntry::screening("t1", "person", &format!("r{}", seq), None, &[], Duration::ZERO);
+                entry.seq = seq;
+                entry.prev_hash = prev.clone();
+                entry.entry_hash = entry.compute_hash();
+                prev = entry.entry_hash.clone();
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let entries = chain(3);
+        let result = verify_chain(&entries);
+        assert!(result.valid);
+        assert_eq!(result.entries_checked, 3);
+        assert_eq!(result.head_hash.as_deref(), Some(entries[2].entry_hash.as_str()));
+    }
+
+    #[test]
+    fn edited_entry_is_reported() {
+        let mut entries = chain(3);
+        entries[1].hit_count = 5;
+        let broken = verify_chain(&entries).first_broken.unwrap();
+        assert_eq!(broken.seq, 2);
+        assert_eq!(broken.request_id, "r2");
+    }
+
+    #[test]
+    fn deleted_entry_is_reported() {
+        let mut entries = chain(3);
+        entries.remove(1);
+        let result = verify_chain(&entries);
+        assert!(!result.valid);
+        assert_eq!(result.entries_checked, 1);
+        assert_eq!(result.first_broken.unwrap().seq, 3);
+    }
 }
@@ -1,15 +1,182 @@
 use axum::{
-    extract::{Request, State},
-    http::StatusCode,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderValue, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
 
-use crate::{ApiError, AppState};
+use crate::rate_limit::{self, REMAINING_HEADER};
+use crate::{tenant_db, ApiError, AppState};
 
 #[derive(Clone)]
 pub struct ApiKeyAuth {
     pub tenant_id: String,
+    /// `api_key` row the request was made with, for usage attribution
+    pub api_key_id: String,
+    pub rate_limit_per_minute: u32,
+    pub scopes: Vec<Scope>,
+}
+
+/// What an API key may call. Routes are grouped by scope in `build_router`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    /// Single person and entity screening
+    Screen,
+    Batch,
+    Monitoring,
+    /// Working case alerts and suppressions
+    Cases,
+    ReadAudit,
+    /// Tenant configuration: risk config and case settings
+    Admin,
+}
+
+impl Scope {
+    pub const ALL: &'static [Scope] = &[
+        Scope::Screen,
+        Scope::Batch,
+        Scope::Monitoring,
+        Scope::Cases,
+        Scope::ReadAudit,
+        Scope::Admin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Screen => "screen",
+            Scope::Batch => "batch",
+            Scope::Monitoring => "monitoring",
+            Scope::Cases => "cases",
+            Scope::ReadAudit => "read-audit",
+            Scope::Admin => "admin",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Scope::ALL.iter().copied().find(|scope| scope.as_str() == s)
+    }
+
+    /// Comma-separated form stored in `api_key.scopes`
+    pub fn join(scopes: &[Scope]) -> String {
+        scopes.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(",")
+    }
+
+    /// Inverse of `join`; unknown names are dropped
+    pub fn split(stored: &str) -> Vec<Scope> {
+        stored.split(',').filter_map(|s| Scope::parse(s.trim())).collect()
+    }
+}
+
+/// Route layer rejecting keys that lack `scope`. Runs after `auth_middleware`.
+pub async fn require_scope(
+    scope: Scope,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, axum::Json<ApiError>)> {
+    let granted = request
+        .extensions()
+        .get::<ApiKeyAuth>()
+        .is_some_and(|auth| auth.scopes.contains(&scope));
+    if !granted {
+        return Err((
+            StatusCode::FORBIDDEN,
+            axum::Json(ApiError {
+                message: "insufficient_scope".to_string(),
+                details: vec![format!("API key lacks the '{}' scope", scope.as_str())],
+            }),
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+/// Verified keys the cache holds at most; an arbitrary one makes room for a new one
+const KEY_CACHE_CAPACITY: usize = 10_000;
+
+/// Keys that already passed Argon2 verification, by SHA-256 of the raw key.
+/// Only the key id is cached; revocation, expiry and tenant status are read
+/// from the DB on every request.
+#[derive(Default)]
+pub struct KeyCache {
+    verified: Mutex<HashMap<String, String>>,
+}
+
+impl KeyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn digest(api_key: &str) -> String {
+        hex::encode(Sha256::digest(api_key.as_bytes()))
+    }
+
+    fn get(&self, api_key: &str) -> Option<String> {
+        self.verified.lock().unwrap().get(&Self::digest(api_key)).cloned()
+    }
+
+    fn insert(&self, api_key: &str, key_id: &str) {
+        let digest = Self::digest(api_key);
+        let mut verified = self.verified.lock().unwrap();
+        if verified.len() >= KEY_CACHE_CAPACITY && !verified.contains_key(&digest) {
+            if let Some(evicted) = verified.keys().next().cloned() {
+                verified.remove(&evicted);
+            }
+        }
+        verified.insert(digest, key_id.to_string());
+    }
+
+    fn remove(&self, api_key: &str) {
+        self.verified.lock().unwrap().remove(&Self::digest(api_key));
+    }
+}
+
+/// Resolve a raw key to its id. The tenant DB is only locked to read the
+/// candidate rows and write a legacy key's upgrade; Argon2 runs without it,
+/// so bad keys cannot hold up every other tenant's writes.
+async fn verify_key(state: &AppState, api_key: &str) -> Result<Option<String>, (StatusCode, axum::Json<ApiError>)> {
+    let candidates = {
+        let db = state.tenant_db.lock().await;
+        tenant_db::key_candidates(&db, api_key).map_err(auth_store_error)?
+    };
+    let key = api_key.to_string();
+    let found = blocking(move || Ok(tenant_db::match_api_key(&key, &candidates))).await?;
+    if found.is_some() {
+        return Ok(found);
+    }
+
+    let legacy = {
+        let db = state.tenant_db.lock().await;
+        tenant_db::find_legacy_key(&db, api_key).map_err(auth_store_error)?
+    };
+    let Some(key_id) = legacy else {
+        return Ok(None);
+    };
+    let key = api_key.to_string();
+    let key_hash = blocking(move || tenant_db::hash_api_key(&key)).await?;
+    let db = state.tenant_db.lock().await;
+    tenant_db::upgrade_legacy_key(&db, &key_id, api_key, &key_hash).map_err(auth_store_error)?;
+    Ok(Some(key_id))
+}
+
+/// Run Argon2 work on the blocking pool
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> Result<T, (StatusCode, axum::Json<ApiError>)> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|r| r)
+        .map_err(auth_store_error)
+}
+
+/// Batch submissions and bulk monitoring uploads are charged per record by
+/// the handler itself
+fn charged_by_handler(method: &Method, endpoint: &str) -> bool {
+    method == Method::POST && matches!(endpoint, "/v1/batch" | "/v1/monitoring/bulk")
 }
 
 pub async fn auth_middleware(
@@ -37,17 +204,35 @@ pub async fn auth_middleware(
         }
     };
 
-    // Validate API key
-    let tenant = match state.tenant_store.get_tenant_by_key(&api_key) {
+    let invalid_key = || {
+        (
+            StatusCode::UNAUTHORIZED,
+            axum::Json(ApiError {
+                message: "invalid_api_key".to_string(),
+                details: vec!["API key is invalid or expired".to_string()],
+            }),
+        )
+    };
+
+    // Validate API key; Argon2 runs on the blocking pool and only on a cache miss
+    let key_id = match state.key_cache.get(&api_key) {
+        Some(key_id) => key_id,
+        None => {
+            let key_id = verify_key(&state, &api_key).await?.ok_or_else(invalid_key)?;
+            state.key_cache.insert(&api_key, &key_id);
+            key_id
+        }
+    };
+
+    let tenant = {
+        let db = state.tenant_db.lock().await;
+        tenant_db::authorize_key(&db, &key_id).map_err(auth_store_error)?
+    };
+    let tenant_db::AuthorizedKey { tenant, scopes } = match tenant {
         Some(t) => t,
         None => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                axum::Json(ApiError {
-                    message: "invalid_api_key".to_string(),
-                    details: vec!["API key is invalid or expired".to_string()],
-                }),
-            ));
+            state.key_cache.remove(&api_key);
+            return Err(invalid_key());
         }
     };
 
@@ -62,11 +247,46 @@ pub async fn auth_middleware(
         ));
     }
 
-    // Add auth info to request extensions
-    request.extensions_mut().insert(ApiKeyAuth {
+    let auth = ApiKeyAuth {
         tenant_id: tenant.id.clone(),
-    });
+        api_key_id: key_id,
+        rate_limit_per_minute: tenant.rate_limit_per_minute,
+        scopes,
+    };
 
-    Ok(next.run(request).await)
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let remaining = if charged_by_handler(request.method(), &path) {
+        None
+    } else {
+        let endpoint = format!("{} {}", request.method(), path);
+        match rate_limit::charge(&state, &auth, &endpoint, 1).await {
+            Ok(remaining) => Some(remaining),
+            Err(response) => return Ok(response),
+        }
+    };
+
+    // Add auth info to request extensions
+    request.extensions_mut().insert(auth);
+
+    let mut response = next.run(request).await;
+    if let Some(remaining) = remaining {
+        response.headers_mut().insert(REMAINING_HEADER, HeaderValue::from(remaining));
+    }
+    Ok(response)
 }
 
+
+fn auth_store_error(e: anyhow::Error) -> (StatusCode, axum::Json<ApiError>) {
+    tracing::error!(error = %e, "API key lookup failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        axum::Json(ApiError {
+            message: "auth_error".to_string(),
+            details: vec!["Failed to verify API key".to_string()],
+        }),
+    )
+}
@@ -1,38 +1,59 @@
-use aegistry_core::Hit;
+use aegistry_core::{Hit, SubjectKind};
+use anyhow::Result;
+use axum::{http::StatusCode, Json};
 use chrono::Utc;
+use matching_core::{BirthDate, ScreeningQuery};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use utoipa::ToSchema;
 
-use crate::{perform_screening, AppState};
+use crate::audit::{self, AuditEntry};
+use crate::{cases, rate_limit};
+use crate::risk::RiskConfig;
+use crate::{perform_screening, ApiError, AppState};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, ToSchema)]
 pub enum BatchStatus {
     Processing,
     Completed,
     Failed,
+    Cancelled,
+}
+
+impl BatchStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BatchStatus::Processing => "processing",
+            BatchStatus::Completed => "completed",
+            BatchStatus::Failed => "failed",
+            BatchStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "completed" => BatchStatus::Completed,
+            "failed" => BatchStatus::Failed,
+            "cancelled" => BatchStatus::Cancelled,
+            _ => BatchStatus::Processing,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct BatchJob {
     pub id: String,
+    pub tenant_id: String,
     pub status: BatchStatus,
     pub total_records: usize,
+    /// Records `0..processed_records` have stored results; a resumed job continues from here
     pub processed_records: usize,
+    /// Set for a job larger than the tenant's per-minute limit: instead of
+    /// being charged on submission, its records are charged to this key as
+    /// they are screened
+    pub paced_api_key_id: Option<String>,
     pub created_at: String,
-    pub results: Vec<BatchResult>,
-}
-
-impl BatchJob {
-    pub fn new(id: String, total_records: usize) -> Self {
-        Self {
-            id,
-            status: BatchStatus::Processing,
-            total_records,
-            processed_records: 0,
-            created_at: Utc::now().to_rfc3339(),
-            results: Vec::new(),
-        }
-    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -57,6 +78,16 @@ pub enum RecordType {
     Entity,
 }
 
+impl RecordType {
+    /// The only kind of listed subject a record of this type is matched against
+    pub fn subject_kind(&self) -> SubjectKind {
+        match self {
+            RecordType::Person => SubjectKind::Person,
+            RecordType::Entity => SubjectKind::Entity,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct BatchResponse {
     pub job_id: String,
@@ -66,49 +97,590 @@ pub struct BatchResponse {
     pub created_at: String,
 }
 
+impl From<BatchJob> for BatchResponse {
+    fn from(job: BatchJob) -> Self {
+        Self {
+            job_id: job.id,
+            status: job.status,
+            total_records: job.total_records,
+            processed_records: job.processed_records,
+            created_at: job.created_at,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, ToSchema)]
 pub struct BatchResult {
     pub reference_id: Option<String>,
     pub name: String,
+    /// Subject kind the record was restricted to, from its `record_type`
+    pub kind_filter: SubjectKind,
     pub hits: Vec<Hit>,
     pub checked_at: String,
 }
 
-pub async fn process_batch(state: AppState, job_id: String, records: Vec<BatchRecord>) {
-    let mut results = Vec::new();
+/// Batch tables live in the tenant DB; called from `tenant_db::init_tenant_schema`.
+pub fn init_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS batch_job (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'processing',
+            total_records INTEGER NOT NULL,
+            processed_records INTEGER NOT NULL DEFAULT 0,
+            records TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
 
-    for (idx, record) in records.iter().enumerate() {
-        let dob_year = record
-            .date_of_birth
-            .as_ref()
-            .and_then(|d| d.split('-').next())
-            .and_then(|y| y.parse::<i32>().ok());
+        CREATE INDEX IF NOT EXISTS idx_batch_job_status ON batch_job(status);
+        CREATE INDEX IF NOT EXISTS idx_batch_job_tenant ON batch_job(tenant_id, created_at DESC);
 
-        let hits = perform_screening(&state, &record.name, record.country.as_deref(), dob_year);
+        CREATE TABLE IF NOT EXISTS batch_result (
+            job_id TEXT NOT NULL REFERENCES batch_job(id),
+            idx INTEGER NOT NULL,
+            reference_id TEXT,
+            name TEXT NOT NULL,
+            hits TEXT NOT NULL,
+            checked_at TEXT NOT NULL,
+            PRIMARY KEY (job_id, idx)
+        );
+        "
+    )?;
+    ingest::db::add_column_if_missing(conn, "batch_result", "kind_filter", "TEXT NOT NULL DEFAULT 'person'")?;
+    ingest::db::add_column_if_missing(conn, "batch_job", "paced_api_key_id", "TEXT")?;
+    Ok(())
+}
 
-        results.push(BatchResult {
-            reference_id: record.reference_id.clone(),
-            name: record.name.clone(),
-            hits,
-            checked_at: Utc::now().to_rfc3339(),
-        });
+/// Store a new job together with its input records so it can be resumed
+pub fn create_job(
+    conn: &Connection,
+    tenant_id: &str,
+    job_id: &str,
+    records: &[BatchRecord],
+    paced_api_key_id: Option<&str>,
+) -> Result<BatchJob> {
+    let job = BatchJob {
+        id: job_id.to_string(),
+        tenant_id: tenant_id.to_string(),
+        status: BatchStatus::Processing,
+        total_records: records.len(),
+        processed_records: 0,
+        paced_api_key_id: paced_api_key_id.map(str::to_string),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    conn.execute(
+        "INSERT INTO batch_job (id, tenant_id, status, total_records, records, paced_api_key_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            job.id,
+            job.tenant_id,
+            job.status.as_str(),
+            job.total_records as i64,
+            serde_json::to_string(records)?,
+            job.paced_api_key_id,
+            job.created_at,
+        ],
+    )?;
+    Ok(job)
+}
+
+const JOB_COLUMNS: &str = "id, tenant_id, status, total_records, processed_records, paced_api_key_id, created_at";
+
+fn job_from_row(row: &rusqlite::Row) -> rusqlite::Result<BatchJob> {
+    Ok(BatchJob {
+        id: row.get(0)?,
+        tenant_id: row.get(1)?,
+        status: BatchStatus::parse(&row.get::<_, String>(2)?),
+        total_records: row.get::<_, i64>(3)? as usize,
+        processed_records: row.get::<_, i64>(4)? as usize,
+        paced_api_key_id: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// A job owned by `tenant_id`; other tenants' jobs are indistinguishable from missing ones
+pub fn get_job(conn: &Connection, tenant_id: &str, job_id: &str) -> Result<Option<BatchJob>> {
+    let job = conn
+        .query_row(
+            &format!("SELECT {} FROM batch_job WHERE tenant_id = ?1 AND id = ?2", JOB_COLUMNS),
+            [tenant_id, job_id],
+            job_from_row,
+        )
+        .optional()?;
+    Ok(job)
+}
+
+pub fn list_jobs(conn: &Connection, tenant_id: &str, limit: i64, offset: i64) -> Result<Vec<BatchJob>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM batch_job WHERE tenant_id = ?1
+         ORDER BY created_at DESC, rowid DESC LIMIT ?2 OFFSET ?3",
+        JOB_COLUMNS
+    ))?;
+    let jobs = stmt
+        .query_map(rusqlite::params![tenant_id, limit, offset], job_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(jobs)
+}
+
+/// Jobs a previous run left unfinished
+pub fn unfinished_jobs(conn: &Connection) -> Result<Vec<BatchJob>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM batch_job WHERE status = ?1 ORDER BY created_at",
+        JOB_COLUMNS
+    ))?;
+    let jobs = stmt
+        .query_map([BatchStatus::Processing.as_str()], job_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(jobs)
+}
+
+pub fn get_records(conn: &Connection, job_id: &str) -> Result<Vec<BatchRecord>> {
+    let records: String = conn.query_row("SELECT records FROM batch_job WHERE id = ?1", [job_id], |row| row.get(0))?;
+    Ok(serde_json::from_str(&records)?)
+}
+
+/// Store results for records `start..start + results.len()` and advance the
+/// job's progress in one transaction. Re-saving an index overwrites it, so a
+/// chunk interrupted by a restart can simply be screened again.
+pub fn save_results(conn: &Connection, job_id: &str, start: usize, results: &[BatchResult]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    for (offset, result) in results.iter().enumerate() {
+        tx.execute(
+            "INSERT OR REPLACE INTO batch_result (job_id, idx, reference_id, name, kind_filter, hits, checked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                job_id,
+                (start + offset) as i64,
+                result.reference_id,
+                result.name,
+                result.kind_filter.as_str(),
+                serde_json::to_string(&result.hits)?,
+                result.checked_at,
+            ],
+        )?;
+    }
+    tx.execute(
+        "UPDATE batch_job SET processed_records = ?2, updated_at = datetime('now') WHERE id = ?1",
+        rusqlite::params![job_id, (start + results.len()) as i64],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Move a processing job to `status`. Returns false when the job had
+/// already finished, so a late completion cannot overwrite a cancellation.
+pub fn set_status(conn: &Connection, job_id: &str, status: BatchStatus) -> Result<bool> {
+    let updated = conn.execute(
+        "UPDATE batch_job SET status = ?2, updated_at = datetime('now') WHERE id = ?1 AND status = ?3",
+        [job_id, status.as_str(), BatchStatus::Processing.as_str()],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Delete finished jobs and their results once they are older than
+/// `retention_days`. Returns the number of jobs removed.
+pub fn purge_expired(conn: &Connection, retention_days: i64) -> Result<usize> {
+    let cutoff = format!("-{} days", retention_days);
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM batch_result WHERE job_id IN (
+             SELECT id FROM batch_job WHERE status != ?1 AND updated_at < datetime('now', ?2))",
+        [BatchStatus::Processing.as_str(), &cutoff],
+    )?;
+    let removed = tx.execute(
+        "DELETE FROM batch_job WHERE status != ?1 AND updated_at < datetime('now', ?2)",
+        [BatchStatus::Processing.as_str(), &cutoff],
+    )?;
+    tx.commit()?;
+    Ok(removed)
+}
+
+pub fn get_results(conn: &Connection, job_id: &str) -> Result<Vec<BatchResult>> {
+    let results = get_results_page(conn, job_id, 0, i64::MAX as usize)?;
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Up to `limit` stored results with index `since` or later, with their indexes
+pub fn get_results_page(
+    conn: &Connection,
+    job_id: &str,
+    since: usize,
+    limit: usize,
+) -> Result<Vec<(usize, BatchResult)>> {
+    let mut stmt = conn.prepare(
+        "SELECT idx, reference_id, name, kind_filter, hits, checked_at FROM batch_result
+         WHERE job_id = ?1 AND idx >= ?2 ORDER BY idx LIMIT ?3",
+    )?;
+    let rows = stmt
+        .query_map(rusqlite::params![job_id, since as i64, limit as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    rows.into_iter()
+        .map(|(idx, reference_id, name, kind_filter, hits, checked_at)| {
+            let result = BatchResult {
+                reference_id,
+                name,
+                kind_filter: SubjectKind::from_code(&kind_filter).unwrap_or(SubjectKind::Person),
+                hits: serde_json::from_str(&hits)?,
+                checked_at,
+            };
+            Ok((idx as usize, result))
+        })
+        .collect()
+}
+
+/// One line of an NDJSON results stream; `index` + 1 is the `since` cursor
+/// to resume after this line.
+#[derive(Serialize)]
+struct NdjsonLine<'a> {
+    index: usize,
+    #[serde(flatten)]
+    result: &'a BatchResult,
+}
+
+const NDJSON_PAGE_SIZE: usize = 500;
+
+/// Stream results from index `since` as newline-delimited JSON, following a
+/// running job until it stops processing. The DB lock is only held while a
+/// page is read, never while waiting for more records.
+pub fn ndjson_stream(
+    state: AppState,
+    tenant_id: String,
+    job_id: String,
+    since: usize,
+) -> impl futures::Stream<Item = Result<axum::body::Bytes, std::io::Error>> {
+    futures::stream::unfold(Some(since), move |cursor| {
+        let (state, tenant_id, job_id) = (state.clone(), tenant_id.clone(), job_id.clone());
+        async move {
+            let mut next = cursor?;
+            loop {
+                let (page, status) = {
+                    let db = state.tenant_db.lock().await;
+                    let page = get_results_page(&db, &job_id, next, NDJSON_PAGE_SIZE);
+                    let status = get_job(&db, &tenant_id, &job_id).map(|job| job.map(|j| j.status));
+                    (page, status)
+                };
+                let page = match page {
+                    Ok(page) => page,
+                    Err(e) => {
+                        tracing::error!(error = %e, job_id = %job_id, "failed to read batch results");
+                        return Some((Err(std::io::Error::other(e.to_string())), None));
+                    }
+                };
 
-        // Update progress
-        {
-            let mut jobs = state.batch_jobs.write().await;
-            if let Some(job) = jobs.get_mut(&job_id) {
-                job.processed_records = idx + 1;
+                if let Some((last, _)) = page.last() {
+                    next = last + 1;
+                    let mut chunk = Vec::new();
+                    for (index, result) in &page {
+                        if let Err(e) = serde_json::to_writer(&mut chunk, &NdjsonLine { index: *index, result }) {
+                            return Some((Err(std::io::Error::other(e)), None));
+                        }
+                        chunk.push(b'\n');
+                    }
+                    return Some((Ok(axum::body::Bytes::from(chunk)), Some(next)));
+                }
+
+                match status {
+                    Ok(Some(BatchStatus::Processing)) => {
+                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    }
+                    _ => return None,
+                }
             }
         }
+    })
+}
+
+/// Screen one record, returning its result and how long screening took
+async fn screen_record(
+    state: &AppState,
+    risk_config: &RiskConfig,
+    record: &BatchRecord,
+) -> Result<(BatchResult, Duration), (StatusCode, Json<ApiError>)> {
+    let kind = record.record_type.subject_kind();
+    // Entities have no date of birth; a date on an entity record is ignored
+    let dob = match kind {
+        SubjectKind::Person => record.date_of_birth.as_deref().and_then(BirthDate::parse),
+        SubjectKind::Entity | SubjectKind::Vessel | SubjectKind::Aircraft => None,
+    };
+
+    let start = Instant::now();
+    let query = ScreeningQuery {
+        name: record.name.clone(),
+        country: record.country.clone(),
+        dob,
+        kind: Some(kind),
+        identifiers: Vec::new(),
+    };
+    let hits = perform_screening(state, risk_config, record.reference_id.as_deref(), &record.name, query).await?;
+
+    let result = BatchResult {
+        reference_id: record.reference_id.clone(),
+        name: record.name.clone(),
+        kind_filter: kind,
+        hits,
+        checked_at: Utc::now().to_rfc3339(),
+    };
+    Ok((result, start.elapsed()))
+}
+
+/// Audit a stored record result and open its case alerts. Only called once
+/// the result is saved, so a chunk re-screened on resume is recorded once.
+async fn record_screening(
+    state: &AppState,
+    tenant_id: &str,
+    job_id: &str,
+    idx: usize,
+    record: &BatchRecord,
+    result: &BatchResult,
+    elapsed: Duration,
+) {
+    // One audit row per record, addressable as <job_id>:<index>
+    let request_id = format!("{}:{}", job_id, idx);
+    let entry = AuditEntry::screening(
+        tenant_id,
+        "batch",
+        &request_id,
+        record.reference_id.as_deref(),
+        &result.hits,
+        elapsed,
+    )
+    .with_payloads(record, result);
+    audit::record(state, entry).await;
+    cases::record(
+        state,
+        tenant_id,
+        &request_id,
+        record.reference_id.as_deref(),
+        &record.name,
+        &result.hits,
+    )
+    .await;
+}
+
+/// Screen `records[start..]` in chunks of `batch_concurrency` records run
+/// concurrently, persisting each chunk's results and then auditing them
+/// before starting the next.
+/// Stops after the current chunk once the job is no longer processing.
+/// A paced job takes each chunk's tokens before screening it. A record the
+/// matching engine fails on fails the job, keeping earlier chunks' results.
+pub async fn process_batch(
+    state: AppState,
+    tenant_id: String,
+    risk_config: RiskConfig,
+    job_id: String,
+    records: Vec<BatchRecord>,
+    start: usize,
+    paced_api_key_id: Option<String>,
+) {
+    let chunk_size = state.config.batch_concurrency.max(1);
+
+    for chunk_start in (start..records.len()).step_by(chunk_size) {
+        let chunk_end = (chunk_start + chunk_size).min(records.len());
+        if let Some(api_key_id) = &paced_api_key_id {
+            let cost = (chunk_end - chunk_start) as u32;
+            if !rate_limit::charge_paced(&state, &tenant_id, api_key_id, "POST /v1/batch", cost).await {
+                tracing::warn!(job_id = %job_id, tenant_id = %tenant_id, "tenant no longer active, batch job failed");
+                let db = state.tenant_db.lock().await;
+                if let Err(e) = set_status(&db, &job_id, BatchStatus::Failed) {
+                    tracing::error!(error = %e, job_id = %job_id, "failed to mark batch job as failed");
+                }
+                return;
+            }
+        }
+        let chunk = &records[chunk_start..chunk_end];
+        let screened = futures::future::join_all(chunk.iter().map(|record| screen_record(&state, &risk_config, record))).await;
+
+        let db = state.tenant_db.lock().await;
+        let Ok((results, elapsed)): Result<(Vec<_>, Vec<_>), _> = screened.into_iter().collect() else {
+            tracing::error!(job_id = %job_id, "batch record screening failed");
+            if let Err(e) = set_status(&db, &job_id, BatchStatus::Failed) {
+                tracing::error!(error = %e, job_id = %job_id, "failed to mark batch job as failed");
+            }
+            return;
+        };
+        if let Err(e) = save_results(&db, &job_id, chunk_start, &results) {
+            tracing::error!(error = %e, job_id = %job_id, "failed to store batch results");
+            if let Err(e) = set_status(&db, &job_id, BatchStatus::Failed) {
+                tracing::error!(error = %e, job_id = %job_id, "failed to mark batch job as failed");
+            }
+            return;
+        }
+        let stopped = match get_job(&db, &tenant_id, &job_id) {
+            Ok(Some(job)) => job.status != BatchStatus::Processing,
+            Ok(None) => true,
+            Err(e) => {
+                tracing::warn!(error = %e, job_id = %job_id, "failed to check batch job status");
+                false
+            }
+        };
+        drop(db);
+
+        for (offset, (record, result)) in chunk.iter().zip(&results).enumerate() {
+            record_screening(&state, &tenant_id, &job_id, chunk_start + offset, record, result, elapsed[offset]).await;
+        }
+        if stopped {
+            tracing::info!(job_id = %job_id, processed = chunk_end, "batch job stopped");
+            return;
+        }
+    }
+
+    let db = state.tenant_db.lock().await;
+    match set_status(&db, &job_id, BatchStatus::Completed) {
+        Ok(true) => tracing::info!(job_id = %job_id, tenant_id = %tenant_id, records = records.len(), "batch job completed"),
+        Ok(false) => {}
+        Err(e) => tracing::error!(error = %e, job_id = %job_id, "failed to mark batch job as completed"),
+    }
+}
+
+/// Periodically delete finished jobs older than `batch_retention_days`
+pub async fn retention_loop(state: AppState) {
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        let db = state.tenant_db.lock().await;
+        match purge_expired(&db, state.config.batch_retention_days) {
+            Ok(0) => {}
+            Ok(removed) => tracing::info!(removed, "purged expired batch jobs"),
+            Err(e) => tracing::warn!(error = %e, "failed to purge expired batch jobs"),
+        }
     }
+}
 
-    // Mark as completed
-    {
-        let mut jobs = state.batch_jobs.write().await;
-        if let Some(job) = jobs.get_mut(&job_id) {
-            job.status = BatchStatus::Completed;
-            job.results = results;
+/// Restart jobs that were still processing when the service stopped, each
+/// from its last stored record.
+pub async fn resume_unfinished(state: &AppState) {
+    let jobs = {
+        let db = state.tenant_db.lock().await;
+        match unfinished_jobs(&db) {
+            Ok(jobs) => jobs
+                .into_iter()
+                .filter_map(|job| match get_records(&db, &job.id) {
+                    Ok(records) => Some((job, records)),
+                    Err(e) => {
+                        tracing::error!(error = %e, job_id = %job.id, "failed to load batch records");
+                        set_status(&db, &job.id, BatchStatus::Failed).ok();
+                        None
+                    }
+                })
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                tracing::error!(error = %e, "failed to list unfinished batch jobs");
+                return;
+            }
         }
+    };
+
+    for (job, records) in jobs {
+        let risk_config = match state.risk_store.get_config(&job.tenant_id).await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!(error = %e, job_id = %job.id, "failed to load risk config, batch job not resumed");
+                continue;
+            }
+        };
+        tracing::info!(
+            job_id = %job.id,
+            processed = job.processed_records,
+            total = job.total_records,
+            "resuming batch job"
+        );
+        let state = state.clone();
+        tokio::spawn(async move {
+            process_batch(
+                state,
+                job.tenant_id,
+                risk_config,
+                job.id,
+                records,
+                job.processed_records,
+                job.paced_api_key_id,
+            )
+            .await;
+        });
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str) -> BatchRecord {
+        BatchRecord {
+            reference_id: Some(format!("ref-{}", name)),
+            name: name.to_string(),
+            country: None,
+            date_of_birth: None,
+            record_type: RecordType::Person,
+        }
+    }
+
+    fn result(name: &str) -> BatchResult {
+        BatchResult {
+            reference_id: Some(format!("ref-{}", name)),
+            name: name.to_string(),
+            kind_filter: SubjectKind::Person,
+            hits: Vec::new(),
+            checked_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn progress_survives_reopen() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let records = [record("a"), record("b"), record("c")];
+        create_job(&conn, "t1", "job-1", &records, None).unwrap();
+
+        save_results(&conn, "job-1", 0, &[result("a"), result("b")]).unwrap();
+        let unfinished = unfinished_jobs(&conn).unwrap();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].processed_records, 2);
+        assert_eq!(get_records(&conn, "job-1").unwrap()[2].name, "c");
+
+        // A chunk screened again after a restart overwrites rather than duplicates
+        save_results(&conn, "job-1", 1, &[result("b"), result("c")]).unwrap();
+        set_status(&conn, "job-1", BatchStatus::Completed).unwrap();
+        assert!(unfinished_jobs(&conn).unwrap().is_empty());
+
+        let job = get_job(&conn, "t1", "job-1").unwrap().unwrap();
+        assert_eq!(job.status, BatchStatus::Completed);
+        assert!(get_job(&conn, "t2", "job-1").unwrap().is_none());
+        assert_eq!(job.processed_records, 3);
+        let names: Vec<_> = get_results(&conn, "job-1").unwrap().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn cancelled_jobs_stay_cancelled_and_expire() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        create_job(&conn, "t1", "job-1", &[record("a")], None).unwrap();
+        create_job(&conn, "t1", "job-2", &[record("b")], None).unwrap();
+        save_results(&conn, "job-1", 0, &[result("a")]).unwrap();
+
+        assert!(set_status(&conn, "job-1", BatchStatus::Cancelled).unwrap());
+        // The worker finishing afterwards does not undo the cancellation
+        assert!(!set_status(&conn, "job-1", BatchStatus::Completed).unwrap());
+        assert_eq!(get_job(&conn, "t1", "job-1").unwrap().unwrap().status, BatchStatus::Cancelled);
+
+        conn.execute("UPDATE batch_job SET updated_at = datetime('now', '-31 days')", []).unwrap();
+        assert_eq!(purge_expired(&conn, 30).unwrap(), 1);
+        assert!(get_job(&conn, "t1", "job-1").unwrap().is_none());
+        assert!(get_results(&conn, "job-1").unwrap().is_empty());
+        // Jobs still processing are never purged
+        assert_eq!(list_jobs(&conn, "t1", 10, 0).unwrap()[0].id, "job-2");
+    }
+}
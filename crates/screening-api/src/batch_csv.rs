@@ -0,0 +1,432 @@
+use aegistry_core::{HitSource, RiskLevel, SubjectKind};
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Multipart, Request},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use calamine::{Data, DataType, Reader};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::batch::{BatchRecord, BatchRequest, BatchResult, RecordType};
+use crate::ApiError;
+
+pub const CSV_CONTENT_TYPE: &str = "text/csv";
+
+/// Request body limit for batch submissions; axum's 2 MB default only fits a
+/// few tens of thousands of CSV rows, and workbooks are larger still
+pub const BATCH_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Which CSV header holds each record field. Headers are matched
+/// case-insensitively; only the name column is required.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ColumnMapping {
+    pub name_column: String,
+    pub country_column: String,
+    pub dob_column: String,
+    pub reference_id_column: String,
+    pub record_type_column: String,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            name_column: "name".to_string(),
+            country_column: "country".to_string(),
+            dob_column: "date_of_birth".to_string(),
+            reference_id_column: "reference_id".to_string(),
+            record_type_column: "record_type".to_string(),
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Apply a multipart form field named like one of the query parameters
+    fn set(&mut self, field: &str, value: String) -> bool {
+        let target = match field {
+            "name_column" => &mut self.name_column,
+            "country_column" => &mut self.country_column,
+            "dob_column" => &mut self.dob_column,
+            "reference_id_column" => &mut self.reference_id_column,
+            "record_type_column" => &mut self.record_type_column,
+            _ => return false,
+        };
+        *target = value;
+        true
+    }
+}
+
+/// Read batch records from a JSON body, a CSV or spreadsheet body, or a
+/// `multipart/form-data` upload whose `file` field holds either. Multipart
+/// text fields named like the `ColumnMapping` query parameters override them.
+pub async fn read_records(
+    request: Request,
+    mut mapping: ColumnMapping,
+) -> Result<Vec<BatchRecord>, (StatusCode, Json<ApiError>)> {
+    match read_upload::<BatchRequest>(request, |field, value| mapping.set(field, value)).await? {
+        Upload::Json(req) => Ok(req.records),
+        Upload::Csv(data) => parse_csv(&data, &mapping),
+    }
+}
+
+/// An upload body before its CSV is parsed. Spreadsheets arrive here
+/// already converted to CSV.
+pub enum Upload<T> {
+    Json(T),
+    Csv(Bytes),
+}
+
+/// Read a JSON body, a `text/csv` or Excel body, or a `multipart/form-data`
+/// upload whose `file` field holds a CSV or workbook. Other multipart text
+/// fields go to `on_field`, which returns whether it recognised the field.
+pub async fn read_upload<T: DeserializeOwned>(
+    request: Request,
+    mut on_field: impl FnMut(&str, String) -> bool,
+) -> Result<Upload<T>, (StatusCode, Json<ApiError>)> {
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    if content_type.starts_with(CSV_CONTENT_TYPE) {
+        let body = Bytes::from_request(request, &())
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?;
+        Ok(Upload::Csv(body))
+    } else if is_spreadsheet(None, Some(&content_type), &[]) {
+        let body = Bytes::from_request(request, &())
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?;
+        Ok(Upload::Csv(spreadsheet_to_csv(&body)?))
+    } else if content_type.starts_with("multipart/form-data") {
+        let mut multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?;
+        let mut file = None;
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?
+        {
+            let name = field.name().unwrap_or_default().to_string();
+            if name == "file" {
+                let file_name = field.file_name().map(str::to_string);
+                let file_type = field.content_type().map(str::to_string);
+                let data = field.bytes().await.map_err(|e| rejection(e.status(), e.body_text()))?;
+                file = Some(if is_spreadsheet(file_name.as_deref(), file_type.as_deref(), &data) {
+                    spreadsheet_to_csv(&data)?
+                } else {
+                    data
+                });
+            } else {
+                let value = field.text().await.map_err(|e| rejection(e.status(), e.body_text()))?;
+                if !on_field(&name, value) {
+                    tracing::debug!(field = %name, "ignoring unknown multipart field");
+                }
+            }
+        }
+        let file = file.ok_or_else(|| {
+            upload_error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_request",
+                "file: multipart upload has no 'file' field".to_string(),
+            )
+        })?;
+        Ok(Upload::Csv(file))
+    } else {
+        let Json(req) = Json::<T>::from_request(request, &())
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?;
+        Ok(Upload::Json(req))
+    }
+}
+
+/// Whether an upload is a workbook: by its zip (xlsx) or OLE (xls) magic
+/// bytes, else by file extension. Windows browsers send .csv files as
+/// `application/vnd.ms-excel`, so the content type only counts when there
+/// is no file name.
+fn is_spreadsheet(file_name: Option<&str>, content_type: Option<&str>, data: &[u8]) -> bool {
+    if data.starts_with(b"PK\x03\x04") || data.starts_with(&[0xD0, 0xCF, 0x11, 0xE0]) {
+        return true;
+    }
+    match file_name.filter(|name| !name.is_empty()) {
+        Some(name) => {
+            let name = name.to_ascii_lowercase();
+            name.ends_with(".xlsx") || name.ends_with(".xls")
+        }
+        None => content_type.is_some_and(|ct| ct.contains("spreadsheetml") || ct.contains("ms-excel")),
+    }
+}
+
+/// Convert the first worksheet of a workbook to CSV, so spreadsheets go
+/// through the same column mapping and row checks. Dates are written as
+/// `YYYY-MM-DD` and whole numbers without a decimal point, the way they
+/// would be typed into a CSV.
+fn spreadsheet_to_csv(data: &[u8]) -> Result<Bytes, (StatusCode, Json<ApiError>)> {
+    let invalid = |detail: String| upload_error(StatusCode::UNPROCESSABLE_ENTITY, "invalid_spreadsheet", detail);
+
+    let mut workbook = calamine::open_workbook_auto_from_rs(std::io::Cursor::new(data))
+        .map_err(|e| invalid(format!("file: {}", e)))?;
+    let sheet = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| invalid("file: workbook has no worksheets".to_string()))?
+        .map_err(|e| invalid(format!("file: {}", e)))?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in sheet.rows() {
+        writer
+            .write_record(row.iter().map(cell_text))
+            .map_err(|e| invalid(format!("file: {}", e)))?;
+    }
+    let csv = writer.into_inner().map_err(|e| invalid(format!("file: {}", e.error())))?;
+    Ok(Bytes::from(csv))
+}
+
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::Float(f) if f.fract() == 0.0 => format!("{}", *f as i64),
+        Data::DateTime(_) | Data::DateTimeIso(_) => cell.as_date().map_or_else(|| cell.to_string(), |d| d.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Parse CSV records; rows are numbered from 1 after the header in errors
+pub fn parse_csv(data: &[u8], mapping: &ColumnMapping) -> Result<Vec<BatchRecord>, (StatusCode, Json<ApiError>)> {
+    let invalid = |detail: String| upload_error(StatusCode::UNPROCESSABLE_ENTITY, "invalid_csv", detail);
+
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(data);
+    let headers = reader.headers().map_err(|e| invalid(format!("header: {}", e)))?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name.trim()));
+
+    let name_idx = column(&mapping.name_column)
+        .ok_or_else(|| invalid(format!("name_column: no '{}' column in header", mapping.name_column)))?;
+    let country_idx = column(&mapping.country_column);
+    let dob_idx = column(&mapping.dob_column);
+    let reference_idx = column(&mapping.reference_id_column);
+    let record_type_idx = column(&mapping.record_type_column);
+
+    let mut records = Vec::new();
+    for (i, row) in reader.records().enumerate() {
+        let row_number = i + 1;
+        let row = row.map_err(|e| invalid(format!("row {}: {}", row_number, e)))?;
+        let field = |idx: Option<usize>| {
+            idx.and_then(|idx| row.get(idx))
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        let name = field(Some(name_idx)).ok_or_else(|| invalid(format!("row {}: name is empty", row_number)))?;
+        let record_type = match field(record_type_idx).map(|v| v.to_ascii_lowercase()).as_deref() {
+            None | Some("person") => RecordType::Person,
+            Some("entity") => RecordType::Entity,
+            Some(other) => {
+                return Err(invalid(format!(
+                    "row {}: record_type '{}' is not 'person' or 'entity'",
+                    row_number, other
+                )))
+            }
+        };
+
+        records.push(BatchRecord {
+            reference_id: field(reference_idx),
+            name,
+            country: field(country_idx),
+            date_of_birth: field(dob_idx),
+            record_type,
+        });
+    }
+    Ok(records)
+}
+
+/// One row per hit. Records without hits get a single row with empty hit
+/// columns so every input record appears in the export.
+#[derive(Serialize)]
+struct HitRow<'a> {
+    reference_id: Option<&'a str>,
+    name: &'a str,
+    kind_filter: SubjectKind,
+    subject_id: Option<&'a str>,
+    matched_name: Option<&'a str>,
+    source: Option<HitSource>,
+    kind: Option<SubjectKind>,
+    score: Option<f32>,
+    risk_level: Option<RiskLevel>,
+    suppressed: Option<bool>,
+    checked_at: &'a str,
+}
+
+pub fn results_to_csv(results: &[BatchResult]) -> anyhow::Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for result in results {
+        let empty = HitRow {
+            reference_id: result.reference_id.as_deref(),
+            name: &result.name,
+            kind_filter: result.kind_filter,
+            subject_id: None,
+            matched_name: None,
+            source: None,
+            kind: None,
+            score: None,
+            risk_level: None,
+            suppressed: None,
+            checked_at: &result.checked_at,
+        };
+        if result.hits.is_empty() {
+            writer.serialize(empty)?;
+            continue;
+        }
+        for hit in &result.hits {
+            writer.serialize(HitRow {
+                subject_id: Some(&hit.subject_id),
+                matched_name: Some(&hit.matched_name),
+                source: Some(hit.source),
+                kind: Some(hit.kind),
+                score: Some(hit.score),
+                risk_level: Some(hit.risk_level),
+                suppressed: Some(hit.suppressed),
+                ..empty
+            })?;
+        }
+    }
+    Ok(writer.into_inner()?)
+}
+
+pub fn csv_response(results: &[BatchResult]) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    let body = results_to_csv(results).map_err(|e| {
+        tracing::error!(error = %e, "failed to write batch results as CSV");
+        upload_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "batch_error",
+            format!("Failed to write CSV: {}", e),
+        )
+    })?;
+    Ok(([(header::CONTENT_TYPE, CSV_CONTENT_TYPE)], body).into_response())
+}
+
+fn rejection(status: StatusCode, body_text: String) -> (StatusCode, Json<ApiError>) {
+    upload_error(status, "invalid_request", body_text)
+}
+
+pub fn upload_error(status: StatusCode, message: &str, detail: String) -> (StatusCode, Json<ApiError>) {
+    (
+        status,
+        Json(ApiError {
+            message: message.to_string(),
+            details: vec![detail],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aegistry_core::{Hit, ScoreComponents};
+
+    #[test]
+    fn csv_columns_follow_mapping() {
+        let data = "Customer No,Full Name,Nationality,Type\n\
+                    C-1,Maria Garcia,ES,\n\
+                    C-2, Acme Trading Ltd ,,Entity\n";
+        let mapping = ColumnMapping {
+            name_column: "full name".to_string(),
+            country_column: "Nationality".to_string(),
+            reference_id_column: "Customer No".to_string(),
+            record_type_column: "Type".to_string(),
+            ..ColumnMapping::default()
+        };
+
+        let records = parse_csv(data.as_bytes(), &mapping).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].reference_id.as_deref(), Some("C-1"));
+        assert_eq!(records[0].country.as_deref(), Some("ES"));
+        assert!(matches!(records[0].record_type, RecordType::Person));
+        assert_eq!(records[1].name, "Acme Trading Ltd");
+        assert_eq!(records[1].country, None);
+        assert!(matches!(records[1].record_type, RecordType::Entity));
+        assert_eq!(records[1].date_of_birth, None);
+    }
+
+    #[test]
+    fn spreadsheet_cells_read_like_csv() {
+        let csv = spreadsheet_to_csv(include_bytes!("../testdata/batch.xlsx")).unwrap();
+        let records = parse_csv(&csv, &ColumnMapping::default()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].date_of_birth.as_deref(), Some("1970-04-28"));
+        assert_eq!(records[0].country.as_deref(), Some("ES"));
+        // A numeric customer number keeps no trailing ".0"
+        assert_eq!(records[1].reference_id.as_deref(), Some("12345"));
+        assert!(matches!(records[1].record_type, RecordType::Entity));
+
+        assert!(spreadsheet_to_csv(b"PK").is_err());
+    }
+
+    #[test]
+    fn csv_errors_name_the_row() {
+        let err = parse_csv(
+            b"name,record_type\nMaria Garcia,person\nAcme,vessel\n",
+            &ColumnMapping::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.0, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(err.1.details[0].starts_with("row 2:"));
+
+        let err = parse_csv(b"customer\nMaria Garcia\n", &ColumnMapping::default()).unwrap_err();
+        assert!(err.1.details[0].contains("'name'"));
+    }
+
+    #[test]
+    fn results_export_one_row_per_hit() {
+        let hit = |subject_id: &str| Hit {
+            subject_id: subject_id.to_string(),
+            matched_name: "Maria Garcia".to_string(),
+            source: HitSource::Ofac,
+            kind: SubjectKind::Person,
+            score: 0.97,
+            risk_level: RiskLevel::Hit,
+            components: ScoreComponents {
+                name_similarity: 0.97,
+                dob_similarity: 0.0,
+                country_match: 0.0,
+                phonetic_match: true,
+                identifier_match: 0.0,
+            },
+            explanation: vec![],
+            matched_alias: None,
+            record_version: None,
+            suppressed: false,
+            suppression_reason: None,
+        };
+        let results = [
+            BatchResult {
+                reference_id: Some("C-1".to_string()),
+                name: "Maria Garcia".to_string(),
+                kind_filter: SubjectKind::Person,
+                hits: vec![hit("s1"), hit("s2")],
+                checked_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+            BatchResult {
+                reference_id: None,
+                name: "John Smith".to_string(),
+                kind_filter: SubjectKind::Person,
+                hits: vec![],
+                checked_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+        ];
+
+        let csv = String::from_utf8(results_to_csv(&results).unwrap()).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "reference_id,name,kind_filter,subject_id,matched_name,source,kind,score,risk_level,suppressed,checked_at"
+        );
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("C-1,Maria Garcia,Person,s1,Maria Garcia,Ofac,Person,0.97,Hit,false,"));
+        assert!(lines[2].starts_with("C-1,Maria Garcia,Person,s2,"));
+        assert_eq!(lines[3], ",John Smith,Person,,,,,,,,2026-01-01T00:00:00Z");
+    }
+}
@@ -0,0 +1,794 @@
+use aegistry_core::{Hit, RiskLevel};
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::auth::ApiKeyAuth;
+use crate::suppression::{self, NewSuppression};
+use crate::{ApiError, AppState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseDecision {
+    TrueMatch,
+    FalsePositive,
+    Escalated,
+}
+
+impl CaseDecision {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CaseDecision::TrueMatch => "true_match",
+            CaseDecision::FalsePositive => "false_positive",
+            CaseDecision::Escalated => "escalated",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "true_match" => Some(CaseDecision::TrueMatch),
+            "false_positive" => Some(CaseDecision::FalsePositive),
+            "escalated" => Some(CaseDecision::Escalated),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Open,
+    /// A decision was made and waits for a second analyst (four-eyes tenants only)
+    PendingApproval,
+    TrueMatch,
+    FalsePositive,
+    Escalated,
+}
+
+impl AlertStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatus::Open => "open",
+            AlertStatus::PendingApproval => "pending_approval",
+            AlertStatus::TrueMatch => "true_match",
+            AlertStatus::FalsePositive => "false_positive",
+            AlertStatus::Escalated => "escalated",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "pending_approval" => AlertStatus::PendingApproval,
+            "true_match" => AlertStatus::TrueMatch,
+            "false_positive" => AlertStatus::FalsePositive,
+            "escalated" => AlertStatus::Escalated,
+            _ => AlertStatus::Open,
+        }
+    }
+
+    /// Escalated alerts stay workable until someone settles them
+    fn accepts_decision(&self) -> bool {
+        matches!(self, AlertStatus::Open | AlertStatus::Escalated)
+    }
+}
+
+impl From<CaseDecision> for AlertStatus {
+    fn from(decision: CaseDecision) -> Self {
+        match decision {
+            CaseDecision::TrueMatch => AlertStatus::TrueMatch,
+            CaseDecision::FalsePositive => AlertStatus::FalsePositive,
+            CaseDecision::Escalated => AlertStatus::Escalated,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Alert {
+    pub id: String,
+    pub tenant_id: String,
+    pub request_id: String,
+    /// The screened customer, used to suppress the hit once cleared
+    pub reference_id: Option<String>,
+    pub input_name: String,
+    pub subject_id: String,
+    /// `record_hash` of the subject when the alert was opened
+    pub record_version: Option<String>,
+    pub matched_name: String,
+    pub score: f32,
+    pub risk_level: String,
+    pub status: AlertStatus,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Decision {
+    pub id: i64,
+    pub decision: CaseDecision,
+    pub comment: Option<String>,
+    pub decided_by: String,
+    /// API key the decision was made with
+    pub decided_by_key: Option<String>,
+    pub decided_at: String,
+    /// `pending`, `approved` or `rejected` under four-eyes review, `final` otherwise
+    pub review_status: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_by_key: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CaseDetail {
+    pub alert: Alert,
+    pub decisions: Vec<Decision>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseSettings {
+    /// Decisions only take effect once approved with a different API key
+    pub four_eyes_required: bool,
+}
+
+/// Case tables live in the tenant DB; called from `tenant_db::init_tenant_schema`.
+pub fn init_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS case_alert (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            request_id TEXT NOT NULL,
+            subject_id TEXT NOT NULL,
+            matched_name TEXT NOT NULL,
+            score REAL NOT NULL,
+            risk_level TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'open',
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE(tenant_id, request_id, subject_id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_case_alert_status
+            ON case_alert(tenant_id, status, created_at DESC);
+
+        CREATE TABLE IF NOT EXISTS case_decision (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            alert_id TEXT NOT NULL REFERENCES case_alert(id),
+            decision TEXT NOT NULL,
+            comment TEXT,
+            decided_by TEXT NOT NULL,
+            decided_at TEXT NOT NULL DEFAULT (datetime('now')),
+            review_status TEXT NOT NULL,
+            reviewed_by TEXT,
+            reviewed_at TEXT,
+            review_comment TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_case_decision_alert ON case_decision(alert_id);
+
+        CREATE TABLE IF NOT EXISTS case_settings (
+            tenant_id TEXT PRIMARY KEY,
+            four_eyes_required INTEGER NOT NULL DEFAULT 0
+        );
+        "
+    )?;
+    ingest::db::add_column_if_missing(conn, "case_alert", "reference_id", "TEXT")?;
+    ingest::db::add_column_if_missing(conn, "case_alert", "input_name", "TEXT NOT NULL DEFAULT ''")?;
+    ingest::db::add_column_if_missing(conn, "case_alert", "record_version", "TEXT")?;
+    ingest::db::add_column_if_missing(conn, "case_decision", "decided_by_key", "TEXT")?;
+    ingest::db::add_column_if_missing(conn, "case_decision", "reviewed_by_key", "TEXT")?;
+    Ok(())
+}
+
+/// Open an alert for every hit at or above the tenant's review threshold,
+/// skipping suppressed hits. Returns the number of alerts opened.
+pub fn open_alerts(
+    conn: &Connection,
+    tenant_id: &str,
+    request_id: &str,
+    reference_id: Option<&str>,
+    input_name: &str,
+    hits: &[Hit],
+) -> Result<usize> {
+    let mut opened = 0;
+    for hit in hits.iter().filter(|h| !h.suppressed) {
+        let risk_level = match hit.risk_level {
+            RiskLevel::Hit => "hit",
+            RiskLevel::Review => "review",
+            RiskLevel::None => continue,
+        };
+        opened += conn.execute(
+            "INSERT OR IGNORE INTO case_alert (id, tenant_id, request_id, reference_id, input_name, subject_id,
+                                               record_version, matched_name, score, risk_level)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                uuid::Uuid::new_v4().to_string(),
+                tenant_id,
+                request_id,
+                reference_id,
+                input_name,
+                hit.subject_id,
+                hit.record_version,
+                hit.matched_name,
+                hit.score,
+                risk_level,
+            ],
+        )?;
+    }
+    Ok(opened)
+}
+
+/// `open_alerts` for use from request handlers; failures are logged so a
+/// case-store problem never loses the screening result itself.
+pub async fn record(
+    state: &AppState,
+    tenant_id: &str,
+    request_id: &str,
+    reference_id: Option<&str>,
+    input_name: &str,
+    hits: &[Hit],
+) {
+    let db = state.tenant_db.lock().await;
+    match open_alerts(&db, tenant_id, request_id, reference_id, input_name, hits) {
+        Ok(0) => {}
+        Ok(opened) => tracing::info!(tenant_id, request_id, opened, "opened case alerts"),
+        Err(e) => tracing::error!(error = %e, tenant_id, request_id, "failed to open case alerts"),
+    }
+}
+
+const ALERT_COLUMNS: &str = "id, tenant_id, request_id, reference_id, input_name, subject_id, record_version, \
+                             matched_name, score, risk_level, status, created_at, updated_at";
+
+fn alert_from_row(row: &rusqlite::Row) -> rusqlite::Result<Alert> {
+    Ok(Alert {
+        id: row.get(0)?,
+        tenant_id: row.get(1)?,
+        request_id: row.get(2)?,
+        reference_id: row.get(3)?,
+        input_name: row.get(4)?,
+        subject_id: row.get(5)?,
+        record_version: row.get(6)?,
+        matched_name: row.get(7)?,
+        score: row.get(8)?,
+        risk_level: row.get(9)?,
+        status: AlertStatus::parse(&row.get::<_, String>(10)?),
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
+pub fn list_alerts(
+    conn: &Connection,
+    tenant_id: &str,
+    status: Option<AlertStatus>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Alert>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM case_alert
+         WHERE tenant_id = ?1 AND (?2 IS NULL OR status = ?2)
+         ORDER BY created_at DESC, rowid DESC LIMIT ?3 OFFSET ?4",
+        ALERT_COLUMNS
+    ))?;
+    let alerts = stmt
+        .query_map(
+            rusqlite::params![tenant_id, status.map(|s| s.as_str()), limit, offset],
+            alert_from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(alerts)
+}
+
+pub fn get_alert(conn: &Connection, tenant_id: &str, alert_id: &str) -> Result<Option<Alert>> {
+    let alert = conn
+        .query_row(
+            &format!("SELECT {} FROM case_alert WHERE tenant_id = ?1 AND id = ?2", ALERT_COLUMNS),
+            [tenant_id, alert_id],
+            alert_from_row,
+        )
+        .optional()?;
+    Ok(alert)
+}
+
+pub fn get_decisions(conn: &Connection, alert_id: &str) -> Result<Vec<Decision>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, decision, comment, decided_by, decided_by_key, decided_at, review_status,
+                reviewed_by, reviewed_by_key, reviewed_at, review_comment
+         FROM case_decision WHERE alert_id = ?1 ORDER BY id",
+    )?;
+    let decisions = stmt
+        .query_map([alert_id], |row| {
+            let decision: String = row.get(1)?;
+            Ok(Decision {
+                id: row.get(0)?,
+                decision: CaseDecision::parse(&decision).unwrap_or(CaseDecision::Escalated),
+                comment: row.get(2)?,
+                decided_by: row.get(3)?,
+                decided_by_key: row.get(4)?,
+                decided_at: row.get(5)?,
+                review_status: row.get(6)?,
+                reviewed_by: row.get(7)?,
+                reviewed_by_key: row.get(8)?,
+                reviewed_at: row.get(9)?,
+                review_comment: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(decisions)
+}
+
+pub fn get_settings(conn: &Connection, tenant_id: &str) -> Result<CaseSettings> {
+    let four_eyes_required = conn
+        .query_row(
+            "SELECT four_eyes_required FROM case_settings WHERE tenant_id = ?1",
+            [tenant_id],
+            |row| row.get::<_, i32>(0),
+        )
+        .optional()?
+        .unwrap_or(0)
+        == 1;
+    Ok(CaseSettings { four_eyes_required })
+}
+
+pub fn set_settings(conn: &Connection, tenant_id: &str, settings: &CaseSettings) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO case_settings (tenant_id, four_eyes_required) VALUES (?1, ?2)",
+        rusqlite::params![tenant_id, settings.four_eyes_required as i32],
+    )?;
+    Ok(())
+}
+
+/// Why a decision or review could not be applied
+#[derive(Debug, PartialEq)]
+pub enum CaseError {
+    NotFound,
+    /// The alert is not in a state that allows the action
+    InvalidState(AlertStatus),
+    /// Four-eyes review requires a different API key, and analyst, than the decision
+    SameReviewer,
+}
+
+/// Who acts on a case. The name is whatever the caller supplies, so
+/// four-eyes review is enforced on the API key the request authenticated
+/// with; each analyst needs a key of their own.
+#[derive(Debug, Clone, Copy)]
+pub struct Analyst<'a> {
+    pub name: &'a str,
+    pub api_key_id: &'a str,
+}
+
+/// Record an analyst's decision. Without four-eyes review it takes effect at
+/// once; otherwise the alert waits in `pending_approval`.
+pub fn decide(
+    conn: &Connection,
+    tenant_id: &str,
+    alert_id: &str,
+    decision: CaseDecision,
+    comment: Option<&str>,
+    analyst: Analyst,
+) -> Result<std::result::Result<AlertStatus, CaseError>> {
+    let Some(alert) = get_alert(conn, tenant_id, alert_id)? else {
+        return Ok(Err(CaseError::NotFound));
+    };
+    if !alert.status.accepts_decision() {
+        return Ok(Err(CaseError::InvalidState(alert.status)));
+    }
+
+    let four_eyes = get_settings(conn, tenant_id)?.four_eyes_required;
+    let (new_status, review_status) = if four_eyes {
+        (AlertStatus::PendingApproval, "pending")
+    } else {
+        (AlertStatus::from(decision), "final")
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO case_decision (alert_id, decision, comment, decided_by, decided_by_key, decided_at, review_status)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            alert_id,
+            decision.as_str(),
+            comment,
+            analyst.name,
+            analyst.api_key_id,
+            Utc::now().to_rfc3339(),
+            review_status,
+        ],
+    )?;
+    tx.execute(
+        "UPDATE case_alert SET status = ?1, updated_at = datetime('now') WHERE id = ?2",
+        rusqlite::params![new_status.as_str(), alert_id],
+    )?;
+    if new_status == AlertStatus::FalsePositive {
+        suppress(&tx, &alert, comment, analyst.name)?;
+    }
+    tx.commit()?;
+
+    Ok(Ok(new_status))
+}
+
+/// Second-analyst review of a pending decision. Approval applies the decision;
+/// rejection reopens the alert.
+pub fn review(
+    conn: &Connection,
+    tenant_id: &str,
+    alert_id: &str,
+    approve: bool,
+    comment: Option<&str>,
+    reviewer: Analyst,
+) -> Result<std::result::Result<AlertStatus, CaseError>> {
+    let Some(alert) = get_alert(conn, tenant_id, alert_id)? else {
+        return Ok(Err(CaseError::NotFound));
+    };
+    if alert.status != AlertStatus::PendingApproval {
+        return Ok(Err(CaseError::InvalidState(alert.status)));
+    }
+
+    let (decision_id, decision, decided_by, decided_by_key): (i64, String, String, Option<String>) = conn.query_row(
+        "SELECT id, decision, decided_by, decided_by_key FROM case_decision
+         WHERE alert_id = ?1 AND review_status = 'pending' ORDER BY id DESC LIMIT 1",
+        [alert_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )?;
+    if decided_by_key.as_deref() == Some(reviewer.api_key_id) || decided_by == reviewer.name {
+        return Ok(Err(CaseError::SameReviewer));
+    }
+
+    let new_status = match (approve, CaseDecision::parse(&decision)) {
+        (true, Some(decision)) => AlertStatus::from(decision),
+        _ => AlertStatus::Open,
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE case_decision SET review_status = ?1, reviewed_by = ?2, reviewed_by_key = ?3, reviewed_at = ?4,
+                                  review_comment = ?5
+         WHERE id = ?6",
+        rusqlite::params![
+            if approve { "approved" } else { "rejected" },
+            reviewer.name,
+            reviewer.api_key_id,
+            Utc::now().to_rfc3339(),
+            comment,
+            decision_id,
+        ],
+    )?;
+    tx.execute(
+        "UPDATE case_alert SET status = ?1, updated_at = datetime('now') WHERE id = ?2",
+        rusqlite::params![new_status.as_str(), alert_id],
+    )?;
+    if new_status == AlertStatus::FalsePositive {
+        suppress(&tx, &alert, comment, &decided_by)?;
+    }
+    tx.commit()?;
+
+    Ok(Ok(new_status))
+}
+
+/// A settled false positive stops the same customer from hitting the same
+/// subject record again.
+fn suppress(conn: &Connection, alert: &Alert, comment: Option<&str>, analyst: &str) -> Result<()> {
+    let reason = match comment {
+        Some(comment) => comment.to_string(),
+        None => format!("cleared as false positive by {}", analyst),
+    };
+    suppression::create(
+        conn,
+        &NewSuppression {
+            tenant_id: &alert.tenant_id,
+            reference_id: alert.reference_id.as_deref(),
+            input_name: &alert.input_name,
+            subject_id: &alert.subject_id,
+            record_version: alert.record_version.as_deref(),
+            reason: &reason,
+            alert_id: Some(&alert.id),
+            created_by: analyst,
+        },
+    )?;
+    Ok(())
+}
+
+// HTTP handlers
+
+#[derive(Debug, Deserialize)]
+pub struct CaseQueryParams {
+    pub status: Option<AlertStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DecisionRequest {
+    pub decision: CaseDecision,
+    pub comment: Option<String>,
+    /// The deciding analyst, recorded alongside the API key used
+    #[validate(length(min = 1))]
+    pub analyst: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReviewRequest {
+    pub approve: bool,
+    pub comment: Option<String>,
+    /// The reviewing analyst; under four-eyes review they must use a
+    /// different API key than the decision was made with
+    #[validate(length(min = 1))]
+    pub reviewer: String,
+}
+
+pub async fn list_cases(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(params): Query<CaseQueryParams>,
+) -> Result<Json<Vec<Alert>>, (StatusCode, Json<ApiError>)> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let db = state.tenant_db.lock().await;
+    list_alerts(&db, &auth.tenant_id, params.status, limit, offset)
+        .map(Json)
+        .map_err(case_store_error)
+}
+
+pub async fn get_case(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(alert_id): Path<String>,
+) -> Result<Json<CaseDetail>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    let alert = get_alert(&db, &auth.tenant_id, &alert_id)
+        .map_err(case_store_error)?
+        .ok_or_else(|| case_error(&alert_id, CaseError::NotFound))?;
+    let decisions = get_decisions(&db, &alert_id).map_err(case_store_error)?;
+    Ok(Json(CaseDetail { alert, decisions }))
+}
+
+pub async fn decide_case(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(alert_id): Path<String>,
+    Json(req): Json<DecisionRequest>,
+) -> Result<Json<CaseDetail>, (StatusCode, Json<ApiError>)> {
+    if let Err(e) = req.validate() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(ApiError::from_validation(e))));
+    }
+
+    let db = state.tenant_db.lock().await;
+    let analyst = Analyst {
+        name: &req.analyst,
+        api_key_id: &auth.api_key_id,
+    };
+    let status = decide(&db, &auth.tenant_id, &alert_id, req.decision, req.comment.as_deref(), analyst)
+        .map_err(case_store_error)?
+        .map_err(|e| case_error(&alert_id, e))?;
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        alert_id = %alert_id,
+        decision = req.decision.as_str(),
+        analyst = %req.analyst,
+        api_key_id = %auth.api_key_id,
+        status = status.as_str(),
+        "case decision recorded"
+    );
+
+    let alert = get_alert(&db, &auth.tenant_id, &alert_id).map_err(case_store_error)?;
+    let decisions = get_decisions(&db, &alert_id).map_err(case_store_error)?;
+    Ok(Json(CaseDetail {
+        alert: alert.ok_or_else(|| case_error(&alert_id, CaseError::NotFound))?,
+        decisions,
+    }))
+}
+
+pub async fn review_case(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(alert_id): Path<String>,
+    Json(req): Json<ReviewRequest>,
+) -> Result<Json<CaseDetail>, (StatusCode, Json<ApiError>)> {
+    if let Err(e) = req.validate() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(ApiError::from_validation(e))));
+    }
+
+    let db = state.tenant_db.lock().await;
+    let reviewer = Analyst {
+        name: &req.reviewer,
+        api_key_id: &auth.api_key_id,
+    };
+    let status = review(&db, &auth.tenant_id, &alert_id, req.approve, req.comment.as_deref(), reviewer)
+        .map_err(case_store_error)?
+        .map_err(|e| case_error(&alert_id, e))?;
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        alert_id = %alert_id,
+        approve = req.approve,
+        reviewer = %req.reviewer,
+        api_key_id = %auth.api_key_id,
+        status = status.as_str(),
+        "case decision reviewed"
+    );
+
+    let alert = get_alert(&db, &auth.tenant_id, &alert_id).map_err(case_store_error)?;
+    let decisions = get_decisions(&db, &alert_id).map_err(case_store_error)?;
+    Ok(Json(CaseDetail {
+        alert: alert.ok_or_else(|| case_error(&alert_id, CaseError::NotFound))?,
+        decisions,
+    }))
+}
+
+pub async fn get_case_settings(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+) -> Result<Json<CaseSettings>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    get_settings(&db, &auth.tenant_id).map(Json).map_err(case_store_error)
+}
+
+pub async fn put_case_settings(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Json(settings): Json<CaseSettings>,
+) -> Result<Json<CaseSettings>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    set_settings(&db, &auth.tenant_id, &settings).map_err(case_store_error)?;
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        four_eyes_required = settings.four_eyes_required,
+        "updated case settings"
+    );
+    Ok(Json(settings))
+}
+
+fn case_error(alert_id: &str, e: CaseError) -> (StatusCode, Json<ApiError>) {
+    let (status, message, detail) = match e {
+        CaseError::NotFound => (
+            StatusCode::NOT_FOUND,
+            "not_found",
+            format!("Case {} not found", alert_id),
+        ),
+        CaseError::InvalidState(current) => (
+            StatusCode::CONFLICT,
+            "invalid_case_state",
+            format!("Case {} is {}", alert_id, current.as_str()),
+        ),
+        CaseError::SameReviewer => (
+            StatusCode::FORBIDDEN,
+            "four_eyes_violation",
+            "The reviewer must be a different analyst, with a different API key, than the one who decided".to_string(),
+        ),
+    };
+    (
+        status,
+        Json(ApiError {
+            message: message.to_string(),
+            details: vec![detail],
+        }),
+    )
+}
+
+fn case_store_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "case store failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "case_error".to_string(),
+            details: vec![format!("Failed to access cases: {}", e)],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aegistry_core::{HitSource, ScoreComponents, SubjectKind};
+
+    fn hit(subject_id: &str, risk_level: RiskLevel) -> Hit {
+        Hit {
+            subject_id: subject_id.to_string(),
+            matched_name: "Test Subject".to_string(),
+            source: HitSource::Stub,
+            kind: SubjectKind::Person,
+            score: 0.93,
+            risk_level,
+            components: ScoreComponents {
+                name_similarity: 0.93,
+                dob_similarity: 0.0,
+                country_match: 0.0,
+                phonetic_match: true,
+                identifier_match: 0.0,
+            },
+            explanation: vec![],
+            matched_alias: None,
+            record_version: Some("v1".to_string()),
+            suppressed: false,
+            suppression_reason: None,
+        }
+    }
+
+    const ALICE: Analyst = Analyst { name: "alice", api_key_id: "key-1" };
+    const BOB: Analyst = Analyst { name: "bob", api_key_id: "key-2" };
+
+    fn conn_with_alert() -> (Connection, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        suppression::init_schema(&conn).unwrap();
+        let hits = [hit("s1", RiskLevel::Review), hit("s2", RiskLevel::None)];
+        assert_eq!(open_alerts(&conn, "t1", "req-1", Some("cust-1"), "Test Subject", &hits).unwrap(), 1);
+        // Re-recording the same request does not duplicate alerts
+        assert_eq!(open_alerts(&conn, "t1", "req-1", Some("cust-1"), "Test Subject", &hits).unwrap(), 0);
+        let alert_id = list_alerts(&conn, "t1", None, 10, 0).unwrap().remove(0).id;
+        (conn, alert_id)
+    }
+
+    #[test]
+    fn decision_applies_immediately_without_four_eyes() {
+        let (conn, alert_id) = conn_with_alert();
+        let status = decide(&conn, "t1", &alert_id, CaseDecision::FalsePositive, Some("different DOB"), ALICE)
+            .unwrap()
+            .unwrap();
+        assert_eq!(status, AlertStatus::FalsePositive);
+        // Settled alerts take no further decisions
+        let again = decide(&conn, "t1", &alert_id, CaseDecision::TrueMatch, None, ALICE).unwrap();
+        assert_eq!(again, Err(CaseError::InvalidState(AlertStatus::FalsePositive)));
+        // Other tenants cannot see the alert
+        assert!(get_alert(&conn, "t2", &alert_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn four_eyes_requires_a_second_analyst() {
+        let (conn, alert_id) = conn_with_alert();
+        set_settings(&conn, "t1", &CaseSettings { four_eyes_required: true }).unwrap();
+
+        let status = decide(&conn, "t1", &alert_id, CaseDecision::TrueMatch, None, ALICE).unwrap().unwrap();
+        assert_eq!(status, AlertStatus::PendingApproval);
+
+        let same = review(&conn, "t1", &alert_id, true, None, ALICE).unwrap();
+        assert_eq!(same, Err(CaseError::SameReviewer));
+        // Another name typed in with the same key is still the same analyst
+        let renamed = Analyst { name: "bob", ..ALICE };
+        assert_eq!(review(&conn, "t1", &alert_id, true, None, renamed).unwrap(), Err(CaseError::SameReviewer));
+
+        let status = review(&conn, "t1", &alert_id, true, Some("agreed"), BOB).unwrap().unwrap();
+        assert_eq!(status, AlertStatus::TrueMatch);
+
+        let decisions = get_decisions(&conn, &alert_id).unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].review_status, "approved");
+        assert_eq!(decisions[0].reviewed_by.as_deref(), Some("bob"));
+        assert_eq!(decisions[0].reviewed_by_key.as_deref(), Some("key-2"));
+    }
+
+    #[test]
+    fn rejected_review_reopens_alert() {
+        let (conn, alert_id) = conn_with_alert();
+        set_settings(&conn, "t1", &CaseSettings { four_eyes_required: true }).unwrap();
+        decide(&conn, "t1", &alert_id, CaseDecision::FalsePositive, None, ALICE).unwrap().unwrap();
+
+        let status = review(&conn, "t1", &alert_id, false, Some("check the passport"), BOB).unwrap().unwrap();
+        assert_eq!(status, AlertStatus::Open);
+        assert!(suppression::list(&conn, "t1", false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn false_positive_suppresses_future_hits() {
+        let (conn, alert_id) = conn_with_alert();
+        decide(&conn, "t1", &alert_id, CaseDecision::FalsePositive, None, ALICE).unwrap().unwrap();
+
+        let suppressions = suppression::list(&conn, "t1", false).unwrap();
+        assert_eq!(suppressions.len(), 1);
+        assert_eq!(suppressions[0].reason, "cleared as false positive by alice");
+        assert_eq!(suppressions[0].alert_id.as_deref(), Some(alert_id.as_str()));
+
+        // The next screening of the same customer is suppressed and opens no alert
+        let mut hits = vec![hit("s1", RiskLevel::Review)];
+        suppression::apply(&conn, "t1", Some("cust-1"), "Test Subject", &mut hits).unwrap();
+        assert!(hits[0].suppressed);
+        assert_eq!(open_alerts(&conn, "t1", "req-2", Some("cust-1"), "Test Subject", &hits).unwrap(), 0);
+    }
+}
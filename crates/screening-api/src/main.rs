@@ -1,6 +1,6 @@
 use aegistry_core::{
-    health_status, new_request_id, HealthStatus, Hit, RiskLevel, ScreenPersonRequest,
-    ScreenPersonResponse, VersionResponse, PROJECT_NAME, PROJECT_VERSION,
+    health_status, new_request_id, HealthStatus, Hit, HitSource, ScreenIdentifier, ScreenPersonRequest,
+    ScreenPersonResponse, SourceInfo, SubjectKind, VersionResponse, PROJECT_NAME, PROJECT_VERSION,
 };
 use axum::{
     extract::{Path, Query, State},
@@ -8,12 +8,13 @@ use axum::{
     middleware,
     response::{Html, IntoResponse, Response},
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
 use chrono::Utc;
-use matching_core::{score_against_stub, MatchingEngine};
+use matching_core::{score_against_stub, BirthDate, MatchingEngine, ScreeningQuery};
 use metrics::{counter, histogram};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use utoipa::ToSchema;
 use std::sync::Arc;
@@ -23,22 +24,29 @@ use tokio::net::TcpListener;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use validator::Validate;
 
+mod addresses;
+mod admin;
 mod analytics;
 mod audit;
 mod auth;
 mod batch;
+mod batch_csv;
+mod cases;
+mod monitoring;
+mod rate_limit;
 mod risk;
+mod suppression;
 mod tenant;
 mod tenant_db;
+mod vessels;
 mod webhooks;
 
-use auth::auth_middleware;
-use batch::{BatchJob, BatchRequest, BatchResponse, BatchStatus, BatchResult};
+use auth::{auth_middleware, ApiKeyAuth, Scope};
+use batch::{BatchResponse, BatchStatus, BatchResult};
 use ingest::monitoring::{
-    add_monitored_subject, get_pending_notifications, mark_notified, remove_monitored_subject,
-    compute_result_hash,
+    claim_recording, diff_hits, get_pending_notifications, get_previous_hits, list_monitored_subjects, mark_notified,
+    remove_monitored_subject, reportable_hits, upsert_monitored_subject, MonitoredSubject, SubjectFilter,
 };
-use tenant::TenantStore;
 use tenant_db::{ensure_default_tenant, open_tenant_db};
 
 const SERVICE_NAME: &str = "screening-api";
@@ -47,11 +55,12 @@ const SERVICE_NAME: &str = "screening-api";
 pub struct AppState {
     pub config: AppConfig,
     pub engine: Option<Arc<MatchingEngine>>,
-    pub tenant_store: Arc<TenantStore>,
-    pub batch_jobs: Arc<tokio::sync::RwLock<std::collections::HashMap<String, BatchJob>>>,
+    pub key_cache: Arc<auth::KeyCache>,
     pub monitoring_db: Arc<tokio::sync::Mutex<rusqlite::Connection>>,
+    pub tenant_db: Arc<tokio::sync::Mutex<rusqlite::Connection>>,
     pub risk_store: Arc<risk::RiskStore>,
     pub analytics_store: Arc<analytics::AnalyticsStore>,
+    pub rate_limiter: Arc<rate_limit::RateLimiter>,
 }
 
 #[tokio::main]
@@ -71,25 +80,25 @@ async fn main() {
         tracing::warn!("no sanctions data found, using stub data. Run 'cargo run -p ingest' first.");
     }
 
-    // Initialize tenant store
-    let tenant_store = Arc::new(TenantStore::new(&cfg.data_dir));
-    
-    // Always ensure default tenant exists in memory store (used for auth)
-    tenant_store.create_default_tenant();
-    
-    // Also try to use persistent tenant DB for future use
-    match open_tenant_db(&cfg.data_dir) {
+    // Persistent tenant DB: API keys, audit trail and cases
+    let tenant_db = match open_tenant_db(&cfg.data_dir) {
         Ok(conn) => {
             if let Err(e) = ensure_default_tenant(&conn) {
                 tracing::warn!(error = %e, "failed to ensure default tenant in persistent DB");
             } else {
                 tracing::info!("persistent tenant storage initialized");
             }
+            conn
         }
         Err(e) => {
-            tracing::warn!(error = %e, "failed to open tenant DB, using in-memory store only");
+            tracing::warn!(error = %e, "failed to open tenant DB, tenants and audit trail will not persist");
+            let conn = rusqlite::Connection::open_in_memory().unwrap();
+            tenant_db::init_tenant_schema(&conn).ok();
+            ensure_default_tenant(&conn).ok();
+            conn
         }
-    }
+    };
+    let tenant_db = Arc::new(tokio::sync::Mutex::new(tenant_db));
 
     // Open monitoring database (same as main aegistry.db)
     let db_path = std::path::PathBuf::from(&cfg.data_dir).join("aegistry.db");
@@ -134,13 +143,17 @@ async fn main() {
     let state = AppState {
         config: cfg.clone(),
         engine: engine.clone(),
-        tenant_store,
-        batch_jobs: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+        key_cache: Arc::new(auth::KeyCache::new()),
         monitoring_db: monitoring_db.clone(),
+        tenant_db,
         risk_store: risk_store.clone(),
         analytics_store: analytics_store.clone(),
+        rate_limiter: Arc::new(rate_limit::RateLimiter::new()),
     };
 
+    batch::resume_unfinished(&state).await;
+    tokio::spawn(batch::retention_loop(state.clone()));
+
     // Start background callback task
     if engine.is_some() {
         let callback_state = state.clone();
@@ -196,25 +209,97 @@ pub fn build_router(state: AppState) -> Router {
         .route("/health", get(health))
         .route("/metrics", get(metrics_handler));
 
-    // Protected routes (require API key)
+    // Protected routes (require API key), grouped by the scope each needs
     let protected_routes = Router::new()
         .route("/v1/version", get(version))
-        .route("/v1/persons/screen", post(screen_person))
-        .route("/v1/entities/screen", post(screen_entity))
-        .route("/v1/batch", post(create_batch))
-        .route("/v1/batch/:job_id", get(get_batch_status))
-        .route("/v1/batch/:job_id/results", get(get_batch_results))
-        .route("/v1/monitoring", post(add_monitoring))
-        .route("/v1/monitoring", get(list_monitoring))
-        .route("/v1/monitoring/:reference_id", axum::routing::delete(remove_monitoring))
+        .route("/v1/sources", get(list_sources))
+        .merge(scoped(
+            Scope::Screen,
+            Router::new()
+                .route("/v1/persons/screen", post(screen_person))
+                .route("/v1/entities/screen", post(screen_entity))
+                .route("/v1/addresses/screen", post(addresses::screen_address))
+                .route("/v1/vessels/screen", post(vessels::screen_vessel))
+                .route("/v1/subjects/:subject_id", get(get_subject)),
+        ))
+        .merge(scoped(
+            Scope::Batch,
+            Router::new()
+                .route(
+                    "/v1/batch",
+                    post(create_batch)
+                        .layer(axum::extract::DefaultBodyLimit::max(batch_csv::BATCH_BODY_LIMIT))
+                        .get(list_batches),
+                )
+                .route("/v1/batch/:job_id", get(get_batch_status).delete(cancel_batch))
+                .route("/v1/batch/:job_id/results", get(get_batch_results)),
+        ))
+        .merge(scoped(
+            Scope::Monitoring,
+            Router::new()
+                .route("/v1/monitoring", post(add_monitoring))
+                .route("/v1/monitoring", get(list_monitoring))
+                .route(
+                    "/v1/monitoring/bulk",
+                    post(monitoring::bulk_add_monitoring)
+                        .layer(axum::extract::DefaultBodyLimit::max(monitoring::BULK_BODY_LIMIT)),
+                )
+                .route(
+                    "/v1/monitoring/:reference_id",
+                    axum::routing::delete(remove_monitoring).patch(monitoring::update_monitoring),
+                ),
+        ))
+        .merge(scoped(
+            Scope::ReadAudit,
+            Router::new()
+                .route("/v1/audit", get(audit::list_audit_entries))
+                .route("/v1/audit/verify", get(audit::verify_audit_chain))
+                .route("/v1/audit/:request_id", get(audit::get_audit_entry)),
+        ))
+        .merge(scoped(
+            Scope::Cases,
+            Router::new()
+                .route("/v1/cases", get(cases::list_cases))
+                .route("/v1/cases/:alert_id", get(cases::get_case))
+                .route("/v1/cases/:alert_id/decision", post(cases::decide_case))
+                .route("/v1/cases/:alert_id/review", post(cases::review_case))
+                .route("/v1/suppressions", get(suppression::list_suppressions))
+                .route("/v1/suppressions/:id", axum::routing::delete(suppression::revoke_suppression)),
+        ))
+        .merge(scoped(
+            Scope::Admin,
+            Router::new()
+                .route("/v1/risk-config", get(risk::get_risk_config).put(risk::put_risk_config))
+                .route("/v1/cases/settings", get(cases::get_case_settings).put(cases::put_case_settings)),
+        ))
         .layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
 
+    // Tenant administration, behind the separate admin credential
+    let admin_routes = Router::new()
+        .route("/admin/v1/tenants", get(admin::list_tenants).post(admin::create_tenant))
+        .route("/admin/v1/tenants/:tenant_id", get(admin::get_tenant).patch(admin::update_tenant))
+        .route("/admin/v1/tenants/:tenant_id/keys", get(admin::list_keys).post(admin::create_key))
+        .route("/admin/v1/tenants/:tenant_id/usage", get(admin::tenant_usage))
+        .route(
+            "/admin/v1/tenants/:tenant_id/keys/:key_id",
+            axum::routing::delete(admin::revoke_key),
+        )
+        .layer(middleware::from_fn_with_state(state.clone(), admin::admin_middleware));
+
     Router::new()
         .merge(public_routes)
         .merge(protected_routes)
+        .merge(admin_routes)
         .with_state(state)
 }
 
+/// Routes that additionally require `scope` on the caller's API key
+fn scoped(scope: Scope, routes: Router<AppState>) -> Router<AppState> {
+    routes.route_layer(middleware::from_fn(move |request, next| {
+        auth::require_scope(scope, request, next)
+    }))
+}
+
 fn init_tracing() {
     tracing_subscriber::registry()
         .with(
@@ -264,6 +349,87 @@ async fn version() -> Json<VersionResponse<'static>> {
     })
 }
 
+/// A list the service screens against, with its latest load when known
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SourceEntry {
+    #[serde(flatten)]
+    pub info: SourceInfo,
+    pub last_loaded_at: Option<String>,
+    pub record_count: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/sources",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every sanctions and PEP list the service ingests", body = Vec<SourceEntry>)
+    )
+)]
+async fn list_sources(State(state): State<AppState>) -> Json<Vec<SourceEntry>> {
+    let loads = {
+        let db = state.monitoring_db.lock().await;
+        ingest::latest_dataset_loads(&db).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "failed to read dataset loads");
+            Vec::new()
+        })
+    };
+
+    let sources = HitSource::INGESTED
+        .iter()
+        .map(|source| {
+            let load = loads.iter().find(|l| l.source == source.code());
+            SourceEntry {
+                info: source.info(),
+                last_loaded_at: load.map(|l| l.fetched_at.clone()),
+                record_count: load.map(|l| l.record_count),
+            }
+        })
+        .collect();
+    Json(sources)
+}
+
+/// A listed subject as stored from its source, for adjudicating a hit
+#[derive(Debug, Serialize)]
+pub struct SubjectDetailResponse {
+    #[serde(flatten)]
+    pub record: ingest::SubjectRecord,
+    /// The list the subject comes from; absent for sources no longer ingested
+    pub list: Option<SourceInfo>,
+}
+
+async fn get_subject(
+    State(state): State<AppState>,
+    Path(subject_id): Path<String>,
+) -> Result<Json<SubjectDetailResponse>, (StatusCode, Json<ApiError>)> {
+    let record = {
+        let db = state.monitoring_db.lock().await;
+        ingest::get_subject(&db, &subject_id).map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    message: "subject_error".to_string(),
+                    details: vec![format!("Failed to read subject: {}", e)],
+                }),
+            )
+        })?
+    };
+
+    match record {
+        Some(record) => {
+            let list = HitSource::from_code(&record.source).map(|source| source.info());
+            Ok(Json(SubjectDetailResponse { record, list }))
+        }
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                message: "subject_not_found".to_string(),
+                details: vec![format!("Subject {} not found", subject_id)],
+            }),
+        )),
+    }
+}
+
 async fn metrics_handler() -> impl IntoResponse {
     // Metrics are collected but we return a simple status for now
     // Full Prometheus export requires storing the handle globally
@@ -272,6 +438,7 @@ async fn metrics_handler() -> impl IntoResponse {
 
 async fn screen_person(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
     headers: HeaderMap,
     Json(req): Json<ScreenPersonRequest>,
 ) -> Result<Response, (StatusCode, Json<ApiError>)> {
@@ -286,17 +453,50 @@ async fn screen_person(
         ));
     }
 
-    let hits = perform_screening(&state, &req.full_name(), req.country.as_deref(), req.dob_year());
+    let risk_config = state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map_err(risk::risk_store_error)?;
+    let full_name = req.full_name();
+    let query = ScreeningQuery {
+        name: full_name.clone(),
+        country: req.country.clone(),
+        dob: req.dob_year().map(BirthDate::year),
+        kind: None,
+        identifiers: req.identifiers.clone(),
+    };
+    let hits = perform_screening(&state, &risk_config, req.reference_id.as_deref(), &full_name, query).await?;
 
     let response = ScreenPersonResponse {
         request_id: new_request_id(),
-        reference_id: req.reference_id,
+        reference_id: req.reference_id.clone(),
         hits,
         checked_at: Utc::now().to_rfc3339(),
     };
 
     histogram!("screening_latency_seconds", "type" => "person").record(start.elapsed().as_secs_f64());
 
+    let entry = audit::AuditEntry::screening(
+        &auth.tenant_id,
+        "person",
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &response.hits,
+        start.elapsed(),
+    )
+    .with_payloads(&req, &response);
+    audit::record(&state, entry).await;
+    cases::record(
+        &state,
+        &auth.tenant_id,
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &full_name,
+        &response.hits,
+    )
+    .await;
+
     format_response(&headers, &response)
 }
 
@@ -312,6 +512,7 @@ async fn screen_person(
 )]
 async fn screen_entity(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
     headers: HeaderMap,
     Json(req): Json<ScreenEntityRequest>,
 ) -> Result<Response, (StatusCode, Json<ApiError>)> {
@@ -326,134 +527,244 @@ async fn screen_entity(
         ));
     }
 
-    let hits = perform_screening(&state, &req.name, req.country.as_deref(), None);
+    let risk_config = state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map_err(risk::risk_store_error)?;
+    let query = ScreeningQuery {
+        name: req.name.clone(),
+        country: req.country.clone(),
+        dob: None,
+        kind: None,
+        identifiers: req.identifiers.clone(),
+    };
+    let hits = perform_screening(&state, &risk_config, req.reference_id.as_deref(), &req.name, query).await?;
 
     let response = ScreenEntityResponse {
         request_id: new_request_id(),
-        reference_id: req.reference_id,
+        reference_id: req.reference_id.clone(),
         hits,
         checked_at: Utc::now().to_rfc3339(),
     };
 
     histogram!("screening_latency_seconds", "type" => "entity").record(start.elapsed().as_secs_f64());
 
+    let entry = audit::AuditEntry::screening(
+        &auth.tenant_id,
+        "entity",
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &response.hits,
+        start.elapsed(),
+    )
+    .with_payloads(&req, &response);
+    audit::record(&state, entry).await;
+    cases::record(
+        &state,
+        &auth.tenant_id,
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &req.name,
+        &response.hits,
+    )
+    .await;
+
     format_response(&headers, &response)
 }
 
-fn perform_screening(
+/// Screen a customer and mark hits they have already been cleared for.
+/// `reference_id` identifies the customer for suppressions; without one
+/// `input_name` does. Both must be what the screening's alerts are opened
+/// with, since suppressions are created from those alerts.
+async fn perform_screening(
     state: &AppState,
-    name: &str,
-    country: Option<&str>,
-    dob_year: Option<i32>,
-) -> Vec<Hit> {
-    if let Some(ref engine) = state.engine {
-        let matches = engine.search_and_score(name, country, dob_year, 10);
+    risk_config: &risk::RiskConfig,
+    reference_id: Option<&str>,
+    input_name: &str,
+    query: ScreeningQuery,
+) -> Result<Vec<Hit>, (StatusCode, Json<ApiError>)> {
+    let mut hits: Vec<Hit> = if let Some(engine) = state.engine.clone() {
+        // Tantivy search is CPU-bound; keep it off the async workers
+        let (weights, thresholds) = (risk_config.weights(), risk_config.thresholds());
+        let matches = tokio::task::spawn_blocking(move || engine.search_and_score(&query, 10, &weights, &thresholds))
+            .await
+            .map_err(screening_engine_error)?
+            .map_err(screening_engine_error)?;
 
         matches
             .into_iter()
             .map(|m| {
-                let explanation = m.components.explain(&m.primary_name, m.country.as_deref());
-                Hit {
-                    subject_id: m.subject_id,
-                    matched_name: m.primary_name,
-                    source: m.source,
-                    kind: m.kind,
-                    score: m.score,
-                    risk_level: if m.score >= 0.95 {
-                        RiskLevel::Hit
-                    } else if m.score >= 0.90 {
-                        RiskLevel::Review
-                    } else {
-                        RiskLevel::None
-                    },
-                    components: m.components,
-                    explanation,
-                }
+                let risk_level = risk_config.risk_level(m.score);
+                m.into_hit(risk_level)
             })
             .collect()
     } else {
-        let matches = score_against_stub(name, country, dob_year, 5);
+        let matches = score_against_stub(
+            &query.name,
+            query.country.as_deref(),
+            query.dob,
+            query.kind,
+            5,
+            &risk_config.weights(),
+        );
 
         matches
             .into_iter()
             .map(|m| {
-                let explanation = m.components.explain(m.subject.name, m.subject.country);
+                // Stub subjects have no aliases or identifiers to match on
+                let explanation = m.components.explain(m.subject.kind, m.subject.name, m.subject.country, None, None);
                 Hit {
                     subject_id: m.subject.subject_id.to_string(),
                     matched_name: m.subject.name.to_string(),
                     source: m.subject.source,
                     kind: m.subject.kind,
                     score: m.score,
-                    risk_level: if m.score >= 0.95 {
-                        RiskLevel::Hit
-                    } else if m.score >= 0.90 {
-                        RiskLevel::Review
-                    } else {
-                        RiskLevel::None
-                    },
+                    risk_level: risk_config.risk_level(m.score),
                     components: m.components,
                     explanation,
+                    matched_alias: None,
+                    record_version: None,
+                    suppressed: false,
+                    suppression_reason: None,
                 }
             })
             .collect()
+    };
+
+    let db = state.tenant_db.lock().await;
+    if let Err(e) = suppression::apply(&db, &risk_config.tenant_id, reference_id, input_name, &mut hits) {
+        tracing::error!(error = %e, tenant_id = %risk_config.tenant_id, "failed to apply suppressions");
     }
+    Ok(hits)
+}
+
+/// A search that failed or panicked; reported as an error, never as no hits
+fn screening_engine_error(e: impl std::fmt::Display) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "matching engine search failed");
+    counter!("screening_errors_total", "type" => "engine").increment(1);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "screening_error".to_string(),
+            details: vec!["The matching engine failed to complete the search".to_string()],
+        }),
+    )
 }
 
 async fn create_batch(
     State(state): State<AppState>,
-    Json(req): Json<BatchRequest>,
-) -> Result<Json<BatchResponse>, (StatusCode, Json<ApiError>)> {
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(mapping): Query<batch_csv::ColumnMapping>,
+    request: axum::extract::Request,
+) -> Result<Response, Response> {
     counter!("batch_requests_total").increment(1);
 
-    let total_records = req.records.len();
-    let job_id = new_request_id();
-    let job = BatchJob::new(job_id.clone(), total_records);
+    let records = batch_csv::read_records(request, mapping)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    // Each record counts against the rate limit and is billed individually.
+    // A batch within one minute's allowance is charged up front; a larger one
+    // is admitted and charged as its records are screened, at the tenant's rate.
+    let cost = records.len().max(1) as u32;
+    let (remaining, paced_api_key_id) = if cost > auth.rate_limit_per_minute {
+        (None, Some(auth.api_key_id.clone()))
+    } else {
+        (Some(rate_limit::charge(&state, &auth, "POST /v1/batch", cost).await?), None)
+    };
 
-    // Store job
-    {
-        let mut jobs = state.batch_jobs.write().await;
-        jobs.insert(job_id.clone(), job.clone());
-    }
+    let risk_config = state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map_err(|e| risk::risk_store_error(e).into_response())?;
+
+    let job_id = new_request_id();
+    let job = {
+        let db = state.tenant_db.lock().await;
+        batch::create_job(&db, &auth.tenant_id, &job_id, &records, paced_api_key_id.as_deref())
+            .map_err(|e| batch_store_error(e).into_response())?
+    };
 
     // Process in background
     let state_clone = state.clone();
-    let records = req.records;
-    let jid = job_id.clone();
     tokio::spawn(async move {
-        batch::process_batch(state_clone, jid, records).await;
+        batch::process_batch(state_clone, auth.tenant_id, risk_config, job_id, records, 0, paced_api_key_id).await;
     });
 
-    Ok(Json(BatchResponse {
-        job_id,
-        status: BatchStatus::Processing,
-        total_records,
-        processed_records: 0,
-        created_at: Utc::now().to_rfc3339(),
-    }))
+    let mut response = Json(BatchResponse::from(job)).into_response();
+    if let Some(remaining) = remaining {
+        response.headers_mut().insert(rate_limit::REMAINING_HEADER, remaining.into());
+    }
+    Ok(response)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchListParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+async fn list_batches(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(params): Query<BatchListParams>,
+) -> Result<Json<Vec<BatchResponse>>, (StatusCode, Json<ApiError>)> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let db = state.tenant_db.lock().await;
+    let jobs = batch::list_jobs(&db, &auth.tenant_id, limit, offset).map_err(batch_store_error)?;
+    Ok(Json(jobs.into_iter().map(BatchResponse::from).collect()))
 }
 
 async fn get_batch_status(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
     Path(job_id): Path<String>,
 ) -> Result<Json<BatchResponse>, (StatusCode, Json<ApiError>)> {
-    let jobs = state.batch_jobs.read().await;
-    
-    match jobs.get(&job_id) {
-        Some(job) => Ok(Json(BatchResponse {
-            job_id: job.id.clone(),
-            status: job.status.clone(),
-            total_records: job.total_records,
-            processed_records: job.processed_records,
-            created_at: job.created_at.clone(),
-        })),
-        None => Err((
-            StatusCode::NOT_FOUND,
+    let db = state.tenant_db.lock().await;
+
+    match batch::get_job(&db, &auth.tenant_id, &job_id).map_err(batch_store_error)? {
+        Some(job) => Ok(Json(BatchResponse::from(job))),
+        None => Err(job_not_found(&job_id)),
+    }
+}
+
+/// Stop a running job after the records already in flight. Results screened
+/// so far are kept until the job expires.
+async fn cancel_batch(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(job_id): Path<String>,
+) -> Result<Json<BatchResponse>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+
+    let job = batch::get_job(&db, &auth.tenant_id, &job_id)
+        .map_err(batch_store_error)?
+        .ok_or_else(|| job_not_found(&job_id))?;
+    if !batch::set_status(&db, &job.id, BatchStatus::Cancelled).map_err(batch_store_error)? {
+        return Err((
+            StatusCode::CONFLICT,
             Json(ApiError {
-                message: "job_not_found".to_string(),
-                details: vec![format!("Job {} not found", job_id)],
+                message: "job_not_running".to_string(),
+                details: vec![format!("Job {} is already {:?}", job_id, job.status)],
             }),
-        )),
+        ));
     }
+    tracing::info!(tenant_id = %auth.tenant_id, job_id = %job_id, "batch job cancelled");
+
+    let job = batch::get_job(&db, &auth.tenant_id, &job_id)
+        .map_err(batch_store_error)?
+        .ok_or_else(|| job_not_found(&job_id))?;
+    Ok(Json(BatchResponse::from(job)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchResultsParams {
+    /// NDJSON only: first record index to return, for tailing a running job
+    pub since: Option<usize>,
 }
 
 #[utoipa::path(
@@ -471,57 +782,147 @@ async fn get_batch_status(
 )]
 async fn get_batch_results(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
     headers: HeaderMap,
     Path(job_id): Path<String>,
+    Query(params): Query<BatchResultsParams>,
 ) -> Result<Response, (StatusCode, Json<ApiError>)> {
-    let jobs = state.batch_jobs.read().await;
-    
-    match jobs.get(&job_id) {
+    let db = state.tenant_db.lock().await;
+
+    match batch::get_job(&db, &auth.tenant_id, &job_id).map_err(batch_store_error)? {
+        Some(job) if accepts(&headers, NDJSON_CONTENT_TYPE) => {
+            drop(db);
+            // Streams whatever is stored and follows the job while it runs
+            let stream = batch::ndjson_stream(state.clone(), auth.tenant_id, job.id, params.since.unwrap_or(0));
+            Ok((
+                [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+                axum::body::Body::from_stream(stream),
+            )
+                .into_response())
+        }
         Some(job) => {
-            if job.status != BatchStatus::Completed {
+            // A cancelled job serves the results screened before it stopped
+            let detail = match job.status {
+                BatchStatus::Completed | BatchStatus::Cancelled => None,
+                BatchStatus::Processing => Some("Job is still processing"),
+                BatchStatus::Failed => Some("Job failed before completing"),
+            };
+            if let Some(detail) = detail {
                 return Err((
                     StatusCode::BAD_REQUEST,
                     Json(ApiError {
                         message: "job_not_complete".to_string(),
-                        details: vec!["Job is still processing".to_string()],
+                        details: vec![detail.to_string()],
                     }),
                 ));
             }
-            format_response(&headers, &job.results)
+            let results: Vec<BatchResult> = batch::get_results(&db, &job.id).map_err(batch_store_error)?;
+            drop(db);
+            if accepts(&headers, batch_csv::CSV_CONTENT_TYPE) {
+                return batch_csv::csv_response(&results);
+            }
+            format_response(&headers, &results)
         }
-        None => Err((
-            StatusCode::NOT_FOUND,
-            Json(ApiError {
-                message: "job_not_found".to_string(),
-                details: vec![format!("Job {} not found", job_id)],
-            }),
-        )),
+        None => Err(job_not_found(&job_id)),
     }
 }
 
+fn job_not_found(job_id: &str) -> (StatusCode, Json<ApiError>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiError {
+            message: "job_not_found".to_string(),
+            details: vec![format!("Job {} not found", job_id)],
+        }),
+    )
+}
+
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+fn accepts(headers: &HeaderMap, content_type: &str) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains(content_type))
+}
+
+fn batch_store_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "batch store failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "batch_error".to_string(),
+            details: vec![format!("Failed to access batch jobs: {}", e)],
+        }),
+    )
+}
+
 // Monitoring endpoints
-#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct AddMonitoringRequest {
+    #[validate(length(min = 1))]
     pub reference_id: String,
     #[validate(length(min = 1))]
     pub name: String,
+    #[serde(default)]
+    pub record_type: batch::RecordType,
     pub country: Option<String>,
     pub dob_year: Option<i32>,
+    /// `YYYY`, `YYYY-MM` or `YYYY-MM-DD`; takes precedence over `dob_year`
+    pub date_of_birth: Option<String>,
+    pub nationality: Option<String>,
     pub callback_url: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MonitoringEntry {
     pub reference_id: String,
     pub name: String,
+    pub kind: SubjectKind,
     pub country: Option<String>,
     pub dob_year: Option<i32>,
+    pub date_of_birth: Option<String>,
+    pub nationality: Option<String>,
     pub last_screened_at: String,
+    pub last_changed_at: Option<String>,
+    pub paused: bool,
     pub callback_url: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl From<MonitoredSubject> for MonitoringEntry {
+    fn from(s: MonitoredSubject) -> Self {
+        Self {
+            reference_id: s.reference_id,
+            name: s.name,
+            kind: s.kind,
+            country: s.country,
+            dob_year: s.dob_year,
+            date_of_birth: s.date_of_birth,
+            nationality: s.nationality,
+            last_screened_at: s.last_screened_at,
+            last_changed_at: s.last_changed_at,
+            paused: s.paused,
+            callback_url: s.callback_url,
+            metadata: s.metadata,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MonitoringListParams {
+    /// RFC 3339 timestamp; only subjects whose hits changed since then
+    pub changed_since: Option<String>,
+    pub paused: Option<bool>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
 }
 
 async fn add_monitoring(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
     Json(req): Json<AddMonitoringRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
     if let Err(e) = req.validate() {
@@ -530,110 +931,95 @@ async fn add_monitoring(
             Json(ApiError::from_validation(e)),
         ));
     }
+    let problems = req.problems("subject");
+    if !problems.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                message: "validation_error".to_string(),
+                details: problems,
+            }),
+        ));
+    }
 
-    // Extract tenant_id from auth (for now use "default")
-    let tenant_id = "default"; // TODO: get from auth middleware
+    let tenant_id = auth.tenant_id.as_str();
+    let risk_config = state
+        .risk_store
+        .get_config(tenant_id)
+        .await
+        .map_err(risk::risk_store_error)?;
 
-    // Store in SQLite
-    let db = state.monitoring_db.lock().await;
-    match add_monitored_subject(
-        &*db,
-        tenant_id,
-        &req.reference_id,
-        &req.name,
-        req.country.as_deref(),
-        req.dob_year,
-        req.callback_url.as_deref(),
-    ) {
-        Ok(_) => {
-            tracing::info!(
-                reference_id = %req.reference_id,
-                name = %req.name,
-                "added subject to monitoring"
-            );
-
-            // Perform initial screening
-            let hits = perform_screening(&state, &req.name, req.country.as_deref(), req.dob_year);
-            let hit_data: Vec<(String, f32)> = hits.iter().map(|h| (h.subject_id.clone(), h.score)).collect();
-            let result_hash = compute_result_hash(&hit_data);
-
-            // Record initial result
-            let db = state.monitoring_db.lock().await;
-            if let Ok(subjects) = ingest::monitoring::get_monitored_subjects(&*db, tenant_id) {
-                if let Some(subject) = subjects.iter().find(|s| s.reference_id == req.reference_id) {
-                    let _ = ingest::monitoring::record_monitoring_result(
-                        &*db,
-                        subject.id,
-                        &result_hash,
-                        hits.len(),
-                        hits.first().map(|h| h.score).unwrap_or(0.0),
-                        false, // No changes on initial screening
-                    );
-                }
-            }
-            drop(db);
+    // Store in SQLite; the lock is released before screening, which locks the DB again
+    let attributes = req.attributes();
+    let (subject_id, created) = {
+        let db = state.monitoring_db.lock().await;
+        upsert_monitored_subject(&db, tenant_id, &req.reference_id, &attributes).map_err(monitoring::monitoring_error)?
+    };
+    tracing::info!(
+        reference_id = %req.reference_id,
+        name = %req.name,
+        created,
+        "added subject to monitoring"
+    );
 
-            Ok(Json(serde_json::json!({
-                "status": "added",
-                "reference_id": req.reference_id,
-                "message": "Subject will be re-screened when sanctions lists update"
-            })))
-        }
-        Err(e) => {
-            tracing::error!(error = %e, "failed to add monitoring");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError {
-                    message: "monitoring_error".to_string(),
-                    details: vec![format!("Failed to add monitoring: {}", e)],
-                }),
-            ))
-        }
-    }
+    monitoring::screen_baseline(&state, tenant_id, &risk_config, subject_id, &req.reference_id, &attributes).await;
+
+    Ok(Json(serde_json::json!({
+        "status": if created { "added" } else { "updated" },
+        "reference_id": req.reference_id,
+        "message": "Subject will be re-screened when sanctions lists update"
+    })))
 }
 
 #[utoipa::path(
     get,
     path = "/v1/monitoring",
     tag = "monitoring",
+    params(
+        ("changed_since" = Option<String>, Query, description = "Only subjects whose hits changed since this RFC 3339 time"),
+        ("paused" = Option<bool>, Query, description = "Only paused or only active subjects"),
+        ("limit" = Option<i64>, Query, description = "Page size, at most 1000"),
+        ("offset" = Option<i64>, Query, description = "Entries to skip")
+    ),
     responses(
-        (status = 200, description = "List of monitored subjects", body = Vec<MonitoringEntry>)
+        (status = 200, description = "List of monitored subjects, most recently changed first", body = Vec<MonitoringEntry>)
     )
 )]
 async fn list_monitoring(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(params): Query<MonitoringListParams>,
 ) -> Result<Json<Vec<MonitoringEntry>>, (StatusCode, Json<ApiError>)> {
-    let tenant_id = "default"; // TODO: get from auth
-    
+    // Stored timestamps are SQLite datetimes in UTC
+    let changed_since = match params.changed_since.as_deref() {
+        Some(ts) => Some(
+            chrono::DateTime::parse_from_rfc3339(ts)
+                .map_err(|e| {
+                    (
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        Json(ApiError {
+                            message: "validation_error".to_string(),
+                            details: vec![format!("changed_since: {}", e)],
+                        }),
+                    )
+                })?
+                .with_timezone(&Utc)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        ),
+        None => None,
+    };
+    let filter = SubjectFilter {
+        changed_since,
+        paused: params.paused,
+    };
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+
     let db = state.monitoring_db.lock().await;
-    match ingest::monitoring::get_monitored_subjects(&*db, tenant_id) {
-        Ok(subjects) => {
-            let entries: Vec<MonitoringEntry> = subjects
-                .into_iter()
-                .map(|s| MonitoringEntry {
-                    reference_id: s.reference_id,
-                    name: s.name,
-                    country: s.country,
-                    dob_year: s.dob_year,
-                    last_screened_at: s.last_screened_at,
-                    callback_url: s.callback_url,
-                })
-                .collect();
-            drop(db);
-            Ok(Json(entries))
-        }
-        Err(e) => {
-            drop(db);
-            tracing::error!(error = %e, "failed to list monitoring");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError {
-                    message: "monitoring_error".to_string(),
-                    details: vec![format!("Failed to list monitoring: {}", e)],
-                }),
-            ))
-        }
-    }
+    let subjects =
+        list_monitored_subjects(&db, &auth.tenant_id, &filter, limit, offset).map_err(monitoring::monitoring_error)?;
+    Ok(Json(subjects.into_iter().map(MonitoringEntry::from).collect()))
 }
 
 #[utoipa::path(
@@ -650,9 +1036,10 @@ async fn list_monitoring(
 )]
 async fn remove_monitoring(
     State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
     Path(reference_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
-    let tenant_id = "default"; // TODO: get from auth
+    let tenant_id = auth.tenant_id.as_str();
     
     let db = state.monitoring_db.lock().await;
     match remove_monitored_subject(&*db, tenant_id, &reference_id) {
@@ -688,7 +1075,8 @@ async fn remove_monitoring(
     }
 }
 
-/// Background task that processes monitoring callbacks
+/// Background task that audits and alerts on monitoring changes, then sends
+/// callbacks for subjects that have a callback URL
 async fn monitoring_callback_loop(state: AppState) {
     let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
     
@@ -708,84 +1096,186 @@ async fn monitoring_callback_loop(state: AppState) {
         };
 
         for (subject, result, result_id) in notifications {
-            if let Some(callback_url) = &subject.callback_url {
-                // Perform re-screening to get current hits
-                let hits = perform_screening(&state, &subject.name, subject.country.as_deref(), subject.dob_year);
-                
-                // Get previous hits from last_result_hash (simplified - in production would store full results)
-                let previous_hits: Vec<Hit> = Vec::new(); // TODO: store previous hits
-                
-                let callback_payload = serde_json::json!({
-                    "reference_id": result.reference_id,
-                    "name": subject.name,
-                    "has_changes": result.has_changes,
-                    "new_hits": hits,
-                    "previous_hits": previous_hits,
-                    "screened_at": chrono::Utc::now().to_rfc3339(),
-                    "hit_count": result.hit_count,
-                    "highest_score": result.highest_score,
-                });
-
-                // Send callback with retries
-                let mut success = false;
-                for attempt in 1..=3 {
-                    let client = match reqwest::Client::builder()
-                        .timeout(std::time::Duration::from_secs(5))
-                        .build()
-                    {
+            let start = Instant::now();
+            // Report the screening that detected the change; only results recorded
+            // before hits were stored need re-screening, under the owning tenant's config
+            let mut hits = match result.hits.clone() {
+                Some(hits) => hits,
+                None => {
+                    let risk_config = match state.risk_store.get_config(&subject.tenant_id).await {
                         Ok(c) => c,
                         Err(e) => {
-                            tracing::warn!(error = %e, attempt, "failed to build HTTP client");
+                            tracing::warn!(error = %e, tenant_id = %subject.tenant_id, "failed to load risk config, skipping monitoring result");
                             continue;
                         }
                     };
-                    
-                    match client
-                        .post(callback_url)
-                        .header("Content-Type", "application/json")
-                        .body(serde_json::to_string(&callback_payload).unwrap_or_default())
-                        .send()
-                        .await
-                    {
-                            Ok(resp) if resp.status().is_success() => {
-                                tracing::info!(
-                                    callback_url = %callback_url,
-                                    reference_id = %result.reference_id,
-                                    "callback sent successfully"
-                                );
-                                success = true;
-                                break;
-                            }
-                            Ok(resp) => {
-                                tracing::warn!(
-                                    callback_url = %callback_url,
-                                    status = %resp.status(),
-                                    attempt,
-                                    "callback failed"
-                                );
-                            }
-                            Err(e) => {
-                                tracing::warn!(
-                                    callback_url = %callback_url,
-                                    error = %e,
-                                    attempt,
-                                    "callback error"
-                                );
-                            }
+                    let query = ScreeningQuery {
+                        name: subject.name.clone(),
+                        country: subject.country.clone(),
+                        dob: subject.birth_date(),
+                        kind: Some(subject.kind),
+                        identifiers: Vec::new(),
+                    };
+                    match perform_screening(&state, &risk_config, Some(&subject.reference_id), &subject.name, query).await {
+                        Ok(hits) => reportable_hits(hits),
+                        Err(_) => {
+                            tracing::warn!(reference_id = %subject.reference_id, "re-screening failed, skipping monitoring result");
+                            continue;
                         }
-                    
-                    // Exponential backoff
-                    if attempt < 3 {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(2_u64.pow(attempt))).await;
                     }
                 }
+            };
+            // Clearances made since the result was stored still apply
+            {
+                let db = state.tenant_db.lock().await;
+                if let Err(e) = suppression::apply(
+                    &db,
+                    &subject.tenant_id,
+                    Some(&subject.reference_id),
+                    &subject.name,
+                    &mut hits,
+                ) {
+                    tracing::error!(error = %e, tenant_id = %subject.tenant_id, "failed to apply suppressions");
+                }
+            }
+            let previous_hits = {
+                let db = state.monitoring_db.lock().await;
+                match get_previous_hits(&db, subject.id, result_id) {
+                    Ok(previous) => previous.unwrap_or_default(),
+                    Err(e) => {
+                        tracing::warn!(error = %e, result_id, "failed to load previous hits");
+                        Vec::new()
+                    }
+                }
+            };
+            let diff = diff_hits(&previous_hits, &hits);
+            // Derived from the result so retried deliveries refer to the same screening
+            let request_id = format!("monitoring:{}", result_id);
+            let first_delivery = {
+                let db = state.monitoring_db.lock().await;
+                claim_recording(&db, result_id).unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, result_id, "failed to claim monitoring result for recording");
+                    false
+                })
+            };
+            if first_delivery {
+                let entry = audit::AuditEntry::screening(
+                    &subject.tenant_id,
+                    "monitoring",
+                    &request_id,
+                    Some(&subject.reference_id),
+                    &hits,
+                    start.elapsed(),
+                )
+                .with_payloads(
+                    &serde_json::json!({
+                        "reference_id": subject.reference_id,
+                        "name": subject.name,
+                        "country": subject.country,
+                        "dob_year": subject.dob_year,
+                    }),
+                    &hits,
+                );
+                audit::record(&state, entry).await;
+                // Hits already reported for this subject have had their alerts
+                let alert_hits: Vec<Hit> = diff
+                    .added
+                    .iter()
+                    .cloned()
+                    .chain(diff.changed.iter().map(|c| c.hit.clone()))
+                    .collect();
+                cases::record(
+                    &state,
+                    &subject.tenant_id,
+                    &request_id,
+                    Some(&subject.reference_id),
+                    &subject.name,
+                    &alert_hits,
+                )
+                .await;
+            }
 
-                if success {
-                    // Mark as notified
-                    let db = state.monitoring_db.lock().await;
-                    if let Err(e) = mark_notified(&*db, result_id) {
-                        tracing::warn!(error = %e, result_id, "failed to mark notification as sent");
+            // Audited and alerted above whether or not anyone is told
+            let Some(callback_url) = &subject.callback_url else {
+                let db = state.monitoring_db.lock().await;
+                if let Err(e) = mark_notified(&db, result_id) {
+                    tracing::warn!(error = %e, result_id, "failed to mark monitoring result as handled");
+                }
+                continue;
+            };
+
+            let callback_payload = serde_json::json!({
+                "request_id": request_id,
+                "reference_id": result.reference_id,
+                "name": subject.name,
+                "has_changes": result.has_changes,
+                "new_hits": hits,
+                "previous_hits": previous_hits,
+                "diff": diff,
+                "screened_at": chrono::Utc::now().to_rfc3339(),
+                "hit_count": result.hit_count,
+                "highest_score": result.highest_score,
+            });
+
+            // Send callback with retries
+            let mut success = false;
+            for attempt in 1..=3 {
+                let client = match reqwest::Client::builder()
+                    .timeout(std::time::Duration::from_secs(5))
+                    .build()
+                {
+                    Ok(c) => c,
+                    Err(e) => {
+                        tracing::warn!(error = %e, attempt, "failed to build HTTP client");
+                        continue;
+                    }
+                };
+                
+                match client
+                    .post(callback_url)
+                    .header("Content-Type", "application/json")
+                    .body(serde_json::to_string(&callback_payload).unwrap_or_default())
+                    .send()
+                    .await
+                {
+                        Ok(resp) if resp.status().is_success() => {
+                            tracing::info!(
+                                callback_url = %callback_url,
+                                reference_id = %result.reference_id,
+                                "callback sent successfully"
+                            );
+                            success = true;
+                            break;
+                        }
+                        Ok(resp) => {
+                            tracing::warn!(
+                                callback_url = %callback_url,
+                                status = %resp.status(),
+                                attempt,
+                                "callback failed"
+                            );
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                callback_url = %callback_url,
+                                error = %e,
+                                attempt,
+                                "callback error"
+                            );
+                        }
                     }
+                
+                // Exponential backoff
+                if attempt < 3 {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(2_u64.pow(attempt))).await;
+                }
+            }
+
+            if success {
+                // Mark as notified
+                let db = state.monitoring_db.lock().await;
+                if let Err(e) = mark_notified(&*db, result_id) {
+                    tracing::warn!(error = %e, result_id, "failed to mark notification as sent");
                 }
             }
         }
@@ -812,6 +1302,12 @@ pub struct AppConfig {
     pub bind_addr: String,
     pub run_env: String,
     pub data_dir: String,
+    /// SHA-256 of `ADMIN_API_KEY`; the admin API is disabled when unset
+    pub admin_key_hash: Option<String>,
+    /// Batch records screened concurrently per job
+    pub batch_concurrency: usize,
+    /// Days finished batch jobs and their results are kept
+    pub batch_retention_days: i64,
 }
 
 impl AppConfig {
@@ -820,6 +1316,20 @@ impl AppConfig {
             bind_addr: env::var("BIND_ADDR").unwrap_or_else(|_| "127.0.0.1:3100".to_string()),
             run_env: env::var("RUN_ENV").unwrap_or_else(|_| "local".to_string()),
             data_dir: env::var("DATA_DIR").unwrap_or_else(|_| "data".to_string()),
+            admin_key_hash: env::var("ADMIN_API_KEY")
+                .ok()
+                .filter(|k| !k.is_empty())
+                .map(|k| admin::hash_admin_key(&k)),
+            batch_concurrency: env::var("BATCH_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(8),
+            batch_retention_days: env::var("BATCH_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(30),
         }
     }
 }
@@ -844,13 +1354,18 @@ impl ApiError {
     }
 }
 
-#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct ScreenEntityRequest {
     pub reference_id: Option<String>,
     #[validate(length(min = 1))]
     pub name: String,
     #[validate(length(min = 2, max = 2))]
     pub country: Option<String>,
+    /// LEI, registration, IMO or wallet numbers; an exact match is strong
+    /// evidence regardless of how well the name matches
+    #[serde(default)]
+    #[validate(length(max = 20), nested)]
+    pub identifiers: Vec<ScreenIdentifier>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -875,9 +1390,26 @@ mod tests {
         AppState {
             config: AppConfig::from_env(),
             engine: None,
-            tenant_store: Arc::new(TenantStore::new("data")),
-            batch_jobs: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
-            monitoring_db: Arc::new(tokio::sync::Mutex::new(db)),
+            key_cache: Arc::new(auth::KeyCache::new()),
+            monitoring_db: {
+                ingest::monitoring::init_monitoring_schema(&db).unwrap();
+                Arc::new(tokio::sync::Mutex::new(db))
+            },
+            tenant_db: {
+                let conn = rusqlite::Connection::open_in_memory().unwrap();
+                tenant_db::init_tenant_schema(&conn).unwrap();
+                ensure_default_tenant(&conn).unwrap();
+                Arc::new(tokio::sync::Mutex::new(conn))
+            },
+            risk_store: {
+                let conn = rusqlite::Connection::open_in_memory().unwrap();
+                risk::RiskStore::init_schema(&conn).unwrap();
+                Arc::new(risk::RiskStore::new(conn))
+            },
+            analytics_store: Arc::new(analytics::AnalyticsStore::new(
+                rusqlite::Connection::open_in_memory().unwrap(),
+            )),
+            rate_limiter: Arc::new(rate_limit::RateLimiter::new()),
         }
     }
 
@@ -892,48 +1424,1038 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn screen_requires_auth() {
-        let app = build_router(test_state());
-        let body = serde_json::json!({
-            "first_name": "Maria",
-            "last_name": "Garcia",
-        });
+    async fn sources_describe_every_ingested_list() {
+        let state = test_state();
+        {
+            let db = state.monitoring_db.lock().await;
+            ingest::init_schema(&db).unwrap();
+            ingest::record_dataset_version(&db, "PEP_DE_BUNDESTAG", 733, None).unwrap();
+        }
+        let app = build_router(state);
         let res = app
             .oneshot(
                 Request::builder()
-                    .method("POST")
-                    .uri("/v1/persons/screen")
-                    .header("content-type", "application/json")
-                    .body(Body::from(body.to_string()))
+                    .uri("/v1/sources")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let sources: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(sources.len(), HitSource::INGESTED.len());
+
+        let bundestag = sources.iter().find(|s| s["source"] == "PepDeBundestag").unwrap();
+        assert_eq!(bundestag["list_type"], "pep");
+        assert_eq!(bundestag["jurisdiction"], "DE");
+        assert_eq!(bundestag["record_count"], 733);
+        let canada = sources.iter().find(|s| s["code"] == "CANADA").unwrap();
+        assert_eq!(canada["list_type"], "sanctions");
+        assert!(canada["last_loaded_at"].is_null());
     }
 
     #[tokio::test]
-    async fn screen_with_auth() {
+    async fn subject_detail_exposes_stored_list_data() {
         let state = test_state();
-        state.tenant_store.create_default_tenant();
+        {
+            let db = state.monitoring_db.lock().await;
+            ingest::init_schema(&db).unwrap();
+            let subject = ingest::ParsedSubject {
+                source_ref: "ofac_7890".to_string(),
+                kind: ingest::SubjectKind::Person,
+                primary_name: "Uday HUSSEIN".to_string(),
+                aliases: vec![],
+                date_of_birth: Some("1964-06-18".to_string()),
+                date_of_birth_year: Some(1964),
+                country: Some("IQ".to_string()),
+                nationalities: vec!["IQ".to_string()],
+                details: ingest::SubjectDetails {
+                    identifiers: vec![ingest::ParsedIdentifier {
+                        id_type: "Passport".to_string(),
+                        number: "A1234567".to_string(),
+                        country: Some("Iraq".to_string()),
+                        issued_on: None,
+                        expires_on: None,
+                        note: None,
+                    }],
+                    programs: vec!["IRAQ2".to_string()],
+                    remarks: Some("Son of Saddam Hussein".to_string()),
+                    ..Default::default()
+                },
+            };
+            ingest::upsert_subjects(&db, &[subject], "OFAC").unwrap();
+        }
         let app = build_router(state);
-        let body = serde_json::json!({
-            "first_name": "Maria",
-            "last_name": "Garcia",
-        });
+
         let res = app
+            .clone()
             .oneshot(
                 Request::builder()
-                    .method("POST")
-                    .uri("/v1/persons/screen")
-                    .header("content-type", "application/json")
+                    .uri("/v1/subjects/ofac_ofac_7890")
                     .header("x-api-key", "test-api-key")
-                    .body(Body::from(body.to_string()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let subject: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(subject["primary_name"], "Uday HUSSEIN");
+        assert_eq!(subject["nationalities"][0], "IQ");
+        assert_eq!(subject["identifiers"][0]["number"], "A1234567");
+        assert_eq!(subject["programs"][0], "IRAQ2");
+        assert_eq!(subject["remarks"], "Son of Saddam Hussein");
+        assert_eq!(subject["list"]["name"], HitSource::Ofac.info().name);
+        assert!(subject["record_version"].is_string());
+
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/subjects/ofac_missing")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn addresses_screen_against_ofac_wallets() {
+        let state = test_state();
+        {
+            let db = state.monitoring_db.lock().await;
+            ingest::init_schema(&db).unwrap();
+            let subjects = vec![ingest::ParsedSubject {
+                source_ref: "ofac_25470".to_string(),
+                kind: ingest::SubjectKind::Entity,
+                primary_name: "SUEX OTC, S.R.O.".to_string(),
+                aliases: vec![],
+                date_of_birth: None,
+                date_of_birth_year: None,
+                country: None,
+                nationalities: vec![],
+                details: ingest::SubjectDetails {
+                    identifiers: vec![ingest::ParsedIdentifier {
+                        id_type: "Digital Currency Address - ETH".to_string(),
+                        number: "0x2f389cE8bD8ff92De3402FFCe4691d17fC4f6535".to_string(),
+                        country: None,
+                        issued_on: None,
+                        expires_on: None,
+                        note: None,
+                    }],
+                    ..Default::default()
+                },
+            }];
+            ingest::upsert_subjects(&db, &subjects, "OFAC").unwrap();
+            ingest::replace_crypto_addresses(&db, "OFAC", &subjects).unwrap();
+        }
+        let app = build_router(state);
+        let screen = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/addresses/screen")
+                .header("content-type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let res = app
+            .clone()
+            .oneshot(screen(serde_json::json!({
+                "address": "0x2F389CE8BD8FF92DE3402FFCE4691D17FC4F6535",
+                "chain": "eth",
+            })))
+            .await
+            .unwrap();
         assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let hits = response["hits"].as_array().unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["subject_id"], "ofac_ofac_25470");
+        assert_eq!(hits[0]["matched_name"], "SUEX OTC, S.R.O.");
+        assert_eq!(hits[0]["risk_level"], "Hit");
+        assert_eq!(hits[0]["components"]["identifier_match"], 1.0);
+
+        let res = app
+            .clone()
+            .oneshot(screen(serde_json::json!({
+                "address": "0x2f389ce8bd8ff92de3402ffce4691d17fc4f6535",
+                "chain": "USDT",
+            })))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        // Hex addresses are shared across EVM chains and tokens
+        assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+
+        let res = app.oneshot(screen(serde_json::json!({ "address": "" }))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn vessels_screen_by_imo_and_former_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("aegistry.db");
+        let index_path = dir.path().join("index");
+        {
+            let conn = ingest::open_db(&db_path).unwrap();
+            ingest::init_schema(&conn).unwrap();
+            let subjects = ingest::parse_ofac_xml(
+                br#"<sdnList>
+                    <sdnEntry>
+                        <uid>9001</uid>
+                        <lastName>ADRIAN DARYA 1</lastName>
+                        <sdnType>Vessel</sdnType>
+                        <akaList>
+                            <aka><uid>1</uid><type>f.k.a.</type><category>strong</category><lastName>GRACE 1</lastName></aka>
+                        </akaList>
+                        <idList>
+                            <id><uid>2</uid><idType>Vessel Registration Identification</idType><idNumber>IMO 9116412</idNumber></id>
+                        </idList>
+                        <vesselInfo><vesselFlag>Iran</vesselFlag></vesselInfo>
+                    </sdnEntry>
+                    <sdnEntry>
+                        <uid>9002</uid>
+                        <lastName>ADRIAN TRADING LLC</lastName>
+                        <sdnType>Entity</sdnType>
+                    </sdnEntry>
+                </sdnList>"#,
+            )
+            .unwrap();
+            ingest::upsert_subjects(&conn, &subjects, "OFAC").unwrap();
+            ingest::SearchIndex::create(&index_path).unwrap().build_from_db(&conn).unwrap();
+        }
+        let mut state = test_state();
+        state.engine = Some(Arc::new(MatchingEngine::open(&index_path, &db_path).unwrap()));
+        let app = build_router(state.clone());
+        let screen = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/vessels/screen")
+                .header("content-type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let hits = |res: axum::response::Response| async move {
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            response["hits"].as_array().unwrap().clone()
+        };
+
+        // The IMO number alone finds the ship
+        let res = app.clone().oneshot(screen(serde_json::json!({"imo": "9116412"}))).await.unwrap();
+        let found = hits(res).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["subject_id"], "ofac_ofac_9001");
+        assert_eq!(found[0]["kind"], "Vessel");
+        assert_eq!(found[0]["risk_level"], "Hit");
+        assert_eq!(found[0]["explanation"][0], "IMO number '9116412' is listed for this vessel");
+
+        // Clearing the alert suppresses the hit on the next screening of the ship
+        {
+            let db = state.tenant_db.lock().await;
+            let alert = cases::list_alerts(&db, "default", None, 1, 0).unwrap().remove(0);
+            suppression::create(
+                &db,
+                &suppression::NewSuppression {
+                    tenant_id: &alert.tenant_id,
+                    reference_id: alert.reference_id.as_deref(),
+                    input_name: &alert.input_name,
+                    subject_id: &alert.subject_id,
+                    record_version: alert.record_version.as_deref(),
+                    reason: "different ship",
+                    alert_id: Some(&alert.id),
+                    created_by: "analyst",
+                },
+            )
+            .unwrap();
+        }
+        let res = app.clone().oneshot(screen(serde_json::json!({"imo": "9116412"}))).await.unwrap();
+        assert_eq!(hits(res).await[0]["suppressed"], true);
+
+        // So does a former name; entities with similar names are not vessels
+        let res = app
+            .clone()
+            .oneshot(screen(serde_json::json!({"name": "Grace 1", "flag": "IR"})))
+            .await
+            .unwrap();
+        let found = hits(res).await;
+        assert!(found.iter().all(|hit| hit["kind"] == "Vessel"));
+        assert_eq!(found[0]["subject_id"], "ofac_ofac_9001");
+        let explanation: Vec<&str> = found[0]["explanation"].as_array().unwrap().iter().map(|l| l.as_str().unwrap()).collect();
+        assert_eq!(explanation[0], "Matched on a former name of 'adrian darya 1'");
+        assert!(explanation.contains(&"Vessel name 'GRACE 1' is a very close match (100%)"));
+        assert!(explanation.contains(&"Flag 'IR' matches"));
+
+        // A different IMO number weighs against a name match
+        let res = app
+            .clone()
+            .oneshot(screen(serde_json::json!({"name": "Adrian Darya 1", "imo": "IMO 9999999"})))
+            .await
+            .unwrap();
+        let found = hits(res).await;
+        assert!(found[0]["explanation"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|line| line == "Listed IMO number differs"));
+
+        let res = app.oneshot(screen(serde_json::json!({"flag": "IR"}))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn screen_requires_auth() {
+        let app = build_router(test_state());
+        let body = serde_json::json!({
+            "first_name": "Maria",
+            "last_name": "Garcia",
+        });
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/persons/screen")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn screen_with_auth() {
+        let app = build_router(test_state());
+        let body = serde_json::json!({
+            "first_name": "Maria",
+            "last_name": "Garcia",
+        });
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/persons/screen")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn screen_accepts_typed_identifiers() {
+        let app = build_router(test_state());
+        let screen = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/v1/entities/screen")
+                .header("content-type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let res = app
+            .clone()
+            .oneshot(screen(serde_json::json!({
+                "name": "Acme Shipping",
+                "identifiers": [{ "type": "imo", "number": "IMO 9187629" }, { "type": "lei", "number": "5493001KJTIIGC8Y1R12" }],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = app
+            .clone()
+            .oneshot(screen(serde_json::json!({
+                "name": "Acme Shipping",
+                "identifiers": [{ "type": "imo", "number": "" }],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let res = app
+            .oneshot(screen(serde_json::json!({
+                "name": "Acme Shipping",
+                "identifiers": [{ "type": "vat", "number": "123" }],
+            })))
+            .await
+            .unwrap();
+        assert!(res.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn screening_is_audited() {
+        let app = build_router(test_state());
+        let body = serde_json::json!({
+            "reference_id": "cust-42",
+            "first_name": "Maria",
+            "last_name": "Garcia",
+        });
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/persons/screen")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let audit = |query: String| {
+            Request::builder()
+                .uri(format!("/v1/audit?{}", query))
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let page = |res: axum::response::Response| async {
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let listed = page(app.clone().oneshot(audit("limit=10".to_string())).await.unwrap()).await;
+        assert_eq!(listed["total"], 1);
+        assert_eq!(listed["entries"][0]["reference_id"], "cust-42");
+        assert_eq!(listed["entries"][0]["request_type"], "person");
+
+        // A bare to_date covers the whole of that day
+        let today = Utc::now().date_naive();
+        let listed = page(app.clone().oneshot(audit(format!("from_date={0}&to_date={0}", today))).await.unwrap()).await;
+        assert_eq!(listed["total"], 1);
+        let tomorrow = today.succ_opt().unwrap();
+        let listed = page(app.clone().oneshot(audit(format!("from_date={}", tomorrow))).await.unwrap()).await;
+        assert_eq!(listed["total"], 0);
+
+        let res = app.oneshot(audit("to_date=yesterday".to_string())).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn risk_config_put_then_get() {
+        let app = build_router(test_state());
+
+        let put = |body: serde_json::Value| {
+            Request::builder()
+                .method("PUT")
+                .uri("/v1/risk-config")
+                .header("content-type", "application/json")
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let invalid = serde_json::json!({
+            "hit_threshold": 0.8,
+            "review_threshold": 0.9,
+            "name_weight": 0.7,
+            "dob_weight": 0.1,
+            "country_weight": 0.2,
+        });
+        let res = app.clone().oneshot(put(invalid)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let valid = serde_json::json!({
+            "hit_threshold": 0.85,
+            "review_threshold": 0.7,
+            "name_weight": 0.8,
+            "dob_weight": 0.1,
+            "country_weight": 0.1,
+        });
+        let res = app.clone().oneshot(put(valid)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/risk-config")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let config: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(config["tenant_id"], "default");
+        assert_eq!(config["review_threshold"].as_f64().map(|v| (v * 100.0).round()), Some(70.0));
+    }
+
+    #[tokio::test]
+    async fn bulk_monitoring_above_the_limit_is_paced() {
+        let state = test_state();
+        {
+            let db = state.tenant_db.lock().await;
+            tenant_db::create_tenant(&db, "small", "Small Tenant", "small-key").unwrap();
+            tenant_db::update_tenant(&db, "small", None, None, Some(2)).unwrap();
+        }
+        let subjects: Vec<_> = (0..3)
+            .map(|i| serde_json::json!({ "reference_id": format!("C-{}", i), "name": format!("Person {}", i) }))
+            .collect();
+        let res = build_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/monitoring/bulk")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", "small-key")
+                    .body(Body::from(serde_json::json!({ "subjects": subjects }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Enrolled at once; the baselines are charged as they are screened
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!res.headers().contains_key("x-ratelimit-remaining"));
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let summary: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(summary["created"], 3);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_counts_batch_records() {
+        let state = test_state();
+        {
+            let db = state.tenant_db.lock().await;
+            tenant_db::create_tenant(&db, "small", "Small Tenant", "small-key").unwrap();
+            tenant_db::update_tenant(&db, "small", None, None, Some(5)).unwrap();
+        }
+        let app = build_router(state);
+
+        let batch = |n: usize| {
+            let records: Vec<_> = (0..n).map(|i| serde_json::json!({ "name": format!("Person {}", i) })).collect();
+            Request::builder()
+                .method("POST")
+                .uri("/v1/batch")
+                .header("content-type", "application/json")
+                .header("x-api-key", "small-key")
+                .body(Body::from(serde_json::json!({ "records": records }).to_string()))
+                .unwrap()
+        };
+
+        let res = app.clone().oneshot(batch(3)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["x-ratelimit-remaining"], "2");
+
+        // Larger than the limit: accepted and charged as it is screened. It
+        // waits for a full chunk's tokens, so the bucket is left alone here
+        let res = app.clone().oneshot(batch(6)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!res.headers().contains_key("x-ratelimit-remaining"));
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job["status"], "Processing");
+        assert_eq!(job["total_records"], 6);
+
+        let res = app.clone().oneshot(batch(3)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(res.headers().contains_key("retry-after"));
+        assert_eq!(res.headers()["x-ratelimit-remaining"], "0");
+
+        // Single requests still fit
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/version")
+                    .header("x-api-key", "small-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["x-ratelimit-remaining"], "1");
+    }
+
+    #[tokio::test]
+    async fn batch_results_are_stored() {
+        let app = build_router(test_state());
+
+        let records: Vec<_> = (0..5).map(|i| serde_json::json!({ "name": format!("Person {}", i) })).collect();
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/batch")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(serde_json::json!({ "records": records }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let job_id = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["job_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let get = |uri: String| {
+            Request::builder()
+                .uri(uri)
+                .header("x-api-key", "test-api-key")
+                .body(Body::empty())
+                .unwrap()
+        };
+        for _ in 0..100 {
+            let res = app.clone().oneshot(get(format!("/v1/batch/{}", job_id))).await.unwrap();
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            if status["status"] == "Completed" {
+                assert_eq!(status["processed_records"], 5);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        let res = app.oneshot(get(format!("/v1/batch/{}/results", job_id))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let results: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        let names: Vec<_> = results.iter().map(|r| r["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["Person 0", "Person 1", "Person 2", "Person 3", "Person 4"]);
+    }
+
+    #[tokio::test]
+    async fn batch_results_stream_as_ndjson() {
+        let app = build_router(test_state());
+
+        let records: Vec<_> = (0..5).map(|i| serde_json::json!({ "name": format!("Person {}", i) })).collect();
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/batch")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(serde_json::json!({ "records": records }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let job_id = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["job_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // Requested straight away, the stream follows the job until it finishes
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/v1/batch/{}/results?since=2", job_id))
+                    .header("accept", "application/x-ndjson")
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["content-type"], "application/x-ndjson");
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let lines: Vec<serde_json::Value> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let indexes: Vec<_> = lines.iter().map(|l| l["index"].as_u64().unwrap()).collect();
+        assert_eq!(indexes, [2, 3, 4]);
+        assert_eq!(lines[0]["name"], "Person 2");
+    }
+
+    #[tokio::test]
+    async fn batch_accepts_csv_uploads() {
+        let app = build_router(test_state());
+        let upload = |uri: &str, content_type: &str, body: String| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", content_type)
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let csv = "Customer,Full Name\nC-1,Maria Garcia\nC-2,John Smith\n".to_string();
+        let res = app
+            .clone()
+            .oneshot(upload(
+                "/v1/batch?name_column=Full%20Name&reference_id_column=Customer",
+                "text/csv",
+                csv.clone(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job["total_records"], 2);
+
+        let multipart = |file_name: &str, content: &str| {
+            format!(
+                "--X\r\nContent-Disposition: form-data; name=\"name_column\"\r\n\r\nFull Name\r\n\
+                 --X\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\r\n{}\r\n--X--\r\n",
+                file_name, content
+            )
+        };
+        let res = app
+            .clone()
+            .oneshot(upload("/v1/batch", "multipart/form-data; boundary=X", multipart("book.csv", &csv)))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // Windows browsers label .csv files as Excel; the file name wins
+        let labelled = multipart("book.csv", &csv).replace(
+            "filename=\"book.csv\"\r\n",
+            "filename=\"book.csv\"\r\nContent-Type: application/vnd.ms-excel\r\n",
+        );
+        let res = app
+            .clone()
+            .oneshot(upload("/v1/batch", "multipart/form-data; boundary=X", labelled))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // Workbooks are read from their first sheet
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/batch")
+                    .header(
+                        "content-type",
+                        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+                    )
+                    .header("x-api-key", "test-api-key")
+                    .body(Body::from(&include_bytes!("../testdata/batch.xlsx")[..]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job["total_records"], 2);
+
+        let res = app
+            .oneshot(upload("/v1/batch", "multipart/form-data; boundary=X", multipart("book.xlsx", "PK")))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn batch_jobs_are_tenant_scoped() {
+        let state = test_state();
+        {
+            let db = state.tenant_db.lock().await;
+            tenant_db::create_tenant(&db, "other", "Other Tenant", "other-key").unwrap();
+        }
+        let app = build_router(state);
+        let request = |method: &str, uri: &str, key: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("x-api-key", key)
+                .body(Body::from(serde_json::json!({ "records": [{ "name": "Maria Garcia" }] }).to_string()))
+                .unwrap()
+        };
+        let json = |res: axum::response::Response| async {
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let res = app.clone().oneshot(request("POST", "/v1/batch", "test-api-key")).await.unwrap();
+        let job_id = json(res).await["job_id"].as_str().unwrap().to_string();
+        let uri = format!("/v1/batch/{}", job_id);
+
+        for uri in [uri.clone(), format!("{}/results", uri)] {
+            let res = app.clone().oneshot(request("GET", &uri, "other-key")).await.unwrap();
+            assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        }
+        let res = app.clone().oneshot(request("DELETE", &uri, "other-key")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = app.clone().oneshot(request("GET", "/v1/batch", "other-key")).await.unwrap();
+        assert_eq!(json(res).await.as_array().unwrap().len(), 0);
+        let res = app.clone().oneshot(request("GET", "/v1/batch", "test-api-key")).await.unwrap();
+        assert_eq!(json(res).await[0]["job_id"], job_id.as_str());
+
+        // The owner can cancel unless the job already finished
+        let res = app.oneshot(request("DELETE", &uri, "test-api-key")).await.unwrap();
+        match res.status() {
+            StatusCode::OK => assert_eq!(json(res).await["status"], "Cancelled"),
+            status => assert_eq!(status, StatusCode::CONFLICT),
+        }
+    }
+
+    #[tokio::test]
+    async fn monitoring_portfolio_bulk_enrol_pause_and_filter() {
+        let app = build_router(test_state());
+        let request = |method: &str, uri: &str, content_type: &str, body: String| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", content_type)
+                .header("x-api-key", "test-api-key")
+                .body(Body::from(body))
+                .unwrap()
+        };
+        let json = |res: Response| async move {
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let csv = "reference_id,name,record_type,date_of_birth,segment
+                   C-1,Maria Garcia,person,1970-04-28,retail
+                   C-2,Acme Trading Ltd,entity,,corporate
+";
+        let res = app
+            .clone()
+            .oneshot(request("POST", "/v1/monitoring/bulk", "text/csv", csv.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        // Charged once per row and nothing for the request itself
+        assert_eq!(res.headers()["x-ratelimit-remaining"], "998");
+        let summary = json(res).await;
+        assert_eq!((summary["created"].clone(), summary["updated"].clone()), (2.into(), 0.into()));
+
+        // Existing reference_ids are updated in place
+        let body = serde_json::json!({"subjects": [
+            {"reference_id": "C-1", "name": "Maria Garcia Lopez", "nationality": "ES"},
+            {"reference_id": "C-3", "name": "John Smith", "dob_year": 1980},
+        ]});
+        let res = app
+            .clone()
+            .oneshot(request("POST", "/v1/monitoring/bulk", "application/json", body.to_string()))
+            .await
+            .unwrap();
+        let summary = json(res).await;
+        assert_eq!((summary["created"].clone(), summary["updated"].clone()), (1.into(), 1.into()));
+
+        // One bad row rejects the whole upload
+        let res = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/v1/monitoring/bulk",
+                "text/csv",
+                "reference_id,name
+C-4,Jane Doe
+C-5,
+".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json(res).await["details"][0], "row 2: name is empty");
+
+        let res = app
+            .clone()
+            .oneshot(request("PATCH", "/v1/monitoring/C-2", "application/json", r#"{"paused":true}"#.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(json(res).await["paused"], true);
+        let res = app
+            .clone()
+            .oneshot(request("PATCH", "/v1/monitoring/C-9", "application/json", r#"{"paused":true}"#.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = app
+            .clone()
+            .oneshot(request("GET", "/v1/monitoring?paused=true", "application/json", String::new()))
+            .await
+            .unwrap();
+        let paused = json(res).await;
+        assert_eq!(paused.as_array().unwrap().len(), 1);
+        assert_eq!(paused[0]["kind"], "Entity");
+        assert_eq!(paused[0]["metadata"]["segment"], "corporate");
+
+        let res = app
+            .clone()
+            .oneshot(request("GET", "/v1/monitoring?paused=false", "application/json", String::new()))
+            .await
+            .unwrap();
+        let active = json(res).await;
+        let maria = active.as_array().unwrap().iter().find(|e| e["reference_id"] == "C-1").unwrap();
+        assert_eq!(maria["name"], "Maria Garcia Lopez");
+        assert_eq!(maria["nationality"], "ES");
+
+        let res = app
+            .oneshot(request("GET", "/v1/monitoring?changed_since=yesterday", "application/json", String::new()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn admin_manages_tenants_and_keys() {
+        let mut state = test_state();
+        state.config.admin_key_hash = Some(admin::hash_admin_key("admin-secret"));
+        let app = build_router(state);
+
+        let admin = |method: &str, uri: &str, body: Option<serde_json::Value>| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("x-admin-key", "admin-secret")
+                .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+                .unwrap()
+        };
+        let version = |key: &str| {
+            Request::builder()
+                .uri("/v1/version")
+                .header("x-api-key", key)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let json = |res: axum::response::Response| async {
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        // Tenant keys are not admin credentials
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/v1/tenants")
+                    .header("x-admin-key", "test-api-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let body = serde_json::json!({ "id": "acme", "name": "Acme Bank" });
+        let res = app.clone().oneshot(admin("POST", "/admin/v1/tenants", Some(body))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let created = json(res).await;
+        let first_key = created["api_key"].as_str().unwrap().to_string();
+        let first_key_id = created["key"]["id"].as_str().unwrap().to_string();
+        assert_eq!(app.clone().oneshot(version(&first_key)).await.unwrap().status(), StatusCode::OK);
+
+        // Rotate: issue a second key, revoke the first
+        let res = app
+            .clone()
+            .oneshot(admin("POST", "/admin/v1/tenants/acme/keys", Some(serde_json::json!({ "name": "rotated" }))))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let second_key = json(res).await["api_key"].as_str().unwrap().to_string();
+        let uri = format!("/admin/v1/tenants/acme/keys/{}", first_key_id);
+        let res = app.clone().oneshot(admin("DELETE", &uri, None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(app.clone().oneshot(version(&first_key)).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(app.clone().oneshot(version(&second_key)).await.unwrap().status(), StatusCode::OK);
+
+        // Plaintext keys are never listed
+        let res = app.clone().oneshot(admin("GET", "/admin/v1/tenants/acme/keys", None)).await.unwrap();
+        let listed = json(res).await.to_string();
+        assert!(!listed.contains(&second_key));
+
+        // Billable usage, per endpoint and key
+        let res = app.clone().oneshot(admin("GET", "/admin/v1/tenants/acme/usage", None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let usage = json(res).await;
+        assert_eq!(usage["total"], 2);
+        assert_eq!(usage["usage"].as_array().unwrap().len(), 2);
+        assert_eq!(usage["usage"][0]["endpoint"], "GET /v1/version");
+        let today = Utc::now().date_naive();
+        let uri = format!("/admin/v1/tenants/acme/usage?from_date={}", today.succ_opt().unwrap());
+        assert_eq!(json(app.clone().oneshot(admin("GET", &uri, None)).await.unwrap()).await["total"], 0);
+        let uri = format!("/admin/v1/tenants/acme/usage?from_date={0}&to_date={0}", today);
+        assert_eq!(json(app.clone().oneshot(admin("GET", &uri, None)).await.unwrap()).await["total"], 2);
+
+        // Suspension applies to the very next request
+        let res = app
+            .clone()
+            .oneshot(admin("PATCH", "/admin/v1/tenants/acme", Some(serde_json::json!({ "is_active": false }))))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(app.oneshot(version(&second_key)).await.unwrap().status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn scopes_limit_routes() {
+        let state = test_state();
+        let screen_only = {
+            let db = state.tenant_db.lock().await;
+            tenant_db::create_api_key(&db, "default", Some("onboarding"), None, &[Scope::Screen])
+                .unwrap()
+                .api_key
+        };
+        let app = build_router(state);
+
+        let body = serde_json::json!({ "first_name": "Maria", "last_name": "Garcia" });
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/persons/screen")
+                    .header("content-type", "application/json")
+                    .header("x-api-key", &screen_only)
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/audit")
+                    .header("x-api-key", &screen_only)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["message"], "insufficient_scope");
+        assert!(error["details"][0].as_str().unwrap().contains("read-audit"));
     }
 }
 
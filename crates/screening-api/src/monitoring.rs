@@ -0,0 +1,393 @@
+//! Monitoring portfolio management: bulk enrolment from JSON or CSV, pausing
+//! subjects, and the baseline screening every enrolled or updated subject gets.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use aegistry_core::new_request_id;
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use ingest::monitoring::{
+    get_monitored_subject, hits_result_hash, record_monitoring_result, reportable_hits, set_subject_paused,
+    upsert_monitored_subjects, SubjectAttributes,
+};
+use matching_core::{BirthDate, ScreeningQuery};
+use serde::Deserialize;
+
+use crate::auth::ApiKeyAuth;
+use crate::batch::RecordType;
+use crate::batch_csv::{read_upload, upload_error, Upload};
+use crate::risk::RiskConfig;
+use crate::{audit, cases, rate_limit, risk, AddMonitoringRequest, ApiError, AppState, MonitoringEntry};
+
+/// Bulk uploads are far larger than the default request body limit allows
+pub const BULK_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// At most this many row errors are reported for a rejected upload
+const MAX_REPORTED_ERRORS: usize = 100;
+
+/// CSV columns read into subject fields; any other column becomes metadata
+const CSV_COLUMNS: [&str; 8] = [
+    "reference_id",
+    "name",
+    "record_type",
+    "country",
+    "date_of_birth",
+    "dob_year",
+    "nationality",
+    "callback_url",
+];
+
+#[derive(Debug, Deserialize)]
+pub struct BulkMonitoringRequest {
+    pub subjects: Vec<AddMonitoringRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMonitoringRequest {
+    pub paused: bool,
+}
+
+impl AddMonitoringRequest {
+    pub fn attributes(&self) -> SubjectAttributes {
+        SubjectAttributes {
+            name: self.name.clone(),
+            kind: self.record_type.subject_kind(),
+            country: self.country.clone(),
+            date_of_birth: self
+                .date_of_birth
+                .clone()
+                .or_else(|| self.dob_year.map(|y| y.to_string())),
+            nationality: self.nationality.clone(),
+            callback_url: self.callback_url.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Problems with this subject, prefixed with `label` to locate it
+    pub(crate) fn problems(&self, label: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.reference_id.trim().is_empty() {
+            problems.push(format!("{}: reference_id is empty", label));
+        }
+        if self.name.trim().is_empty() {
+            problems.push(format!("{}: name is empty", label));
+        }
+        if let Some(dob) = &self.date_of_birth {
+            if BirthDate::parse(dob).is_none() {
+                problems.push(format!("{}: date_of_birth '{}' is not YYYY, YYYY-MM or YYYY-MM-DD", label, dob));
+            }
+        }
+        problems
+    }
+}
+
+/// Parse subjects from CSV. Headers are matched case-insensitively; columns
+/// other than the subject fields are kept as per-subject metadata.
+pub fn parse_csv(data: &[u8]) -> Result<Vec<AddMonitoringRequest>, (StatusCode, Json<ApiError>)> {
+    let invalid = |detail: String| upload_error(StatusCode::UNPROCESSABLE_ENTITY, "invalid_csv", detail);
+
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(data);
+    let headers = reader.headers().map_err(|e| invalid(format!("header: {}", e)))?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    for required in ["reference_id", "name"] {
+        if column(required).is_none() {
+            return Err(invalid(format!("header: no '{}' column", required)));
+        }
+    }
+    let metadata_columns: Vec<(usize, &str)> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !CSV_COLUMNS.iter().any(|c| h.eq_ignore_ascii_case(c)))
+        .collect();
+
+    let mut subjects = Vec::new();
+    for (i, row) in reader.records().enumerate() {
+        let row_number = i + 1;
+        let row = row.map_err(|e| invalid(format!("row {}: {}", row_number, e)))?;
+        let field = |name: &str| {
+            column(name)
+                .and_then(|idx| row.get(idx))
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        let record_type = match field("record_type").map(|v| v.to_ascii_lowercase()).as_deref() {
+            None | Some("person") => RecordType::Person,
+            Some("entity") => RecordType::Entity,
+            Some(other) => {
+                return Err(invalid(format!(
+                    "row {}: record_type '{}' is not 'person' or 'entity'",
+                    row_number, other
+                )))
+            }
+        };
+        let dob_year = match field("dob_year") {
+            Some(year) => Some(
+                year.parse()
+                    .map_err(|_| invalid(format!("row {}: dob_year '{}' is not a year", row_number, year)))?,
+            ),
+            None => None,
+        };
+        let metadata: BTreeMap<String, String> = metadata_columns
+            .iter()
+            .filter_map(|(idx, header)| {
+                row.get(*idx)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| (header.to_string(), v.to_string()))
+            })
+            .collect();
+
+        subjects.push(AddMonitoringRequest {
+            reference_id: field("reference_id").unwrap_or_default(),
+            name: field("name").unwrap_or_default(),
+            record_type,
+            country: field("country"),
+            dob_year,
+            date_of_birth: field("date_of_birth"),
+            nationality: field("nationality"),
+            callback_url: field("callback_url"),
+            metadata,
+        });
+    }
+    Ok(subjects)
+}
+
+/// `POST /v1/monitoring/bulk`: enrol or update many subjects from a JSON
+/// body (`{"subjects": [...]}`), a CSV body or a multipart CSV upload. The
+/// upload is all-or-nothing; baseline screening runs in the background.
+pub async fn bulk_add_monitoring(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    request: Request,
+) -> Result<Response, Response> {
+    let subjects = match read_upload::<BulkMonitoringRequest>(request, |_, _| false)
+        .await
+        .map_err(IntoResponse::into_response)?
+    {
+        Upload::Json(req) => req.subjects,
+        Upload::Csv(data) => parse_csv(&data).map_err(IntoResponse::into_response)?,
+    };
+
+    let mut problems: Vec<String> = subjects
+        .iter()
+        .enumerate()
+        .flat_map(|(i, s)| s.problems(&format!("row {}", i + 1)))
+        .collect();
+    if subjects.is_empty() {
+        problems.push("subjects: upload contains no subjects".to_string());
+    }
+    if !problems.is_empty() {
+        problems.truncate(MAX_REPORTED_ERRORS);
+        let error = ApiError {
+            message: "validation_error".to_string(),
+            details: problems,
+        };
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response());
+    }
+
+    // Every subject gets a baseline screening, charged like a batch record:
+    // up front within one minute's allowance, otherwise as the baselines run
+    let cost = subjects.len() as u32;
+    let (remaining, paced_api_key_id) = if cost > auth.rate_limit_per_minute {
+        (None, Some(auth.api_key_id.clone()))
+    } else {
+        (Some(rate_limit::charge(&state, &auth, "POST /v1/monitoring/bulk", cost).await?), None)
+    };
+
+    let risk_config = state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map_err(|e| risk::risk_store_error(e).into_response())?;
+
+    let enrolled: Vec<(String, SubjectAttributes)> = subjects
+        .iter()
+        .map(|s| (s.reference_id.clone(), s.attributes()))
+        .collect();
+    let upserted = {
+        let db = state.monitoring_db.lock().await;
+        upsert_monitored_subjects(&db, &auth.tenant_id, &enrolled).map_err(|e| monitoring_error(e).into_response())?
+    };
+    let created = upserted.iter().filter(|(_, created)| *created).count();
+    tracing::info!(
+        tenant_id = %auth.tenant_id,
+        total = enrolled.len(),
+        created,
+        "bulk monitoring enrolment"
+    );
+
+    let response = serde_json::json!({
+        "status": "accepted",
+        "total": enrolled.len(),
+        "created": created,
+        "updated": enrolled.len() - created,
+    });
+
+    let tenant_id = auth.tenant_id;
+    tokio::spawn(async move {
+        let concurrency = state.config.batch_concurrency.max(1);
+        let work: Vec<_> = upserted.into_iter().map(|(id, _)| id).zip(enrolled).collect();
+        for chunk in work.chunks(concurrency) {
+            if let Some(api_key_id) = &paced_api_key_id {
+                let cost = chunk.len() as u32;
+                if !rate_limit::charge_paced(&state, &tenant_id, api_key_id, "POST /v1/monitoring/bulk", cost).await {
+                    tracing::warn!(tenant_id = %tenant_id, "tenant no longer active, bulk baseline screening stopped");
+                    return;
+                }
+            }
+            let screenings = chunk.iter().map(|(id, (reference_id, attributes))| {
+                screen_baseline(&state, &tenant_id, &risk_config, *id, reference_id, attributes)
+            });
+            futures::future::join_all(screenings).await;
+        }
+        tracing::info!(tenant_id = %tenant_id, subjects = work.len(), "bulk baseline screening completed");
+    });
+
+    let mut response = Json(response).into_response();
+    if let Some(remaining) = remaining {
+        response.headers_mut().insert(rate_limit::REMAINING_HEADER, remaining.into());
+    }
+    Ok(response)
+}
+
+/// `PATCH /v1/monitoring/:reference_id`: pause or resume re-screening
+pub async fn update_monitoring(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(reference_id): Path<String>,
+    Json(req): Json<UpdateMonitoringRequest>,
+) -> Result<Json<MonitoringEntry>, (StatusCode, Json<ApiError>)> {
+    let db = state.monitoring_db.lock().await;
+    if !set_subject_paused(&db, &auth.tenant_id, &reference_id, req.paused).map_err(monitoring_error)? {
+        return Err(not_found(&reference_id));
+    }
+    tracing::info!(reference_id = %reference_id, paused = req.paused, "updated monitoring");
+    let subject = get_monitored_subject(&db, &auth.tenant_id, &reference_id)
+        .map_err(monitoring_error)?
+        .ok_or_else(|| not_found(&reference_id))?;
+    Ok(Json(MonitoringEntry::from(subject)))
+}
+
+/// Screen a newly enrolled or updated subject and record the result as the
+/// baseline later re-screening compares against. Audited and turned into
+/// cases like any other screening.
+pub async fn screen_baseline(
+    state: &AppState,
+    tenant_id: &str,
+    risk_config: &RiskConfig,
+    subject_id: i64,
+    reference_id: &str,
+    attributes: &SubjectAttributes,
+) {
+    let start = Instant::now();
+    let query = ScreeningQuery {
+        name: attributes.name.clone(),
+        country: attributes.country.clone(),
+        dob: attributes.date_of_birth.as_deref().and_then(BirthDate::parse),
+        kind: Some(attributes.kind),
+        identifiers: Vec::new(),
+    };
+    // Without a baseline the next ingest re-screen records the first result
+    let Ok(hits) = crate::perform_screening(state, risk_config, Some(reference_id), &attributes.name, query).await else {
+        tracing::warn!(reference_id, "baseline screening failed");
+        return;
+    };
+
+    // Hash and store the same reportable hits ingest re-screening compares against
+    let reportable = reportable_hits(hits.clone());
+    let recorded = {
+        let db = state.monitoring_db.lock().await;
+        record_monitoring_result(
+            &db,
+            subject_id,
+            &hits_result_hash(&reportable),
+            reportable.len(),
+            reportable.iter().map(|h| h.score).fold(0.0, f32::max),
+            false, // No changes on a baseline screening
+            Some(&reportable),
+        )
+    };
+    if let Err(e) = recorded {
+        tracing::warn!(error = %e, reference_id, "failed to record baseline monitoring result");
+    }
+
+    let request_id = new_request_id();
+    let entry = audit::AuditEntry::screening(
+        tenant_id,
+        "monitoring",
+        &request_id,
+        Some(reference_id),
+        &hits,
+        start.elapsed(),
+    )
+    .with_payloads(
+        &serde_json::json!({
+            "reference_id": reference_id,
+            "name": attributes.name,
+            "kind": attributes.kind,
+            "country": attributes.country,
+            "date_of_birth": attributes.date_of_birth,
+            "nationality": attributes.nationality,
+        }),
+        &hits,
+    );
+    audit::record(state, entry).await;
+    cases::record(state, tenant_id, &request_id, Some(reference_id), &attributes.name, &hits).await;
+}
+
+fn not_found(reference_id: &str) -> (StatusCode, Json<ApiError>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiError {
+            message: "not_found".to_string(),
+            details: vec![format!("Monitoring entry {} not found", reference_id)],
+        }),
+    )
+}
+
+pub(crate) fn monitoring_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "monitoring store error");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "monitoring_error".to_string(),
+            details: vec![e.to_string()],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_extra_columns_become_metadata() {
+        let data = "Reference_ID,Name,Record_Type,Date_of_Birth,Segment,Branch\n\
+                    C-1,Maria Garcia,,1970-04-28,retail,\n\
+                    C-2,Acme Trading Ltd,entity,,corporate,Madrid\n";
+
+        let subjects = parse_csv(data.as_bytes()).unwrap();
+        assert_eq!(subjects.len(), 2);
+        assert_eq!(subjects[0].reference_id, "C-1");
+        assert_eq!(subjects[0].date_of_birth.as_deref(), Some("1970-04-28"));
+        assert_eq!(subjects[0].metadata.len(), 1);
+        assert_eq!(subjects[0].metadata["Segment"], "retail");
+        assert!(matches!(subjects[1].record_type, RecordType::Entity));
+        assert_eq!(subjects[1].metadata["Branch"], "Madrid");
+    }
+
+    #[test]
+    fn problems_name_the_row() {
+        let subjects = parse_csv(b"reference_id,name,date_of_birth\n,Maria Garcia,\nC-2,John Smith,28/04/1970\n").unwrap();
+        assert_eq!(subjects[0].problems("row 1"), vec!["row 1: reference_id is empty"]);
+        assert!(subjects[1].problems("row 2")[0].starts_with("row 2: date_of_birth"));
+
+        let err = parse_csv(b"customer,name\nC-1,Maria Garcia\n").unwrap_err();
+        assert!(err.1.details[0].contains("'reference_id'"));
+    }
+}
@@ -0,0 +1,191 @@
+use axum::{
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use metrics::counter;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::auth::ApiKeyAuth;
+use crate::{tenant_db, ApiError, AppState};
+
+pub const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+
+/// Per-tenant token buckets. Each bucket holds up to `rate_limit_per_minute`
+/// tokens and refills continuously at that rate, so a tenant can burst its
+/// full minute allowance and then proceeds at the steady rate.
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RateDecision {
+    Allowed { remaining: u32 },
+    /// Not enough tokens; the request can be retried after this many seconds
+    Limited { retry_after_secs: u64 },
+    /// The cost exceeds the bucket size and can never be admitted at once
+    TooLarge,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn try_acquire(&self, tenant_id: &str, limit_per_minute: u32, cost: u32) -> RateDecision {
+        self.try_acquire_at(Instant::now(), tenant_id, limit_per_minute, cost)
+    }
+
+    fn try_acquire_at(&self, now: Instant, tenant_id: &str, limit_per_minute: u32, cost: u32) -> RateDecision {
+        let capacity = limit_per_minute as f64;
+        if cost as f64 > capacity {
+            return RateDecision::TooLarge;
+        }
+        let per_second = capacity / 60.0;
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(tenant_id.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+        // min() also applies a lowered limit straight away
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= cost as f64 {
+            bucket.tokens -= cost as f64;
+            RateDecision::Allowed {
+                remaining: bucket.tokens.floor() as u32,
+            }
+        } else {
+            let missing = cost as f64 - bucket.tokens;
+            RateDecision::Limited {
+                retry_after_secs: (missing / per_second).ceil().max(1.0) as u64,
+            }
+        }
+    }
+}
+
+/// Take `cost` tokens from the caller's bucket and record the usage against
+/// its API key. Returns the tokens left, or the response to send instead.
+pub async fn charge(state: &AppState, auth: &ApiKeyAuth, endpoint: &str, cost: u32) -> Result<u32, Response> {
+    let remaining = match state
+        .rate_limiter
+        .try_acquire(&auth.tenant_id, auth.rate_limit_per_minute, cost)
+    {
+        RateDecision::Allowed { remaining } => remaining,
+        RateDecision::Limited { retry_after_secs } => {
+            counter!("rate_limited_total").increment(1);
+            tracing::info!(tenant_id = %auth.tenant_id, endpoint, cost, retry_after_secs, "rate limited");
+            return Err(too_many_requests(retry_after_secs, auth.rate_limit_per_minute));
+        }
+        RateDecision::TooLarge => {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ApiError {
+                    message: "request_too_large".to_string(),
+                    details: vec![format!(
+                        "Request counts as {} requests, above the limit of {} per minute",
+                        cost, auth.rate_limit_per_minute
+                    )],
+                }),
+            )
+                .into_response());
+        }
+    };
+
+    let db = state.tenant_db.lock().await;
+    if let Err(e) = tenant_db::log_usage(&db, &auth.tenant_id, Some(&auth.api_key_id), endpoint, cost as i64) {
+        tracing::error!(error = %e, tenant_id = %auth.tenant_id, endpoint, "failed to record usage");
+    }
+    Ok(remaining)
+}
+
+/// Take `cost` tokens for work the tenant has already been admitted for,
+/// such as a batch too large to charge on submission. Draws at most one
+/// minute's allowance at a time and waits for the bucket to refill, so the
+/// work proceeds at the tenant's rate. Usage is recorded as tokens are taken.
+/// Returns false if the tenant is gone or deactivated meanwhile.
+pub async fn charge_paced(state: &AppState, tenant_id: &str, api_key_id: &str, endpoint: &str, cost: u32) -> bool {
+    let mut owed = cost;
+    while owed > 0 {
+        // Re-read each time so a changed limit applies to the rest of the work
+        let limit = match tenant_db::get_tenant(&*state.tenant_db.lock().await, tenant_id) {
+            Some(tenant) if tenant.is_active && tenant.rate_limit_per_minute > 0 => tenant.rate_limit_per_minute,
+            _ => return false,
+        };
+        let take = owed.min(limit);
+        match state.rate_limiter.try_acquire(tenant_id, limit, take) {
+            RateDecision::Allowed { .. } => {
+                let db = state.tenant_db.lock().await;
+                if let Err(e) = tenant_db::log_usage(&db, tenant_id, Some(api_key_id), endpoint, take as i64) {
+                    tracing::error!(error = %e, tenant_id, endpoint, "failed to record usage");
+                }
+                owed -= take;
+            }
+            RateDecision::Limited { retry_after_secs } => {
+                tokio::time::sleep(tokio::time::Duration::from_secs(retry_after_secs)).await;
+            }
+            RateDecision::TooLarge => return false,
+        }
+    }
+    true
+}
+
+fn too_many_requests(retry_after_secs: u64, limit_per_minute: u32) -> Response {
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [
+            (header::RETRY_AFTER, HeaderValue::from(retry_after_secs)),
+            (REMAINING_HEADER, HeaderValue::from(0)),
+        ],
+        Json(ApiError {
+            message: "rate_limited".to_string(),
+            details: vec![format!("Rate limit of {} requests per minute exceeded", limit_per_minute)],
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn bucket_refills_at_the_minute_rate() {
+        let limiter = RateLimiter::new();
+        let start = Instant::now();
+
+        assert_eq!(limiter.try_acquire_at(start, "t1", 60, 59), RateDecision::Allowed { remaining: 1 });
+        assert_eq!(limiter.try_acquire_at(start, "t1", 60, 1), RateDecision::Allowed { remaining: 0 });
+        assert_eq!(
+            limiter.try_acquire_at(start, "t1", 60, 5),
+            RateDecision::Limited { retry_after_secs: 5 }
+        );
+        // Other tenants have their own bucket
+        assert_eq!(limiter.try_acquire_at(start, "t2", 60, 1), RateDecision::Allowed { remaining: 59 });
+
+        // 60/min refills one token per second
+        let later = start + Duration::from_secs(5);
+        assert_eq!(limiter.try_acquire_at(later, "t1", 60, 5), RateDecision::Allowed { remaining: 0 });
+    }
+
+    #[test]
+    fn cost_above_capacity_is_rejected() {
+        let limiter = RateLimiter::new();
+        assert_eq!(limiter.try_acquire("t1", 10, 11), RateDecision::TooLarge);
+        // Nothing was taken
+        assert_eq!(limiter.try_acquire("t1", 10, 10), RateDecision::Allowed { remaining: 0 });
+    }
+}
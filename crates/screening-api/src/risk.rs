@@ -1,9 +1,15 @@
+use aegistry_core::RiskLevel;
 use anyhow::Result;
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use matching_core::{ScoreThresholds, ScoreWeights};
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::auth::ApiKeyAuth;
+use crate::{ApiError, AppState};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskConfig {
     pub tenant_id: String,
@@ -16,17 +22,80 @@ pub struct RiskConfig {
 
 impl Default for RiskConfig {
     fn default() -> Self {
+        let (thresholds, weights) = (ScoreThresholds::default(), ScoreWeights::default());
         Self {
             tenant_id: String::new(),
-            hit_threshold: 0.95,
-            review_threshold: 0.90,
-            name_weight: 0.70,
-            dob_weight: 0.10,
-            country_weight: 0.20,
+            hit_threshold: thresholds.hit,
+            review_threshold: thresholds.review,
+            name_weight: weights.name,
+            dob_weight: weights.dob,
+            country_weight: weights.country,
         }
     }
 }
 
+impl RiskConfig {
+    pub fn weights(&self) -> ScoreWeights {
+        ScoreWeights {
+            name: self.name_weight,
+            dob: self.dob_weight,
+            country: self.country_weight,
+        }
+    }
+
+    pub fn thresholds(&self) -> ScoreThresholds {
+        ScoreThresholds {
+            hit: self.hit_threshold,
+            review: self.review_threshold,
+        }
+    }
+
+    pub fn risk_level(&self, score: f32) -> RiskLevel {
+        if score >= self.hit_threshold {
+            RiskLevel::Hit
+        } else if score >= self.review_threshold {
+            RiskLevel::Review
+        } else {
+            RiskLevel::None
+        }
+    }
+
+    /// Problems that would make this config unusable, empty when valid
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (field, value) in [
+            ("hit_threshold", self.hit_threshold),
+            ("review_threshold", self.review_threshold),
+            ("name_weight", self.name_weight),
+            ("dob_weight", self.dob_weight),
+            ("country_weight", self.country_weight),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                errors.push(format!("{}: must be between 0 and 1", field));
+            }
+        }
+        // Without a review band, capped matches would have nowhere to go
+        if self.review_threshold >= self.hit_threshold {
+            errors.push("review_threshold: must be below hit_threshold".to_string());
+        }
+        let total = self.name_weight + self.dob_weight + self.country_weight;
+        if (total - 1.0).abs() > 0.001 {
+            errors.push(format!("weights: must sum to 1.0, got {:.3}", total));
+        }
+        errors
+    }
+}
+
+/// Body of `PUT /v1/risk-config`; the tenant comes from the API key
+#[derive(Debug, Deserialize)]
+pub struct RiskConfigRequest {
+    pub hit_threshold: f32,
+    pub review_threshold: f32,
+    pub name_weight: f32,
+    pub dob_weight: f32,
+    pub country_weight: f32,
+}
+
 pub struct RiskStore {
     conn: Arc<Mutex<Connection>>,
 }
@@ -101,5 +170,113 @@ impl RiskStore {
     }
 }
 
+pub async fn get_risk_config(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+) -> Result<Json<RiskConfig>, (StatusCode, Json<ApiError>)> {
+    state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map(Json)
+        .map_err(risk_store_error)
+}
+
+pub async fn put_risk_config(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Json(req): Json<RiskConfigRequest>,
+) -> Result<Json<RiskConfig>, (StatusCode, Json<ApiError>)> {
+    let config = RiskConfig {
+        tenant_id: auth.tenant_id,
+        hit_threshold: req.hit_threshold,
+        review_threshold: req.review_threshold,
+        name_weight: req.name_weight,
+        dob_weight: req.dob_weight,
+        country_weight: req.country_weight,
+    };
+
+    let errors = config.validate();
+    if !errors.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                message: "invalid_request".to_string(),
+                details: errors,
+            }),
+        ));
+    }
+
+    state.risk_store.set_config(&config).await.map_err(risk_store_error)?;
+    tracing::info!(
+        tenant_id = %config.tenant_id,
+        hit_threshold = config.hit_threshold,
+        review_threshold = config.review_threshold,
+        "updated risk config"
+    );
+    Ok(Json(config))
+}
+
+pub(crate) fn risk_store_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "risk config store failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "risk_config_error".to_string(),
+            details: vec![format!("Failed to access risk config: {}", e)],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> RiskStore {
+        let conn = Connection::open_in_memory().unwrap();
+        RiskStore::init_schema(&conn).unwrap();
+        RiskStore::new(conn)
+    }
+
+    #[tokio::test]
+    async fn config_round_trip_per_tenant() {
+        let store = store();
+        let config = RiskConfig {
+            tenant_id: "t1".to_string(),
+            hit_threshold: 0.85,
+            review_threshold: 0.70,
+            name_weight: 0.80,
+            dob_weight: 0.10,
+            country_weight: 0.10,
+        };
+        store.set_config(&config).await.unwrap();
 
+        let loaded = store.get_config("t1").await.unwrap();
+        assert_eq!(loaded.hit_threshold, 0.85);
+        assert!(matches!(loaded.risk_level(0.86), RiskLevel::Hit));
 
+        // Other tenants keep the defaults
+        let other = store.get_config("t2").await.unwrap();
+        assert_eq!(other.hit_threshold, 0.95);
+        assert!(matches!(other.risk_level(0.86), RiskLevel::None));
+    }
+
+    #[test]
+    fn validate_rejects_inverted_thresholds_and_bad_weights() {
+        let config = RiskConfig {
+            hit_threshold: 0.80,
+            review_threshold: 0.90,
+            name_weight: 0.90,
+            ..Default::default()
+        };
+        let errors = config.validate();
+        assert_eq!(errors.len(), 2);
+        assert!(RiskConfig::default().validate().is_empty());
+
+        let equal = RiskConfig {
+            review_threshold: 0.95,
+            ..Default::default()
+        };
+        assert_eq!(equal.validate(), ["review_threshold: must be below hit_threshold"]);
+    }
+}
@@ -0,0 +1,370 @@
+use aegistry_core::Hit;
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use matching_core::normalize_name;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::ApiKeyAuth;
+use crate::{ApiError, AppState};
+
+/// A cleared (customer, listed subject) pair. The customer is identified by
+/// `reference_id` when the screening carried one, otherwise by the normalized
+/// input name. Only valid for the subject record it was made against.
+#[derive(Debug, Serialize)]
+pub struct Suppression {
+    pub id: String,
+    pub tenant_id: String,
+    pub reference_id: Option<String>,
+    pub normalized_name: Option<String>,
+    pub subject_id: String,
+    pub record_version: String,
+    pub reason: String,
+    pub alert_id: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub invalidated_at: Option<String>,
+    pub invalidated_reason: Option<String>,
+}
+
+pub fn init_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS suppression (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            reference_id TEXT,
+            normalized_name TEXT,
+            subject_id TEXT NOT NULL,
+            record_version TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            alert_id TEXT,
+            created_by TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            invalidated_at TEXT,
+            invalidated_reason TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_suppression_subject
+            ON suppression(tenant_id, subject_id, invalidated_at);
+        "
+    )?;
+    Ok(())
+}
+
+/// Fields of a suppression to create. `reference_id` takes precedence over
+/// `input_name` as the customer key.
+pub struct NewSuppression<'a> {
+    pub tenant_id: &'a str,
+    pub reference_id: Option<&'a str>,
+    pub input_name: &'a str,
+    pub subject_id: &'a str,
+    pub record_version: Option<&'a str>,
+    pub reason: &'a str,
+    pub alert_id: Option<&'a str>,
+    pub created_by: &'a str,
+}
+
+pub fn create(conn: &Connection, new: &NewSuppression) -> Result<String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let normalized_name = match new.reference_id {
+        Some(_) => None,
+        None => Some(normalize_name(new.input_name)),
+    };
+    conn.execute(
+        "INSERT INTO suppression (id, tenant_id, reference_id, normalized_name, subject_id,
+                                  record_version, reason, alert_id, created_by)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![
+            id,
+            new.tenant_id,
+            new.reference_id,
+            normalized_name,
+            new.subject_id,
+            new.record_version.unwrap_or_default(),
+            new.reason,
+            new.alert_id,
+            new.created_by,
+        ],
+    )?;
+    tracing::info!(
+        tenant_id = new.tenant_id,
+        subject_id = new.subject_id,
+        suppression_id = %id,
+        "created suppression"
+    );
+    Ok(id)
+}
+
+/// Mark hits the tenant has already cleared for this customer. A customer
+/// with a `reference_id` is only covered by suppressions made under it;
+/// name-keyed ones cover screenings without a reference. A suppression made
+/// against an older version of the subject record is invalidated here
+/// instead, so the hit surfaces again for review.
+pub fn apply(
+    conn: &Connection,
+    tenant_id: &str,
+    reference_id: Option<&str>,
+    input_name: &str,
+    hits: &mut [Hit],
+) -> Result<()> {
+    let normalized_name = normalize_name(input_name);
+    let mut stmt = conn.prepare(
+        "SELECT id, record_version, reason FROM suppression
+         WHERE tenant_id = ?1 AND subject_id = ?2 AND invalidated_at IS NULL
+           AND (reference_id = ?3 OR (?3 IS NULL AND reference_id IS NULL AND normalized_name = ?4))",
+    )?;
+
+    for hit in hits.iter_mut() {
+        let rows = stmt
+            .query_map(
+                rusqlite::params![tenant_id, hit.subject_id, reference_id, normalized_name],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)),
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let current_version = hit.record_version.as_deref().unwrap_or_default();
+        for (id, record_version, reason) in rows {
+            if record_version == current_version {
+                hit.suppressed = true;
+                hit.suppression_reason = Some(reason);
+            } else {
+                conn.execute(
+                    "UPDATE suppression SET invalidated_at = datetime('now'), invalidated_reason = ?1
+                     WHERE id = ?2",
+                    rusqlite::params!["subject record changed", id],
+                )?;
+                tracing::info!(
+                    tenant_id,
+                    subject_id = %hit.subject_id,
+                    suppression_id = %id,
+                    "suppression invalidated by subject record change"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn list(conn: &Connection, tenant_id: &str, include_invalidated: bool) -> Result<Vec<Suppression>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, tenant_id, reference_id, normalized_name, subject_id, record_version, reason,
+                alert_id, created_by, created_at, invalidated_at, invalidated_reason
+         FROM suppression
+         WHERE tenant_id = ?1 AND (?2 OR invalidated_at IS NULL)
+         ORDER BY created_at DESC",
+    )?;
+    let suppressions = stmt
+        .query_map(rusqlite::params![tenant_id, include_invalidated], |row| {
+            Ok(Suppression {
+                id: row.get(0)?,
+                tenant_id: row.get(1)?,
+                reference_id: row.get(2)?,
+                normalized_name: row.get(3)?,
+                subject_id: row.get(4)?,
+                record_version: row.get(5)?,
+                reason: row.get(6)?,
+                alert_id: row.get(7)?,
+                created_by: row.get(8)?,
+                created_at: row.get(9)?,
+                invalidated_at: row.get(10)?,
+                invalidated_reason: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(suppressions)
+}
+
+/// Withdraw an active suppression. Returns false if none matched.
+pub fn revoke(conn: &Connection, tenant_id: &str, id: &str) -> Result<bool> {
+    let rows = conn.execute(
+        "UPDATE suppression SET invalidated_at = datetime('now'), invalidated_reason = 'revoked'
+         WHERE tenant_id = ?1 AND id = ?2 AND invalidated_at IS NULL",
+        [tenant_id, id],
+    )?;
+    Ok(rows > 0)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuppressionQueryParams {
+    #[serde(default)]
+    pub include_invalidated: bool,
+}
+
+pub async fn list_suppressions(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Query(params): Query<SuppressionQueryParams>,
+) -> Result<Json<Vec<Suppression>>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    list(&db, &auth.tenant_id, params.include_invalidated)
+        .map(Json)
+        .map_err(suppression_store_error)
+}
+
+pub async fn revoke_suppression(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    let db = state.tenant_db.lock().await;
+    match revoke(&db, &auth.tenant_id, &id) {
+        Ok(true) => {
+            tracing::info!(tenant_id = %auth.tenant_id, suppression_id = %id, "revoked suppression");
+            Ok(Json(serde_json::json!({ "status": "revoked", "id": id })))
+        }
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                message: "not_found".to_string(),
+                details: vec![format!("Active suppression {} not found", id)],
+            }),
+        )),
+        Err(e) => Err(suppression_store_error(e)),
+    }
+}
+
+fn suppression_store_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    tracing::error!(error = %e, "suppression store failed");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError {
+            message: "suppression_error".to_string(),
+            details: vec![format!("Failed to access suppressions: {}", e)],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aegistry_core::{HitSource, RiskLevel, ScoreComponents, SubjectKind};
+
+    fn hit(subject_id: &str, record_version: &str) -> Hit {
+        Hit {
+            subject_id: subject_id.to_string(),
+            matched_name: "Ivan Petrov".to_string(),
+            source: HitSource::Ofac,
+            kind: SubjectKind::Person,
+            score: 0.96,
+            risk_level: RiskLevel::Hit,
+            components: ScoreComponents {
+                name_similarity: 0.96,
+                dob_similarity: 0.0,
+                country_match: 0.0,
+                phonetic_match: true,
+                identifier_match: 0.0,
+            },
+            explanation: vec![],
+            matched_alias: None,
+            record_version: Some(record_version.to_string()),
+            suppressed: false,
+            suppression_reason: None,
+        }
+    }
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn suppression_applies_per_customer() {
+        let conn = conn();
+        create(
+            &conn,
+            &NewSuppression {
+                tenant_id: "t1",
+                reference_id: Some("cust-1"),
+                input_name: "Ivan Petrov",
+                subject_id: "ofac_1",
+                record_version: Some("v1"),
+                reason: "different DOB",
+                alert_id: None,
+                created_by: "alice",
+            },
+        )
+        .unwrap();
+
+        let mut hits = vec![hit("ofac_1", "v1"), hit("ofac_2", "v1")];
+        apply(&conn, "t1", Some("cust-1"), "Ivan Petrov", &mut hits).unwrap();
+        assert!(hits[0].suppressed);
+        assert_eq!(hits[0].suppression_reason.as_deref(), Some("different DOB"));
+        assert!(!hits[1].suppressed);
+
+        // A different customer with the same name is not covered
+        let mut hits = vec![hit("ofac_1", "v1")];
+        apply(&conn, "t1", Some("cust-2"), "Ivan Petrov", &mut hits).unwrap();
+        assert!(!hits[0].suppressed);
+
+        // Nor is another tenant
+        let mut hits = vec![hit("ofac_1", "v1")];
+        apply(&conn, "t2", Some("cust-1"), "Ivan Petrov", &mut hits).unwrap();
+        assert!(!hits[0].suppressed);
+    }
+
+    #[test]
+    fn name_keyed_suppression_ignores_accents_and_case() {
+        let conn = conn();
+        create(
+            &conn,
+            &NewSuppression {
+                tenant_id: "t1",
+                reference_id: None,
+                input_name: "Iván PETROV",
+                subject_id: "ofac_1",
+                record_version: Some("v1"),
+                reason: "cleared",
+                alert_id: None,
+                created_by: "alice",
+            },
+        )
+        .unwrap();
+
+        let mut hits = vec![hit("ofac_1", "v1")];
+        apply(&conn, "t1", None, "ivan petrov", &mut hits).unwrap();
+        assert!(hits[0].suppressed);
+
+        // A customer with a reference of their own is not covered by the name
+        let mut hits = vec![hit("ofac_1", "v1")];
+        apply(&conn, "t1", Some("cust-2"), "Ivan Petrov", &mut hits).unwrap();
+        assert!(!hits[0].suppressed);
+    }
+
+    #[test]
+    fn record_change_invalidates_suppression() {
+        let conn = conn();
+        create(
+            &conn,
+            &NewSuppression {
+                tenant_id: "t1",
+                reference_id: Some("cust-1"),
+                input_name: "Ivan Petrov",
+                subject_id: "ofac_1",
+                record_version: Some("v1"),
+                reason: "cleared",
+                alert_id: None,
+                created_by: "alice",
+            },
+        )
+        .unwrap();
+
+        let mut hits = vec![hit("ofac_1", "v2")];
+        apply(&conn, "t1", Some("cust-1"), "Ivan Petrov", &mut hits).unwrap();
+        assert!(!hits[0].suppressed);
+
+        // Stays invalid even if the old version were seen again
+        let mut hits = vec![hit("ofac_1", "v1")];
+        apply(&conn, "t1", Some("cust-1"), "Ivan Petrov", &mut hits).unwrap();
+        assert!(!hits[0].suppressed);
+
+        assert!(list(&conn, "t1", false).unwrap().is_empty());
+        let all = list(&conn, "t1", true).unwrap();
+        assert_eq!(all[0].invalidated_reason.as_deref(), Some("subject record changed"));
+    }
+}
@@ -1,7 +1,12 @@
 use anyhow::Result;
-use rusqlite::Connection;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
 use std::path::Path;
 
+use crate::audit::{AuditEntry, GENESIS_HASH};
+use crate::auth::Scope;
 use crate::tenant::Tenant;
 
 /// Initialize tenant tables in SQLite
@@ -13,8 +18,6 @@ pub fn init_tenant_schema(conn: &Connection) -> Result<()> {
             name TEXT NOT NULL,
             api_key_hash TEXT NOT NULL,
             is_active INTEGER NOT NULL DEFAULT 1,
-            hit_threshold REAL NOT NULL DEFAULT 0.9,
-            review_threshold REAL NOT NULL DEFAULT 0.75,
             rate_limit_per_minute INTEGER NOT NULL DEFAULT 1000,
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
             updated_at TEXT NOT NULL DEFAULT (datetime('now'))
@@ -26,6 +29,7 @@ pub fn init_tenant_schema(conn: &Connection) -> Result<()> {
             id TEXT PRIMARY KEY,
             tenant_id TEXT NOT NULL REFERENCES tenant(id),
             key_hash TEXT NOT NULL,
+            key_prefix TEXT,
             name TEXT,
             is_active INTEGER NOT NULL DEFAULT 1,
             last_used_at TEXT,
@@ -47,9 +51,47 @@ pub fn init_tenant_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_usage_tenant_time 
             ON usage_log(tenant_id, timestamp DESC);
+
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            request_id TEXT NOT NULL,
+            reference_id TEXT,
+            request_type TEXT NOT NULL,
+            request_payload TEXT NOT NULL,
+            response_payload TEXT NOT NULL,
+            hit_count INTEGER NOT NULL,
+            max_score REAL,
+            processing_time_ms INTEGER NOT NULL,
+            dataset_versions TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            seq INTEGER NOT NULL,
+            prev_hash TEXT NOT NULL,
+            entry_hash TEXT NOT NULL,
+            UNIQUE(tenant_id, seq)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_audit_tenant_time
+            ON audit_log(tenant_id, created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_audit_request
+            ON audit_log(tenant_id, request_id);
         "
     )?;
 
+    ingest::db::add_column_if_missing(conn, "api_key", "key_prefix", "TEXT")?;
+    // Keys issued before scopes existed keep full access
+    ingest::db::add_column_if_missing(
+        conn,
+        "api_key",
+        "scopes",
+        &format!("TEXT NOT NULL DEFAULT '{}'", Scope::join(Scope::ALL)),
+    )?;
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_api_key_prefix ON api_key(key_prefix)")?;
+
+    crate::cases::init_schema(conn)?;
+    crate::suppression::init_schema(conn)?;
+    crate::batch::init_schema(conn)?;
+
     tracing::info!("tenant schema initialized");
     Ok(())
 }
@@ -71,25 +113,66 @@ pub fn open_tenant_db(data_dir: &str) -> Result<Connection> {
     Ok(conn)
 }
 
-/// Hash an API key for storage
-pub fn hash_api_key(key: &str) -> String {
+/// Leading characters of a key stored in clear so a presented key only has
+/// to be checked against a handful of Argon2 hashes.
+const KEY_PREFIX_LEN: usize = 12;
+
+fn key_prefix(key: &str) -> &str {
+    match key.char_indices().nth(KEY_PREFIX_LEN) {
+        Some((end, _)) => &key[..end],
+        None => key,
+    }
+}
+
+/// Hash an API key for storage (Argon2id, PHC string format)
+pub fn hash_api_key(key: &str) -> Result<String> {
+    use rand::RngCore;
+
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt).map_err(|e| anyhow::anyhow!("invalid salt: {}", e))?;
+    let hash = Argon2::default()
+        .hash_password(key.as_bytes(), &salt)
+        .map_err(|e| anyhow::anyhow!("failed to hash API key: {}", e))?;
+    Ok(hash.to_string())
+}
+
+/// Check a presented key against a stored Argon2 hash
+pub fn verify_api_key(key: &str, key_hash: &str) -> bool {
+    match PasswordHash::new(key_hash) {
+        Ok(parsed) => Argon2::default().verify_password(key.as_bytes(), &parsed).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Unsalted hash written by releases before Argon2. Rows still carrying one
+/// are re-hashed the first time their key is used.
+fn legacy_key_hash(key: &str) -> String {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
-    
+
     let mut hasher = DefaultHasher::new();
     key.hash(&mut hasher);
     format!("{:016x}", hasher.finish())
 }
 
-/// Create a new tenant
+/// Generate a new random API key
+pub fn generate_api_key() -> String {
+    use rand::Rng;
+
+    let bytes: [u8; 24] = rand::thread_rng().gen();
+    format!("ak_{}", base64_encode(&bytes))
+}
+
+/// Create a new tenant with its first API key. Returns the key's id.
 pub fn create_tenant(
     conn: &Connection,
     id: &str,
     name: &str,
     api_key: &str,
-) -> Result<()> {
-    let key_hash = hash_api_key(api_key);
-    
+) -> Result<String> {
+    let key_hash = hash_api_key(api_key)?;
+
     conn.execute(
         "INSERT INTO tenant (id, name, api_key_hash) VALUES (?1, ?2, ?3)",
         rusqlite::params![id, name, key_hash],
@@ -98,88 +181,124 @@ pub fn create_tenant(
     // Also create the API key entry
     let key_id = uuid::Uuid::new_v4().to_string();
     conn.execute(
-        "INSERT INTO api_key (id, tenant_id, key_hash, name) VALUES (?1, ?2, ?3, 'default')",
-        rusqlite::params![key_id, id, key_hash],
+        "INSERT INTO api_key (id, tenant_id, key_hash, key_prefix, name, scopes) VALUES (?1, ?2, ?3, ?4, 'default', ?5)",
+        rusqlite::params![key_id, id, key_hash, key_prefix(api_key), Scope::join(Scope::ALL)],
     )?;
 
     tracing::info!(tenant_id = id, name, "created tenant");
+    Ok(key_id)
+}
+
+/// `(id, key_hash)` of the active keys sharing the raw key's prefix. Finding
+/// the key a raw key belongs to takes `match_api_key` over these, then
+/// `find_legacy_key`; expiry and tenant state are checked separately by
+/// `authorize_key`.
+pub fn key_candidates(conn: &Connection, api_key: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, key_hash FROM api_key WHERE key_prefix = ?1 AND is_active = 1",
+    )?;
+    let candidates = stmt
+        .query_map([key_prefix(api_key)], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(candidates)
+}
+
+/// The candidate whose Argon2 hash the raw key verifies against. Slow by
+/// design, so callers cache the result; it needs no connection and can run
+/// after the DB lock is released.
+pub fn match_api_key(api_key: &str, candidates: &[(String, String)]) -> Option<String> {
+    candidates
+        .iter()
+        .find(|(_, hash)| verify_api_key(api_key, hash))
+        .map(|(key_id, _)| key_id.clone())
+}
+
+/// An active key still stored under its pre-Argon2 SHA-256 hash
+pub fn find_legacy_key(conn: &Connection, api_key: &str) -> Result<Option<String>> {
+    let key_id = conn
+        .query_row(
+            "SELECT id FROM api_key WHERE key_prefix IS NULL AND key_hash = ?1 AND is_active = 1",
+            [legacy_key_hash(api_key)],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(key_id)
+}
+
+/// Replace a legacy key's hash with `key_hash`, its `hash_api_key` output
+pub fn upgrade_legacy_key(conn: &Connection, key_id: &str, api_key: &str, key_hash: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE api_key SET key_hash = ?1, key_prefix = ?2 WHERE id = ?3 AND key_prefix IS NULL",
+        rusqlite::params![key_hash, key_prefix(api_key), key_id],
+    )?;
+    tracing::info!(key_id = %key_id, "upgraded legacy API key hash to Argon2id");
     Ok(())
 }
 
-/// Get tenant by API key
-pub fn get_tenant_by_key(conn: &Connection, api_key: &str) -> Option<Tenant> {
-    let key_hash = hash_api_key(api_key);
-    
-    let result = conn.query_row(
-        "SELECT t.id, t.name, ?1, t.is_active, t.hit_threshold, t.review_threshold, t.rate_limit_per_minute
-         FROM tenant t
-         JOIN api_key k ON t.id = k.tenant_id
-         WHERE k.key_hash = ?2 AND k.is_active = 1 AND t.is_active = 1",
-        rusqlite::params![api_key, key_hash],
-        |row| {
-            Ok(Tenant {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                api_key: row.get(2)?,
-                is_active: row.get::<_, i32>(3)? == 1,
-                hit_threshold: row.get(4)?,
-                review_threshold: row.get(5)?,
-                rate_limit_per_minute: row.get(6)?,
-            })
-        },
-    );
+/// What a verified key may do: its tenant and the key's scopes
+#[derive(Debug)]
+pub struct AuthorizedKey {
+    pub tenant: Tenant,
+    pub scopes: Vec<Scope>,
+}
 
-    match result {
-        Ok(tenant) => {
-            // Update last_used_at
-            let _ = conn.execute(
-                "UPDATE api_key SET last_used_at = datetime('now') WHERE key_hash = ?1",
-                [&key_hash],
-            );
-            Some(tenant)
-        }
-        Err(_) => None,
+/// The tenant and scopes of a key, provided the key is still active and
+/// unexpired. Marks the key as used. Suspended tenants are returned so the
+/// caller can tell them apart from bad keys.
+pub fn authorize_key(conn: &Connection, key_id: &str) -> Result<Option<AuthorizedKey>> {
+    let authorized = conn
+        .query_row(
+            "SELECT t.id, t.name, t.is_active, t.rate_limit_per_minute, k.scopes
+             FROM api_key k
+             JOIN tenant t ON t.id = k.tenant_id
+             WHERE k.id = ?1 AND k.is_active = 1
+               AND (k.expires_at IS NULL OR julianday(k.expires_at) > julianday('now'))",
+            [key_id],
+            |row| {
+                Ok(AuthorizedKey {
+                    tenant: tenant_from_row(row)?,
+                    scopes: Scope::split(&row.get::<_, String>(4)?),
+                })
+            },
+        )
+        .optional()?;
+
+    if authorized.is_some() {
+        conn.execute(
+            "UPDATE api_key SET last_used_at = datetime('now') WHERE id = ?1",
+            [key_id],
+        )?;
     }
+    Ok(authorized)
+}
+
+fn tenant_from_row(row: &rusqlite::Row) -> rusqlite::Result<Tenant> {
+    Ok(Tenant {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        is_active: row.get::<_, i32>(2)? == 1,
+        rate_limit_per_minute: row.get(3)?,
+    })
 }
 
 /// Get tenant by ID
 pub fn get_tenant(conn: &Connection, tenant_id: &str) -> Option<Tenant> {
     conn.query_row(
-        "SELECT id, name, api_key_hash, is_active, hit_threshold, review_threshold, rate_limit_per_minute
+        "SELECT id, name, is_active, rate_limit_per_minute
          FROM tenant WHERE id = ?1",
         [tenant_id],
-        |row| {
-            Ok(Tenant {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                api_key: String::new(), // Don't expose the key
-                is_active: row.get::<_, i32>(3)? == 1,
-                hit_threshold: row.get(4)?,
-                review_threshold: row.get(5)?,
-                rate_limit_per_minute: row.get(6)?,
-            })
-        },
+        tenant_from_row,
     ).ok()
 }
 
 /// List all tenants
 pub fn list_tenants(conn: &Connection) -> Result<Vec<Tenant>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, '', is_active, hit_threshold, review_threshold, rate_limit_per_minute
+        "SELECT id, name, is_active, rate_limit_per_minute
          FROM tenant ORDER BY name"
     )?;
 
-    let tenants = stmt.query_map([], |row| {
-        Ok(Tenant {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            api_key: String::new(),
-            is_active: row.get::<_, i32>(3)? == 1,
-            hit_threshold: row.get(4)?,
-            review_threshold: row.get(5)?,
-            rate_limit_per_minute: row.get(6)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+    let tenants = stmt.query_map([], tenant_from_row)?.collect::<Result<Vec<_>, _>>()?;
 
     Ok(tenants)
 }
@@ -190,8 +309,6 @@ pub fn update_tenant(
     tenant_id: &str,
     name: Option<&str>,
     is_active: Option<bool>,
-    hit_threshold: Option<f32>,
-    review_threshold: Option<f32>,
     rate_limit: Option<u32>,
 ) -> Result<bool> {
     let mut updates = Vec::new();
@@ -205,14 +322,6 @@ pub fn update_tenant(
         updates.push("is_active = ?");
         params.push(Box::new(a as i32));
     }
-    if let Some(h) = hit_threshold {
-        updates.push("hit_threshold = ?");
-        params.push(Box::new(h));
-    }
-    if let Some(r) = review_threshold {
-        updates.push("review_threshold = ?");
-        params.push(Box::new(r));
-    }
     if let Some(l) = rate_limit {
         updates.push("rate_limit_per_minute = ?");
         params.push(Box::new(l as i32));
@@ -236,76 +345,274 @@ pub fn update_tenant(
     Ok(rows > 0)
 }
 
+/// A freshly issued key. `api_key` is the only copy of the plaintext.
+#[derive(Debug)]
+pub struct IssuedKey {
+    pub key_id: String,
+    pub api_key: String,
+}
+
 /// Create a new API key for a tenant
 pub fn create_api_key(
     conn: &Connection,
     tenant_id: &str,
     name: Option<&str>,
     expires_at: Option<&str>,
-) -> Result<String> {
-    use rand::Rng;
-    
-    // Generate a random API key
-    let mut rng = rand::thread_rng();
-    let bytes: [u8; 24] = rng.gen();
-    let key = format!("ak_{}", base64_encode(&bytes));
-    let key_hash = hash_api_key(&key);
+    scopes: &[Scope],
+) -> Result<IssuedKey> {
+    let key = generate_api_key();
+    let key_hash = hash_api_key(&key)?;
     
     let key_id = uuid::Uuid::new_v4().to_string();
     
     conn.execute(
-        "INSERT INTO api_key (id, tenant_id, key_hash, name, expires_at) 
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        rusqlite::params![key_id, tenant_id, key_hash, name, expires_at],
+        "INSERT INTO api_key (id, tenant_id, key_hash, key_prefix, name, expires_at, scopes) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![key_id, tenant_id, key_hash, key_prefix(&key), name, expires_at, Scope::join(scopes)],
     )?;
 
     tracing::info!(tenant_id, key_id, "created new API key");
-    Ok(key)
+    Ok(IssuedKey { key_id, api_key: key })
+}
+
+/// Key metadata safe to show to administrators; never includes the hash
+#[derive(Debug, Serialize)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub tenant_id: String,
+    pub name: Option<String>,
+    /// Leading characters of the key, to tell keys apart
+    pub key_prefix: Option<String>,
+    pub scopes: Vec<Scope>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub expires_at: Option<String>,
 }
 
-/// Revoke an API key
-pub fn revoke_api_key(conn: &Connection, key_id: &str) -> Result<bool> {
+/// List a tenant's API keys, including revoked ones
+pub fn list_api_keys(conn: &Connection, tenant_id: &str) -> Result<Vec<ApiKeyInfo>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, tenant_id, name, key_prefix, is_active, created_at, last_used_at, expires_at, scopes
+         FROM api_key WHERE tenant_id = ?1 ORDER BY created_at, rowid",
+    )?;
+    let keys = stmt
+        .query_map([tenant_id], |row| {
+            Ok(ApiKeyInfo {
+                id: row.get(0)?,
+                tenant_id: row.get(1)?,
+                name: row.get(2)?,
+                key_prefix: row.get(3)?,
+                scopes: Scope::split(&row.get::<_, String>(8)?),
+                is_active: row.get::<_, i32>(4)? == 1,
+                created_at: row.get(5)?,
+                last_used_at: row.get(6)?,
+                expires_at: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(keys)
+}
+
+/// Revoke one of a tenant's API keys
+pub fn revoke_api_key(conn: &Connection, tenant_id: &str, key_id: &str) -> Result<bool> {
     let rows = conn.execute(
-        "UPDATE api_key SET is_active = 0 WHERE id = ?1",
-        [key_id],
+        "UPDATE api_key SET is_active = 0 WHERE tenant_id = ?1 AND id = ?2 AND is_active = 1",
+        [tenant_id, key_id],
     )?;
     Ok(rows > 0)
 }
 
-/// Log API usage
+/// Log API usage. `request_count` is the billable count, e.g. the number of
+/// records in a batch.
 pub fn log_usage(
     conn: &Connection,
     tenant_id: &str,
     api_key_id: Option<&str>,
     endpoint: &str,
+    request_count: i64,
 ) -> Result<()> {
     conn.execute(
-        "INSERT INTO usage_log (tenant_id, api_key_id, endpoint) VALUES (?1, ?2, ?3)",
-        rusqlite::params![tenant_id, api_key_id, endpoint],
+        "INSERT INTO usage_log (tenant_id, api_key_id, endpoint, request_count) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![tenant_id, api_key_id, endpoint, request_count],
     )?;
     Ok(())
 }
 
-/// Get usage stats for a tenant
+/// Billable usage of one endpoint through one API key
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageStat {
+    pub endpoint: String,
+    pub api_key_id: Option<String>,
+    pub request_count: i64,
+}
+
+/// A tenant's usage from `since` (inclusive) until `until` (exclusive), both
+/// `YYYY-MM-DD HH:MM:SS` in UTC as `usage_log` stores them
 pub fn get_usage_stats(
     conn: &Connection,
     tenant_id: &str,
     since: &str,
-) -> Result<Vec<(String, i64)>> {
+    until: Option<&str>,
+) -> Result<Vec<UsageStat>> {
     let mut stmt = conn.prepare(
-        "SELECT endpoint, SUM(request_count) as count
+        "SELECT endpoint, api_key_id, SUM(request_count) as count
          FROM usage_log 
-         WHERE tenant_id = ?1 AND timestamp >= ?2
-         GROUP BY endpoint"
+         WHERE tenant_id = ?1 AND timestamp >= ?2 AND (?3 IS NULL OR timestamp < ?3)
+         GROUP BY endpoint, api_key_id
+         ORDER BY endpoint, api_key_id"
     )?;
 
-    let stats = stmt.query_map(rusqlite::params![tenant_id, since], |row| {
-        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    let stats = stmt.query_map(rusqlite::params![tenant_id, since, until], |row| {
+        Ok(UsageStat {
+            endpoint: row.get(0)?,
+            api_key_id: row.get(1)?,
+            request_count: row.get(2)?,
+        })
     })?.collect::<Result<Vec<_>, _>>()?;
 
     Ok(stats)
 }
 
+/// Append a screening to the audit trail, linking it to the tenant's previous
+/// entry. Fills in `seq`, `prev_hash` and `entry_hash` on `entry`.
+pub fn insert_audit_entry(conn: &Connection, entry: &mut AuditEntry) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    let (last_seq, last_hash) = match tx.query_row(
+        "SELECT seq, entry_hash FROM audit_log WHERE tenant_id = ?1 ORDER BY seq DESC LIMIT 1",
+        [&entry.tenant_id],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+    ) {
+        Ok(last) => last,
+        Err(rusqlite::Error::QueryReturnedNoRows) => (0, GENESIS_HASH.to_string()),
+        Err(e) => return Err(e.into()),
+    };
+    entry.seq = last_seq + 1;
+    entry.prev_hash = last_hash;
+    entry.entry_hash = entry.compute_hash();
+
+    tx.execute(
+        "INSERT INTO audit_log (id, tenant_id, request_id, reference_id, request_type,
+                                request_payload, response_payload, hit_count, max_score,
+                                processing_time_ms, dataset_versions, created_at,
+                                seq, prev_hash, entry_hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        rusqlite::params![
+            entry.id,
+            entry.tenant_id,
+            entry.request_id,
+            entry.reference_id,
+            entry.request_type,
+            entry.request_payload,
+            entry.response_payload,
+            entry.hit_count,
+            entry.max_score,
+            entry.processing_time_ms,
+            serde_json::to_string(&entry.dataset_versions)?,
+            entry.created_at,
+            entry.seq,
+            entry.prev_hash,
+            entry.entry_hash,
+        ],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+const AUDIT_COLUMNS: &str = "id, tenant_id, request_id, reference_id, request_type, request_payload,
+     response_payload, hit_count, max_score, processing_time_ms, dataset_versions, created_at,
+     seq, prev_hash, entry_hash";
+
+fn audit_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<AuditEntry> {
+    let dataset_versions: String = row.get(10)?;
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        tenant_id: row.get(1)?,
+        request_id: row.get(2)?,
+        reference_id: row.get(3)?,
+        request_type: row.get(4)?,
+        request_payload: row.get(5)?,
+        response_payload: row.get(6)?,
+        hit_count: row.get(7)?,
+        max_score: row.get(8)?,
+        processing_time_ms: row.get(9)?,
+        dataset_versions: serde_json::from_str(&dataset_versions).unwrap_or_default(),
+        created_at: row.get(11)?,
+        seq: row.get(12)?,
+        prev_hash: row.get(13)?,
+        entry_hash: row.get(14)?,
+    })
+}
+
+/// Page through a tenant's audit trail, newest first, from `from`
+/// (inclusive) until `until` (exclusive). Bounds must be RFC 3339 in UTC with
+/// a `+00:00` offset, as `created_at` is stored, since they compare as strings.
+/// Returns the page and the total number of matching rows.
+pub fn get_audit_entries(
+    conn: &Connection,
+    tenant_id: &str,
+    from: Option<&str>,
+    until: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<AuditEntry>, i64)> {
+    let filter = "tenant_id = ?1
+         AND (?2 IS NULL OR created_at >= ?2)
+         AND (?3 IS NULL OR created_at < ?3)";
+
+    let total = conn.query_row(
+        &format!("SELECT COUNT(*) FROM audit_log WHERE {}", filter),
+        rusqlite::params![tenant_id, from, until],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM audit_log WHERE {} ORDER BY created_at DESC, rowid DESC LIMIT ?4 OFFSET ?5",
+        AUDIT_COLUMNS, filter
+    ))?;
+    let entries = stmt
+        .query_map(
+            rusqlite::params![tenant_id, from, until, limit, offset],
+            audit_entry_from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((entries, total))
+}
+
+/// A tenant's whole audit trail in chain order, for verification
+pub fn get_audit_chain(conn: &Connection, tenant_id: &str) -> Result<Vec<AuditEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM audit_log WHERE tenant_id = ?1 ORDER BY seq",
+        AUDIT_COLUMNS
+    ))?;
+    let entries = stmt
+        .query_map([tenant_id], audit_entry_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(entries)
+}
+
+/// Look up one audit row by the request_id returned to the client
+pub fn get_audit_entry_by_request_id(
+    conn: &Connection,
+    tenant_id: &str,
+    request_id: &str,
+) -> Result<Option<AuditEntry>> {
+    match conn.query_row(
+        &format!(
+            "SELECT {} FROM audit_log WHERE tenant_id = ?1 AND request_id = ?2",
+            AUDIT_COLUMNS
+        ),
+        [tenant_id, request_id],
+        audit_entry_from_row,
+    ) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 fn base64_encode(data: &[u8]) -> String {
     const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
     let mut result = String::new();
@@ -344,6 +651,28 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    /// The active `api_key` row matching a raw key, the way auth finds it
+    fn find_api_key(conn: &Connection, api_key: &str) -> Result<Option<String>> {
+        if let Some(key_id) = match_api_key(api_key, &key_candidates(conn, api_key)?) {
+            return Ok(Some(key_id));
+        }
+        let legacy = find_legacy_key(conn, api_key)?;
+        if let Some(key_id) = &legacy {
+            upgrade_legacy_key(conn, key_id, api_key, &hash_api_key(api_key)?)?;
+        }
+        Ok(legacy)
+    }
+
+    /// The active tenant a raw API key authenticates as, the way auth resolves it
+    fn tenant_for_key(conn: &Connection, api_key: &str) -> Option<Tenant> {
+        let key_id = find_api_key(conn, api_key).ok().flatten()?;
+        authorize_key(conn, &key_id)
+            .ok()
+            .flatten()
+            .map(|k| k.tenant)
+            .filter(|t| t.is_active)
+    }
+
     #[test]
     fn tenant_crud() {
         let dir = tempdir().unwrap();
@@ -353,19 +682,19 @@ mod tests {
         create_tenant(&conn, "t1", "Test Tenant", "my-secret-key").unwrap();
 
         // Get by key
-        let tenant = get_tenant_by_key(&conn, "my-secret-key").unwrap();
+        let tenant = tenant_for_key(&conn, "my-secret-key").unwrap();
         assert_eq!(tenant.id, "t1");
         assert_eq!(tenant.name, "Test Tenant");
 
         // Invalid key
-        assert!(get_tenant_by_key(&conn, "wrong-key").is_none());
+        assert!(tenant_for_key(&conn, "wrong-key").is_none());
 
         // List tenants
         let tenants = list_tenants(&conn).unwrap();
         assert_eq!(tenants.len(), 1);
 
         // Update
-        update_tenant(&conn, "t1", Some("New Name"), None, None, None, None).unwrap();
+        update_tenant(&conn, "t1", Some("New Name"), None, None).unwrap();
         let tenant = get_tenant(&conn, "t1").unwrap();
         assert_eq!(tenant.name, "New Name");
     }
@@ -378,14 +707,160 @@ mod tests {
         create_tenant(&conn, "t1", "Test", "initial-key").unwrap();
 
         // Create new key
-        let new_key = create_api_key(&conn, "t1", Some("Production"), None).unwrap();
+        let new_key = create_api_key(&conn, "t1", Some("Production"), None, Scope::ALL).unwrap().api_key;
         assert!(new_key.starts_with("ak_"));
 
         // Both keys should work
-        assert!(get_tenant_by_key(&conn, "initial-key").is_some());
-        assert!(get_tenant_by_key(&conn, &new_key).is_some());
+        assert!(tenant_for_key(&conn, "initial-key").is_some());
+        assert!(tenant_for_key(&conn, &new_key).is_some());
+
+        // Keys are stored as salted Argon2id hashes, never in clear
+        let stored: String = conn
+            .query_row("SELECT key_hash FROM api_key WHERE name = 'Production'", [], |row| row.get(0))
+            .unwrap();
+        assert!(stored.starts_with("$argon2id$"));
+        assert!(!stored.contains(&new_key));
+
+        // Rotating: revoke the old key, the new one keeps working
+        let old_id = find_api_key(&conn, "initial-key").unwrap().unwrap();
+        assert!(!revoke_api_key(&conn, "t2", &old_id).unwrap());
+        assert!(revoke_api_key(&conn, "t1", &old_id).unwrap());
+        assert!(tenant_for_key(&conn, "initial-key").is_none());
+        let new_id = find_api_key(&conn, &new_key).unwrap().unwrap();
+        assert_eq!(authorize_key(&conn, &new_id).unwrap().unwrap().tenant.id, "t1");
+        let last_used: Option<String> = conn
+            .query_row("SELECT last_used_at FROM api_key WHERE id = ?1", [&new_id], |row| row.get(0))
+            .unwrap();
+        assert!(last_used.is_some());
     }
-}
 
+    #[test]
+    fn expired_keys_are_rejected() {
+        let dir = tempdir().unwrap();
+        let conn = open_tenant_db(dir.path().to_str().unwrap()).unwrap();
+
+        create_tenant(&conn, "t1", "Test", "initial-key").unwrap();
+        let expired = create_api_key(&conn, "t1", None, Some("2020-01-01T00:00:00Z"), Scope::ALL).unwrap().api_key;
+        let future = create_api_key(&conn, "t1", None, Some("2999-01-01T00:00:00Z"), Scope::ALL).unwrap().api_key;
 
+        assert!(tenant_for_key(&conn, &expired).is_none());
+        assert!(tenant_for_key(&conn, &future).is_some());
+    }
 
+    #[test]
+    fn legacy_hashes_are_upgraded_on_use() {
+        let dir = tempdir().unwrap();
+        let conn = open_tenant_db(dir.path().to_str().unwrap()).unwrap();
+
+        create_tenant(&conn, "t1", "Test", "initial-key").unwrap();
+        conn.execute(
+            "INSERT INTO api_key (id, tenant_id, key_hash) VALUES ('old', 't1', ?1)",
+            [legacy_key_hash("legacy-key")],
+        )
+        .unwrap();
+
+        assert_eq!(find_api_key(&conn, "legacy-key").unwrap().as_deref(), Some("old"));
+        let (hash, prefix): (String, Option<String>) = conn
+            .query_row("SELECT key_hash, key_prefix FROM api_key WHERE id = 'old'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert!(verify_api_key("legacy-key", &hash));
+        assert_eq!(prefix.as_deref(), Some("legacy-key"));
+        assert_eq!(find_api_key(&conn, "legacy-key").unwrap().as_deref(), Some("old"));
+    }
+
+    #[test]
+    fn usage_is_counted_per_endpoint() {
+        let dir = tempdir().unwrap();
+        let conn = open_tenant_db(dir.path().to_str().unwrap()).unwrap();
+
+        create_tenant(&conn, "t1", "Test", "initial-key").unwrap();
+        let key_id = find_api_key(&conn, "initial-key").unwrap().unwrap();
+        log_usage(&conn, "t1", Some(&key_id), "POST /v1/persons/screen", 1).unwrap();
+        log_usage(&conn, "t1", Some(&key_id), "POST /v1/persons/screen", 1).unwrap();
+        log_usage(&conn, "t1", Some(&key_id), "POST /v1/batch", 250).unwrap();
+        log_usage(&conn, "t2", None, "POST /v1/batch", 10).unwrap();
+
+        let stats = get_usage_stats(&conn, "t1", "2000-01-01 00:00:00", None).unwrap();
+        let counts: Vec<(&str, Option<&str>, i64)> = stats
+            .iter()
+            .map(|s| (s.endpoint.as_str(), s.api_key_id.as_deref(), s.request_count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("POST /v1/batch", Some(key_id.as_str()), 250),
+                ("POST /v1/persons/screen", Some(key_id.as_str()), 2),
+            ]
+        );
+        assert!(get_usage_stats(&conn, "t1", "2000-01-01 00:00:00", Some("2000-01-02 00:00:00")).unwrap().is_empty());
+    }
+
+    fn audit_entry(tenant_id: &str, request_id: &str, created_at: &str) -> AuditEntry {
+        AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            tenant_id: tenant_id.to_string(),
+            request_id: request_id.to_string(),
+            reference_id: None,
+            request_type: "person".to_string(),
+            request_payload: "{}".to_string(),
+            response_payload: "{}".to_string(),
+            hit_count: 1,
+            max_score: Some(0.97),
+            processing_time_ms: 3,
+            dataset_versions: vec![4, 7],
+            created_at: created_at.to_string(),
+            seq: 0,
+            prev_hash: String::new(),
+            entry_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn audit_trail_filters_and_pages() {
+        let dir = tempdir().unwrap();
+        let conn = open_tenant_db(dir.path().to_str().unwrap()).unwrap();
+
+        insert_audit_entry(&conn, &mut audit_entry("t1", "r1", "2024-01-10T09:00:00+00:00")).unwrap();
+        insert_audit_entry(&conn, &mut audit_entry("t1", "r2", "2024-02-10T09:00:00+00:00")).unwrap();
+        insert_audit_entry(&conn, &mut audit_entry("t1", "r3", "2024-03-10T09:00:00+00:00")).unwrap();
+        insert_audit_entry(&conn, &mut audit_entry("t2", "r4", "2024-02-10T09:00:00+00:00")).unwrap();
+
+        let (page, total) = get_audit_entries(&conn, "t1", None, None, 2, 0).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page.iter().map(|e| e.request_id.as_str()).collect::<Vec<_>>(), ["r3", "r2"]);
+
+        let (page, total) =
+            get_audit_entries(&conn, "t1", Some("2024-02-01T00:00:00+00:00"), Some("2024-03-01T00:00:00+00:00"), 50, 0)
+                .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(page[0].request_id, "r2");
+        assert_eq!(page[0].dataset_versions, vec![4, 7]);
+
+        assert!(get_audit_entry_by_request_id(&conn, "t1", "r4").unwrap().is_none());
+        assert!(get_audit_entry_by_request_id(&conn, "t2", "r4").unwrap().is_some());
+    }
+
+    #[test]
+    fn audit_chain_detects_tampering_in_db() {
+        let dir = tempdir().unwrap();
+        let conn = open_tenant_db(dir.path().to_str().unwrap()).unwrap();
+
+        for (i, day) in ["01", "02", "03"].iter().enumerate() {
+            let created_at = format!("2024-01-{}T09:00:00+00:00", day);
+            insert_audit_entry(&conn, &mut audit_entry("t1", &format!("r{}", i + 1), &created_at)).unwrap();
+        }
+        // Another tenant's rows form their own chain
+        insert_audit_entry(&conn, &mut audit_entry("t2", "x1", "2024-01-02T09:00:00+00:00")).unwrap();
+
+        let chain = get_audit_chain(&conn, "t1").unwrap();
+        assert_eq!(chain[0].prev_hash, GENESIS_HASH);
+        assert!(crate::audit::verify_chain(&chain).valid);
+        assert!(crate::audit::verify_chain(&get_audit_chain(&conn, "t2").unwrap()).valid);
+
+        conn.execute("UPDATE audit_log SET max_score = 0.1 WHERE request_id = 'r2'", []).unwrap();
+        let result = crate::audit::verify_chain(&get_audit_chain(&conn, "t1").unwrap());
+        assert_eq!(result.first_broken.map(|b| b.request_id), Some("r2".to_string()));
+    }
+}
@@ -0,0 +1,125 @@
+use aegistry_core::{new_request_id, Hit, IdentifierType, ScreenIdentifier, SubjectKind};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    Extension, Json,
+};
+use chrono::Utc;
+use matching_core::ScreeningQuery;
+use metrics::{counter, histogram};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::auth::ApiKeyAuth;
+use crate::{audit, cases, risk, ApiError, AppState};
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ScreenVesselRequest {
+    pub reference_id: Option<String>,
+    /// Current name of the ship; former names it was listed under also match
+    #[validate(length(min = 1, max = 256))]
+    pub name: Option<String>,
+    /// IMO ship identification number, with or without the "IMO" prefix
+    #[validate(length(min = 7, max = 16))]
+    pub imo: Option<String>,
+    /// Flag state, ISO 3166 alpha-2
+    #[validate(length(min = 2, max = 2))]
+    pub flag: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScreenVesselResponse {
+    pub request_id: String,
+    pub reference_id: Option<String>,
+    pub hits: Vec<Hit>,
+    pub checked_at: String,
+}
+
+/// Screen a ship against listed vessels by IMO number and by current and
+/// former names. Either is enough; an IMO match is a hit whatever the name.
+pub async fn screen_vessel(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiKeyAuth>,
+    headers: HeaderMap,
+    Json(req): Json<ScreenVesselRequest>,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    let start = Instant::now();
+    counter!("screening_requests_total", "type" => "vessel").increment(1);
+
+    if let Err(e) = req.validate() {
+        counter!("screening_errors_total", "type" => "validation").increment(1);
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(ApiError::from_validation(e))));
+    }
+    if req.name.is_none() && req.imo.is_none() {
+        counter!("screening_errors_total", "type" => "validation").increment(1);
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiError {
+                message: "invalid_request".to_string(),
+                details: vec!["name or imo is required".to_string()],
+            }),
+        ));
+    }
+
+    let risk_config = state
+        .risk_store
+        .get_config(&auth.tenant_id)
+        .await
+        .map_err(risk::risk_store_error)?;
+
+    // A ship keeps its IMO number through renames, so without a reference it
+    // is the better key for suppressions and cases than the name
+    let label = match &req.imo {
+        Some(imo) => format!("IMO {}", imo.trim()),
+        None => req.name.clone().unwrap_or_default(),
+    };
+    let query = ScreeningQuery {
+        name: req.name.clone().unwrap_or_default(),
+        country: req.flag.clone(),
+        dob: None,
+        kind: Some(SubjectKind::Vessel),
+        identifiers: req
+            .imo
+            .iter()
+            .map(|imo| ScreenIdentifier {
+                id_type: IdentifierType::Imo,
+                number: imo.clone(),
+            })
+            .collect(),
+    };
+    let hits = crate::perform_screening(&state, &risk_config, req.reference_id.as_deref(), &label, query).await?;
+
+    let response = ScreenVesselResponse {
+        request_id: new_request_id(),
+        reference_id: req.reference_id.clone(),
+        hits,
+        checked_at: Utc::now().to_rfc3339(),
+    };
+
+    histogram!("screening_latency_seconds", "type" => "vessel").record(start.elapsed().as_secs_f64());
+
+    let entry = audit::AuditEntry::screening(
+        &auth.tenant_id,
+        "vessel",
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &response.hits,
+        start.elapsed(),
+    )
+    .with_payloads(&req, &response);
+    audit::record(&state, entry).await;
+    cases::record(
+        &state,
+        &auth.tenant_id,
+        &response.request_id,
+        response.reference_id.as_deref(),
+        &label,
+        &response.hits,
+    )
+    .await;
+
+    crate::format_response(&headers, &response)
+}